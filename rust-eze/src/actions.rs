@@ -0,0 +1,598 @@
+use bevy::input::gamepad::{GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType};
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::mem::discriminant;
+use std::path::{Path, PathBuf};
+
+use crate::status::Element;
+
+/// Versão atual do arquivo de controles.
+pub const BINDINGS_VERSION: u32 = 1;
+
+/// Nome do arquivo de controles dentro da pasta de dados do usuário.
+pub const BINDINGS_FILE: &str = "bindings.ron";
+
+/// Inclinação mínima de um stick para contar como movimento ou mira.
+pub const STICK_DEADZONE: f32 = 0.2;
+
+/// Plugin que traduz teclado, mouse e controle em ações do jogo.
+///
+/// Os sistemas de jogo leem só o `ActionState`, nunca as teclas. Os controles
+/// ficam no `InputBindings` e só são lidos e gravados em disco quando existe
+/// um `BindingsFile`.
+pub struct ActionsPlugin;
+
+impl Plugin for ActionsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<InputBindings>()
+            .init_resource::<ActionState>()
+            .add_systems(Startup, load_bindings.run_if(resource_exists::<BindingsFile>))
+            .add_systems(PreUpdate, update_actions.in_set(ActionSet).after(InputSystem));
+    }
+}
+
+/// Conjunto em que o `ActionState` do quadro é montado, no `PreUpdate`.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ActionSet;
+
+/// Ações do jogo, cada uma ligada a teclas e botões no `InputBindings`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    AimUp,
+    AimDown,
+    AimLeft,
+    AimRight,
+    Fire,
+    Dash,
+    PreviousElement,
+    NextElement,
+    SelectElement(Element),
+    NextWeapon,
+    Reload,
+    Map,
+    QuickSave,
+    Pause,
+    Confirm,
+    Options,
+}
+
+impl Action {
+    /// Todas as ações, na ordem do menu de opções.
+    pub const ALL: [Action; 24] = [
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::AimUp,
+        Action::AimDown,
+        Action::AimLeft,
+        Action::AimRight,
+        Action::Fire,
+        Action::Dash,
+        Action::PreviousElement,
+        Action::NextElement,
+        Action::SelectElement(Element::Standard),
+        Action::SelectElement(Element::Fire),
+        Action::SelectElement(Element::Ice),
+        Action::SelectElement(Element::Electric),
+        Action::SelectElement(Element::Explosive),
+        Action::NextWeapon,
+        Action::Reload,
+        Action::Map,
+        Action::QuickSave,
+        Action::Pause,
+        Action::Confirm,
+        Action::Options,
+    ];
+
+    /// Nome mostrado no menu de opções.
+    pub fn label(&self) -> String {
+        let label = match self {
+            Action::MoveUp => "Move up",
+            Action::MoveDown => "Move down",
+            Action::MoveLeft => "Move left",
+            Action::MoveRight => "Move right",
+            Action::AimUp => "Aim up",
+            Action::AimDown => "Aim down",
+            Action::AimLeft => "Aim left",
+            Action::AimRight => "Aim right",
+            Action::Fire => "Fire",
+            Action::Dash => "Dash",
+            Action::PreviousElement => "Previous element",
+            Action::NextElement => "Next element",
+            Action::SelectElement(element) => return format!("Element: {element:?}"),
+            Action::NextWeapon => "Next weapon",
+            Action::Reload => "Reload",
+            Action::Map => "Map",
+            Action::QuickSave => "Quick save",
+            Action::Pause => "Pause / back",
+            Action::Confirm => "Confirm",
+            Action::Options => "Options",
+        };
+        label.to_string()
+    }
+
+    /// Controles de fábrica da ação.
+    pub fn default_bindings(&self) -> Vec<Binding> {
+        use Binding::{Key, Mouse, Pad};
+        match self {
+            Action::MoveUp => vec![Key(KeyCode::KeyW), Pad(GamepadButtonType::DPadUp)],
+            Action::MoveDown => vec![Key(KeyCode::KeyS), Pad(GamepadButtonType::DPadDown)],
+            Action::MoveLeft => vec![Key(KeyCode::KeyA), Pad(GamepadButtonType::DPadLeft)],
+            Action::MoveRight => vec![Key(KeyCode::KeyD), Pad(GamepadButtonType::DPadRight)],
+            Action::AimUp => vec![Key(KeyCode::ArrowUp)],
+            Action::AimDown => vec![Key(KeyCode::ArrowDown)],
+            Action::AimLeft => vec![Key(KeyCode::ArrowLeft)],
+            Action::AimRight => vec![Key(KeyCode::ArrowRight)],
+            Action::Fire => vec![Key(KeyCode::Space), Mouse(MouseButton::Left), Pad(GamepadButtonType::RightTrigger2)],
+            Action::Dash => vec![Key(KeyCode::ShiftLeft), Mouse(MouseButton::Right), Pad(GamepadButtonType::East)],
+            Action::PreviousElement => vec![Key(KeyCode::KeyQ), Pad(GamepadButtonType::LeftTrigger)],
+            Action::NextElement => vec![Key(KeyCode::KeyE), Pad(GamepadButtonType::RightTrigger)],
+            Action::SelectElement(element) => {
+                let key = match element {
+                    Element::Standard => KeyCode::Digit1,
+                    Element::Fire => KeyCode::Digit2,
+                    Element::Ice => KeyCode::Digit3,
+                    Element::Electric => KeyCode::Digit4,
+                    Element::Explosive => KeyCode::Digit5,
+                };
+                vec![Key(key)]
+            }
+            Action::NextWeapon => vec![Key(KeyCode::Tab), Pad(GamepadButtonType::North)],
+            Action::Reload => vec![Key(KeyCode::KeyR), Pad(GamepadButtonType::West)],
+            Action::Map => vec![Key(KeyCode::KeyM), Pad(GamepadButtonType::LeftTrigger2)],
+            Action::QuickSave => vec![Key(KeyCode::F5)],
+            Action::Pause => vec![Key(KeyCode::Escape), Pad(GamepadButtonType::Start)],
+            Action::Confirm => vec![Key(KeyCode::Enter), Pad(GamepadButtonType::South)],
+            Action::Options => vec![Key(KeyCode::KeyO), Pad(GamepadButtonType::Select)],
+        }
+    }
+}
+
+/// Ações analógicas, cada uma ligada a um stick no `InputBindings`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StickAction {
+    Move,
+    Aim,
+}
+
+impl StickAction {
+    /// Todas as ações analógicas, na ordem do menu de opções.
+    pub const ALL: [StickAction; 2] = [StickAction::Move, StickAction::Aim];
+
+    /// Nome mostrado no menu de opções.
+    pub fn label(&self) -> &'static str {
+        match self {
+            StickAction::Move => "Move (stick)",
+            StickAction::Aim => "Aim (stick)",
+        }
+    }
+
+    /// Stick de fábrica da ação.
+    pub fn default_stick(&self) -> Stick {
+        match self {
+            StickAction::Move => Stick::Left,
+            StickAction::Aim => Stick::Right,
+        }
+    }
+}
+
+/// Um dos sticks do controle.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stick {
+    Left,
+    Right,
+}
+
+impl Stick {
+    pub const ALL: [Stick; 2] = [Stick::Left, Stick::Right];
+
+    /// Nome mostrado no menu de opções.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Stick::Left => "Left stick",
+            Stick::Right => "Right stick",
+        }
+    }
+
+    fn axes(&self) -> (GamepadAxisType, GamepadAxisType) {
+        match self {
+            Stick::Left => (GamepadAxisType::LeftStickX, GamepadAxisType::LeftStickY),
+            Stick::Right => (GamepadAxisType::RightStickX, GamepadAxisType::RightStickY),
+        }
+    }
+
+    /// Inclinação do stick no controle dado, zero dentro da zona morta.
+    pub fn tilt(&self, pad_axes: &Axis<GamepadAxis>, pad: Gamepad) -> Vec2 {
+        let (x, y) = self.axes();
+        let value = |axis_type| pad_axes.get(GamepadAxis::new(pad, axis_type)).unwrap_or(0.0);
+        let tilt = Vec2::new(value(x), value(y));
+        if tilt.length() > STICK_DEADZONE { tilt } else { Vec2::ZERO }
+    }
+}
+
+/// Controle conectado de menor id, o único cujos sticks valem. O `Axis`
+/// só tem eixos de controles conectados.
+pub fn first_gamepad(pad_axes: &Axis<GamepadAxis>) -> Option<Gamepad> {
+    pad_axes.devices().map(|axis| axis.gamepad).min_by_key(|pad| pad.id)
+}
+
+/// Ações de movimento e a direção de cada uma.
+const MOVE_ACTIONS: [(Action, Vec2); 4] = [
+    (Action::MoveUp, Vec2::Y),
+    (Action::MoveDown, Vec2::NEG_Y),
+    (Action::MoveLeft, Vec2::NEG_X),
+    (Action::MoveRight, Vec2::X),
+];
+
+/// Ações de mira e a direção de cada uma.
+const AIM_ACTIONS: [(Action, Vec2); 4] = [
+    (Action::AimUp, Vec2::Y),
+    (Action::AimDown, Vec2::NEG_Y),
+    (Action::AimLeft, Vec2::NEG_X),
+    (Action::AimRight, Vec2::X),
+];
+
+/// Uma tecla, um botão do mouse ou um botão de qualquer controle conectado.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Pad(GamepadButtonType),
+}
+
+impl Binding {
+    /// Nome mostrado no menu de opções.
+    pub fn label(&self) -> String {
+        match self {
+            Binding::Key(key) => {
+                let name = format!("{key:?}");
+                name.strip_prefix("Key").or_else(|| name.strip_prefix("Digit")).unwrap_or(&name).to_string()
+            }
+            Binding::Mouse(button) => format!("Mouse {button:?}"),
+            Binding::Pad(button) => format!("Pad {button:?}"),
+        }
+    }
+}
+
+/// Recurso com os controles de cada ação e o stick de cada ação analógica.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct InputBindings {
+    bindings: HashMap<Action, Vec<Binding>>,
+    sticks: HashMap<StickAction, Stick>,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        InputBindings {
+            bindings: Action::ALL.iter().map(|action| (*action, action.default_bindings())).collect(),
+            sticks: StickAction::ALL.iter().map(|action| (*action, action.default_stick())).collect(),
+        }
+    }
+}
+
+/// Formato do arquivo, com as ações na ordem do menu.
+#[derive(Serialize, Deserialize)]
+struct BindingsData {
+    version: u32,
+    actions: Vec<ActionBindings>,
+    #[serde(default)]
+    sticks: Vec<StickBinding>, // Sticks que faltam ficam com os de fábrica
+}
+
+#[derive(Serialize, Deserialize)]
+struct ActionBindings {
+    action: Action,
+    inputs: Vec<Binding>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StickBinding {
+    action: StickAction,
+    stick: Stick,
+}
+
+impl InputBindings {
+    pub fn get(&self, action: Action) -> &[Binding] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn stick(&self, action: StickAction) -> Option<Stick> {
+        self.sticks.get(&action).copied()
+    }
+
+    /// Liga `binding` à ação. Substitui o controle do mesmo tipo (tecla,
+    /// mouse ou controle) que a ação já tinha, e mantém os outros.
+    ///
+    /// Se outra ação já usava `binding`, as duas trocam: a outra fica com o
+    /// controle substituído, ou sem nenhum desse tipo. Devolve a outra ação.
+    pub fn bind(&mut self, action: Action, binding: Binding) -> Option<Action> {
+        let inputs = self.bindings.entry(action).or_default();
+        let mut replaced = inputs
+            .iter()
+            .position(|other| discriminant(other) == discriminant(&binding))
+            .map(|index| inputs.remove(index));
+        inputs.push(binding);
+        if replaced == Some(binding) {
+            return None;
+        }
+
+        let mut swapped = None;
+        for other in Action::ALL {
+            let Some(inputs) = self.bindings.get_mut(&other) else {
+                continue;
+            };
+            if other == action || !inputs.contains(&binding) {
+                continue;
+            }
+            inputs.retain(|input| *input != binding);
+            inputs.extend(replaced.take());
+            swapped.get_or_insert(other);
+        }
+        swapped
+    }
+
+    /// Liga o stick à ação analógica. A outra ação, se usava esse stick,
+    /// fica com o que era desta; devolve a outra ação.
+    pub fn bind_stick(&mut self, action: StickAction, stick: Stick) -> Option<StickAction> {
+        let replaced = self.sticks.insert(action, stick);
+        if replaced == Some(stick) {
+            return None;
+        }
+        let other = StickAction::ALL
+            .into_iter()
+            .find(|other| *other != action && self.stick(*other) == Some(stick))?;
+        match replaced {
+            Some(replaced) => self.sticks.insert(other, replaced),
+            None => self.sticks.remove(&other),
+        };
+        Some(other)
+    }
+
+    /// Controles da ação separados por " / ", para mostrar na tela.
+    pub fn describe(&self, action: Action) -> String {
+        let labels: Vec<String> = self.get(action).iter().map(Binding::label).collect();
+        match labels.is_empty() {
+            true => "-".to_string(),
+            false => labels.join(" / "),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, BindingsError> {
+        let text = std::fs::read_to_string(path).map_err(BindingsError::Io)?;
+        Self::from_ron(&text)
+    }
+
+    /// Interpreta um arquivo de controles. Ações que faltam no arquivo ficam
+    /// com os controles de fábrica.
+    pub fn from_ron(text: &str) -> Result<Self, BindingsError> {
+        let data: BindingsData = ron::from_str(text).map_err(BindingsError::Parse)?;
+        if data.version != BINDINGS_VERSION {
+            return Err(BindingsError::UnsupportedVersion(data.version));
+        }
+        let mut bindings = InputBindings::default();
+        for entry in data.actions {
+            bindings.bindings.insert(entry.action, entry.inputs);
+        }
+        for entry in data.sticks {
+            bindings.sticks.insert(entry.action, entry.stick);
+        }
+        Ok(bindings)
+    }
+
+    pub fn to_ron(&self) -> Result<String, BindingsError> {
+        let data = BindingsData {
+            version: BINDINGS_VERSION,
+            actions: Action::ALL
+                .iter()
+                .map(|action| ActionBindings {
+                    action: *action,
+                    inputs: self.get(*action).to_vec(),
+                })
+                .collect(),
+            sticks: StickAction::ALL
+                .iter()
+                .filter_map(|action| self.stick(*action).map(|stick| StickBinding { action: *action, stick }))
+                .collect(),
+        };
+        ron::ser::to_string_pretty(&data, ron::ser::PrettyConfig::default()).map_err(BindingsError::Serialize)
+    }
+
+    /// Grava os controles, criando a pasta se preciso.
+    pub fn save(&self, path: &Path) -> Result<(), BindingsError> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(BindingsError::Io)?;
+        }
+        let temp = path.with_extension("ron.tmp");
+        std::fs::write(&temp, self.to_ron()?).map_err(BindingsError::Io)?;
+        std::fs::rename(&temp, path).map_err(BindingsError::Io)
+    }
+}
+
+/// Erros ao ler ou gravar os controles.
+#[derive(Debug)]
+pub enum BindingsError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    Serialize(ron::Error),
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for BindingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BindingsError::Io(err) => write!(f, "erro de arquivo: {err}"),
+            BindingsError::Parse(err) => write!(f, "controles inválidos: {err}"),
+            BindingsError::Serialize(err) => write!(f, "não foi possível gerar os controles: {err}"),
+            BindingsError::UnsupportedVersion(version) => {
+                write!(f, "versão de controles {version} não suportada (atual {BINDINGS_VERSION})")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BindingsError {}
+
+/// Onde os controles ficam guardados.
+#[derive(Resource, Debug, Clone)]
+pub struct BindingsFile {
+    pub path: PathBuf,
+}
+
+impl BindingsFile {
+    /// Arquivo na pasta de dados do usuário, se ela existir.
+    pub fn in_user_data_dir() -> Option<Self> {
+        crate::profile::user_data_dir().map(|dir| BindingsFile { path: dir.join(BINDINGS_FILE) })
+    }
+}
+
+/// Ações ativas neste quadro e a posição dos sticks.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ActionState {
+    pressed: HashSet<Action>,
+    just_pressed: HashSet<Action>,
+    move_stick: Vec2, // Stick de movimento, zero dentro da zona morta
+    aim_stick: Vec2,  // Stick de mira, zero dentro da zona morta
+}
+
+impl ActionState {
+    pub fn pressed(&self, action: Action) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.just_pressed.contains(&action)
+    }
+
+    /// Marca a ação como apertada neste quadro.
+    pub fn press(&mut self, action: Action) {
+        self.pressed.insert(action);
+        self.just_pressed.insert(action);
+    }
+
+    /// Esquece o aperto deste quadro, para que só um sistema reaja a ele.
+    pub fn consume(&mut self, action: Action) {
+        self.just_pressed.remove(&action);
+    }
+
+    pub fn set_sticks(&mut self, move_stick: Vec2, aim_stick: Vec2) {
+        self.move_stick = move_stick;
+        self.aim_stick = aim_stick;
+    }
+
+    /// Cópia das ações do quadro que pode ser gravada.
+    pub fn snapshot(&self) -> ActionSnapshot {
+        let listed = |set: &HashSet<Action>| Action::ALL.into_iter().filter(|action| set.contains(action)).collect();
+        ActionSnapshot {
+            pressed: listed(&self.pressed),
+            just_pressed: listed(&self.just_pressed),
+            move_stick: self.move_stick,
+            aim_stick: self.aim_stick,
+        }
+    }
+
+    /// Troca as ações do quadro pelas de uma cópia.
+    pub fn restore(&mut self, snapshot: &ActionSnapshot) {
+        self.pressed = snapshot.pressed.iter().copied().collect();
+        self.just_pressed = snapshot.just_pressed.iter().copied().collect();
+        self.set_sticks(snapshot.move_stick, snapshot.aim_stick);
+    }
+
+    /// Direção do movimento, com comprimento até 1: o stick de movimento,
+    /// analógico, ou os botões de movimento, normalizados para a diagonal
+    /// não ser mais rápida.
+    pub fn movement(&self) -> Vec2 {
+        if self.move_stick != Vec2::ZERO {
+            return self.move_stick.clamp_length_max(1.0);
+        }
+        held_sum(&self.pressed, &MOVE_ACTIONS).normalize_or_zero()
+    }
+
+    /// Direção da mira segurada, pelo stick de mira ou pelos botões de mira,
+    /// ou `None` se nada (ou só direções opostas) estiver pressionado.
+    pub fn aim(&self) -> Option<Vec2> {
+        if self.aim_stick != Vec2::ZERO {
+            return self.aim_stick.try_normalize();
+        }
+        held_sum(&self.pressed, &AIM_ACTIONS).try_normalize()
+    }
+}
+
+/// Ações de um quadro já resolvidas, sem depender dos controles. As listas
+/// seguem a ordem de `Action::ALL`, para a mesma entrada gerar o mesmo texto.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ActionSnapshot {
+    pub pressed: Vec<Action>,
+    pub just_pressed: Vec<Action>,
+    pub move_stick: Vec2,
+    pub aim_stick: Vec2,
+}
+
+fn held_sum(pressed: &HashSet<Action>, directions: &[(Action, Vec2)]) -> Vec2 {
+    directions
+        .iter()
+        .filter(|(action, _)| pressed.contains(action))
+        .map(|(_, direction)| *direction)
+        .sum()
+}
+
+/// Lê os controles do disco. Sem arquivo, ou com um arquivo inválido, ficam
+/// os de fábrica; o arquivo só é reescrito quando o jogador muda algo.
+fn load_bindings(file: Res<BindingsFile>, mut bindings: ResMut<InputBindings>) {
+    if !file.path.exists() {
+        return;
+    }
+    match InputBindings::load(&file.path) {
+        Ok(loaded) => *bindings = loaded,
+        Err(err) => error!("Controles em {:?} ignorados: {}", file.path, err),
+    }
+}
+
+/// Monta as ações do quadro a partir da entrada de todos os dispositivos.
+/// Botões de controle valem em qualquer controle conectado; os sticks, só
+/// no de menor id, para não depender da ordem de um `HashSet`.
+fn update_actions(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    pad_buttons: Res<ButtonInput<GamepadButton>>,
+    pad_axes: Res<Axis<GamepadAxis>>,
+    bindings: Res<InputBindings>,
+    mut actions: ResMut<ActionState>,
+) {
+    let pad_pressed = |button: GamepadButtonType| pad_buttons.get_pressed().any(|pad| pad.button_type == button);
+    let pad_just_pressed =
+        |button: GamepadButtonType| pad_buttons.get_just_pressed().any(|pad| pad.button_type == button);
+
+    actions.pressed.clear();
+    actions.just_pressed.clear();
+    for action in Action::ALL {
+        for binding in bindings.get(action) {
+            let (pressed, just_pressed) = match *binding {
+                Binding::Key(key) => (keyboard.pressed(key), keyboard.just_pressed(key)),
+                Binding::Mouse(button) => (mouse.pressed(button), mouse.just_pressed(button)),
+                Binding::Pad(button) => (pad_pressed(button), pad_just_pressed(button)),
+            };
+            if pressed {
+                actions.pressed.insert(action);
+            }
+            if just_pressed {
+                actions.just_pressed.insert(action);
+            }
+        }
+    }
+
+    let pad = first_gamepad(&pad_axes);
+    let tilt = |action| match (pad, bindings.stick(action)) {
+        (Some(pad), Some(stick)) => stick.tilt(&pad_axes, pad),
+        _ => Vec2::ZERO,
+    };
+    actions.set_sticks(tilt(StickAction::Move), tilt(StickAction::Aim));
+}
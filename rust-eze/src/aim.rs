@@ -0,0 +1,108 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use serde::{Deserialize, Serialize};
+use crate::actions::{Action, ActionState};
+use crate::player::Player;
+use crate::state::AppState;
+use crate::status::Element;
+
+/// Elementos na ordem da troca pelas ações de anterior/próximo.
+pub const ELEMENTS: [Element; 5] = [
+    Element::Standard,
+    Element::Fire,
+    Element::Ice,
+    Element::Electric,
+    Element::Explosive,
+];
+
+/// Plugin que decide para onde o jogador mira, se está atirando e com qual
+/// elemento. O disparo em si fica em `projectiles`.
+pub struct AimPlugin;
+
+impl Plugin for AimPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Aim>()
+            .init_resource::<SelectedElement>()
+            .add_systems(Update, (update_aim, select_element).run_if(in_state(AppState::Playing)))
+            .add_systems(OnExit(AppState::GameOver), reset_aim);
+    }
+}
+
+/// Direção da mira e se o jogador quer atirar neste quadro.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Aim {
+    pub direction: Vec2, // Sempre normalizada
+    pub firing: bool,
+}
+
+impl Default for Aim {
+    fn default() -> Self {
+        Aim { direction: Vec2::Y, firing: false }
+    }
+}
+
+/// Elemento dos próximos disparos.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SelectedElement(pub Element);
+
+impl Default for SelectedElement {
+    fn default() -> Self {
+        SelectedElement(Element::Standard)
+    }
+}
+
+impl SelectedElement {
+    /// Anda `step` posições na lista de `ELEMENTS`, dando a volta.
+    pub fn cycle(&mut self, step: isize) {
+        let index = ELEMENTS.iter().position(|element| *element == self.0).unwrap_or(0);
+        let next = (index as isize + step).rem_euclid(ELEMENTS.len() as isize) as usize;
+        self.0 = ELEMENTS[next];
+    }
+}
+
+/// Mira pelas ações de mira ou pelo stick direito, que também atiram, ou
+/// pelo cursor do mouse, convertido para o mundo pela câmera (descontando as
+/// faixas pretas do enquadramento). Sem nenhum deles, mantém a última direção.
+pub fn update_aim(
+    actions: Res<ActionState>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    player_query: Query<&Transform, With<Player>>,
+    mut aim: ResMut<Aim>,
+) {
+    if let Some(direction) = actions.aim() {
+        aim.direction = direction;
+        aim.firing = true;
+        return;
+    }
+
+    let cursor = window_query.get_single().ok().and_then(|window| window.cursor_position());
+    if let (Some(cursor), Ok((camera, camera_transform)), Ok(player_transform)) =
+        (cursor, camera_query.get_single(), player_query.get_single())
+        && let Some(viewport) = camera.logical_viewport_rect()
+        && let Some(target) = camera.viewport_to_world_2d(camera_transform, cursor - viewport.min)
+        && let Some(direction) = (target - player_transform.translation.xy()).try_normalize()
+    {
+        aim.direction = direction;
+    }
+    aim.firing = actions.pressed(Action::Fire);
+}
+
+/// Escolhe o elemento direto ou troca para o anterior/próximo.
+fn select_element(actions: Res<ActionState>, mut selected: ResMut<SelectedElement>) {
+    if let Some(element) = ELEMENTS.iter().find(|element| actions.just_pressed(Action::SelectElement(**element))) {
+        selected.0 = *element;
+    }
+    if actions.just_pressed(Action::PreviousElement) {
+        selected.cycle(-1);
+    }
+    if actions.just_pressed(Action::NextElement) {
+        selected.cycle(1);
+    }
+}
+
+fn reset_aim(mut aim: ResMut<Aim>, mut selected: ResMut<SelectedElement>) {
+    *aim = Aim::default();
+    *selected = SelectedElement::default();
+}
@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+
+/// Recurso com as dimensões da arena jogável.
+///
+/// Os sistemas de jogo leem o tamanho da arena daqui em vez de consultar a
+/// janela, o que permite rodar a simulação sem janela (testes, CI). O
+/// tamanho não acompanha a janela: a câmera amplia a arena para caber nela.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct ArenaSize {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Default for ArenaSize {
+    fn default() -> Self {
+        // Mesma resolução da janela padrão do Bevy.
+        ArenaSize {
+            width: 1280.0,
+            height: 720.0,
+        }
+    }
+}
+
+impl ArenaSize {
+    pub fn new(width: f32, height: f32) -> Self {
+        ArenaSize { width, height }
+    }
+
+    /// Limites (meia largura, meia altura) para o centro de um objeto com o
+    /// tamanho informado, descontando a espessura das paredes.
+    pub fn bounds(&self, wall_thickness: f32, size: f32) -> Vec2 {
+        Vec2::new(
+            (self.width - wall_thickness - size) / 2.0,
+            (self.height - wall_thickness - size) / 2.0,
+        )
+    }
+}
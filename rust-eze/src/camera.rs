@@ -0,0 +1,204 @@
+use bevy::prelude::*;
+use bevy::render::camera::{ScalingMode, Viewport};
+use bevy::window::PrimaryWindow;
+use crate::arena::ArenaSize;
+use crate::config::GameTuning;
+use crate::game::PlayerDamaged;
+use crate::room_state::LeftRoom;
+use crate::rooms::{place_player_at_entrance, CurrentRoom};
+use crate::state::AppState;
+use crate::status::{Element, ElementHit};
+
+/// Plugin da câmera: enquadra a arena, de tamanho fixo, na janela com
+/// faixas pretas, treme com golpes e explosões e desliza na troca de sala.
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<CameraShake>()
+            .init_resource::<CameraPan>()
+            .add_systems(Startup, spawn_camera)
+            .add_systems(OnEnter(AppState::RoomTransition), start_room_pan.after(place_player_at_entrance))
+            .add_systems(OnExit(AppState::GameOver), stop_room_pan)
+            .add_systems(Update, fit_camera)
+            .add_systems(Update, (add_trauma, move_camera, despawn_left_room_view).chain());
+    }
+}
+
+/// Marca a câmera do jogo.
+#[derive(Component)]
+pub struct GameCamera;
+
+/// Tremor da câmera. O `trauma` vai de 0 a 1 e cai com o tempo.
+#[derive(Resource, Debug, Default)]
+pub struct CameraShake {
+    pub trauma: f32,
+}
+
+impl CameraShake {
+    pub fn add(&mut self, trauma: f32) {
+        self.trauma = (self.trauma + trauma).min(1.0);
+    }
+}
+
+/// Deslizamento da câmera da sala antiga até a nova.
+#[derive(Resource, Debug, Default)]
+pub struct CameraPan {
+    from: Vec2,   // Onde a sala antiga fica em relação à nova
+    timer: Timer,
+}
+
+/// Parte da janela onde a arena é desenhada, em pixels físicos.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Letterbox {
+    pub position: UVec2,
+    pub size: UVec2,
+    pub scale: f32, // Pixels físicos por pixel da arena
+}
+
+/// Maior escala da arena que cabe na janela, centralizada. Com
+/// `integer_scaling`, a escala é arredondada para baixo quando a janela
+/// comporta pelo menos o tamanho original. `None` com a janela minimizada.
+pub fn letterbox(window: UVec2, arena: Vec2, integer_scaling: bool) -> Option<Letterbox> {
+    let mut scale = (window.x as f32 / arena.x).min(window.y as f32 / arena.y);
+    if integer_scaling && scale >= 1.0 {
+        scale = scale.floor();
+    }
+    let size = (arena * scale).round().as_uvec2().min(window);
+    if size.x == 0 || size.y == 0 {
+        return None;
+    }
+    Some(Letterbox {
+        position: (window - size) / 2,
+        size,
+        scale,
+    })
+}
+
+fn spawn_camera(mut commands: Commands, arena: Res<ArenaSize>) {
+    let mut camera = Camera2dBundle::default();
+    camera.projection.scaling_mode = ScalingMode::Fixed {
+        width: arena.width,
+        height: arena.height,
+    };
+    commands.spawn((GameCamera, camera));
+}
+
+/// Mantém a arena enquadrada quando a janela muda de tamanho. A interface
+/// acompanha a escala, então continua posicionada em pixels da arena.
+fn fit_camera(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<&mut Camera, With<GameCamera>>,
+    ui_scale: Option<ResMut<UiScale>>,
+    arena: Res<ArenaSize>,
+    tuning: Res<GameTuning>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let physical = UVec2::new(window.physical_width(), window.physical_height());
+    let Some(letterbox) = letterbox(physical, Vec2::new(arena.width, arena.height), tuning.camera.integer_scaling) else {
+        return;
+    };
+
+    for mut camera in camera_query.iter_mut() {
+        let current = camera.viewport.as_ref().map(|viewport| (viewport.physical_position, viewport.physical_size));
+        if current != Some((letterbox.position, letterbox.size)) {
+            camera.viewport = Some(Viewport {
+                physical_position: letterbox.position,
+                physical_size: letterbox.size,
+                ..default()
+            });
+        }
+    }
+
+    let scale = letterbox.scale / window.scale_factor();
+    if let Some(mut ui_scale) = ui_scale
+        && ui_scale.0 != scale
+    {
+        ui_scale.0 = scale;
+    }
+}
+
+/// Golpes no jogador e explosões fazem a câmera tremer.
+fn add_trauma(
+    mut damaged_events: EventReader<PlayerDamaged>,
+    mut element_hits: EventReader<ElementHit>,
+    mut shake: ResMut<CameraShake>,
+    tuning: Res<GameTuning>,
+) {
+    for _ in damaged_events.read() {
+        shake.add(tuning.camera.damage_shake);
+    }
+    for hit in element_hits.read() {
+        if hit.element == Element::Explosive {
+            shake.add(tuning.camera.explosion_shake);
+        }
+    }
+}
+
+/// Começa a deslizar a partir da sala de onde o jogador veio, que fica do
+/// lado da porta por onde ele entrou.
+fn start_room_pan(
+    current_room: Res<CurrentRoom>,
+    mut pan: ResMut<CameraPan>,
+    arena: Res<ArenaSize>,
+    tuning: Res<GameTuning>,
+) {
+    let Some(entered_from) = current_room.entered_from else {
+        return;
+    };
+    *pan = CameraPan {
+        from: entered_from.as_vec2() * Vec2::new(arena.width, arena.height),
+        timer: Timer::from_seconds(tuning.camera.pan_duration, TimerMode::Once),
+    };
+}
+
+/// Terminado o deslizamento, a sala antiga já saiu da tela.
+fn despawn_left_room_view(
+    mut commands: Commands,
+    pan: Res<CameraPan>,
+    left_query: Query<Entity, With<LeftRoom>>,
+) {
+    if !pan.timer.finished() {
+        return;
+    }
+    for entity in left_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// A partida nova começa com a câmera parada na sala inicial.
+fn stop_room_pan(mut pan: ResMut<CameraPan>) {
+    *pan = CameraPan::default();
+}
+
+fn move_camera(
+    mut camera_query: Query<&mut Transform, With<GameCamera>>,
+    mut shake: ResMut<CameraShake>,
+    mut pan: ResMut<CameraPan>,
+    tuning: Res<GameTuning>,
+    time: Res<Time>,
+) {
+    pan.timer.tick(time.delta());
+    // Começa e termina devagar
+    let t = pan.timer.fraction();
+    let eased = t * t * (3.0 - 2.0 * t);
+    let pan_offset = pan.from * (1.0 - eased);
+
+    // Senoides com frequências diferentes por eixo, sem tocar no gerador
+    // aleatório da partida, que os replays precisam reproduzir
+    let phase = time.elapsed_seconds() * tuning.camera.shake_frequency;
+    let wobble = Vec2::new(phase.sin(), (phase * 1.37 + 1.0).sin());
+    let shake_offset = wobble * tuning.camera.shake_max_offset * shake.trauma * shake.trauma;
+    shake.trauma = (shake.trauma - tuning.camera.shake_decay * time.delta_seconds()).max(0.0);
+
+    for mut transform in camera_query.iter_mut() {
+        let offset = pan_offset + shake_offset;
+        if transform.translation.xy() != offset {
+            transform.translation.x = offset.x;
+            transform.translation.y = offset.y;
+        }
+    }
+}
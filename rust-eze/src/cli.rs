@@ -0,0 +1,76 @@
+use std::fmt;
+use std::path::PathBuf;
+
+/// Argumentos de linha de comando aceitos pelo jogo.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CliArgs {
+    /// `--seed <n>`: semente fixa da partida.
+    pub seed: Option<u64>,
+    /// `--record <arquivo>`: grava a partida em um replay.
+    pub record: Option<PathBuf>,
+    /// `--replay <arquivo>`: reproduz um replay gravado.
+    pub replay: Option<PathBuf>,
+    /// `--headless`: roda o replay sem janela e confere o resultado.
+    pub headless: bool,
+}
+
+/// Erro ao interpretar a linha de comando.
+#[derive(Debug, PartialEq)]
+pub enum CliError {
+    MissingValue(&'static str),
+    InvalidValue { flag: &'static str, value: String },
+    UnknownArgument(String),
+    Conflict(&'static str),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::MissingValue(flag) => write!(f, "{flag} precisa de um valor"),
+            CliError::InvalidValue { flag, value } => write!(f, "valor inválido para {flag}: {value}"),
+            CliError::UnknownArgument(arg) => write!(f, "argumento desconhecido: {arg}"),
+            CliError::Conflict(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+impl CliArgs {
+    /// Interpreta os argumentos (sem o nome do programa).
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, CliError> {
+        let mut parsed = CliArgs::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--seed" => {
+                    let value = args.next().ok_or(CliError::MissingValue("--seed"))?;
+                    let seed = value
+                        .parse()
+                        .map_err(|_| CliError::InvalidValue { flag: "--seed", value })?;
+                    parsed.seed = Some(seed);
+                }
+                "--record" => {
+                    let value = args.next().ok_or(CliError::MissingValue("--record"))?;
+                    parsed.record = Some(PathBuf::from(value));
+                }
+                "--replay" => {
+                    let value = args.next().ok_or(CliError::MissingValue("--replay"))?;
+                    parsed.replay = Some(PathBuf::from(value));
+                }
+                "--headless" => parsed.headless = true,
+                _ => return Err(CliError::UnknownArgument(arg)),
+            }
+        }
+
+        if parsed.replay.is_some() && (parsed.record.is_some() || parsed.seed.is_some()) {
+            return Err(CliError::Conflict("--replay não pode ser usado com --record ou --seed"));
+        }
+        if parsed.headless && parsed.replay.is_none() {
+            return Err(CliError::Conflict("--headless só funciona com --replay"));
+        }
+
+        Ok(parsed)
+    }
+}
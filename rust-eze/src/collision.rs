@@ -0,0 +1,236 @@
+use bevy::prelude::*;
+use std::collections::{BTreeSet, HashMap};
+use std::ops::BitOr;
+
+/// Tamanho das células da grade espacial usada na fase ampla.
+const CELL_SIZE: f32 = 64.0;
+
+/// Plugin de colisão: uma única fonte de verdade para as hitboxes do jogo.
+///
+/// Roda em `FixedUpdate` no conjunto `CollisionSet`. Sistemas que movem
+/// entidades rodam antes dele e os que reagem a `CollisionEvent` depois.
+pub struct CollisionPlugin;
+
+impl Plugin for CollisionPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<CollisionEvent>()
+            .add_systems(FixedFirst, record_collider_starts)
+            .add_systems(FixedUpdate, detect_collisions.in_set(CollisionSet));
+    }
+}
+
+/// Conjunto com a detecção de colisões.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CollisionSet;
+
+/// Camadas de colisão, combináveis com `|`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Layers(u32);
+
+impl Layers {
+    pub const NONE: Layers = Layers(0);
+    pub const PLAYER: Layers = Layers(1 << 0);
+    pub const ENEMY: Layers = Layers(1 << 1);
+    pub const PROJECTILE: Layers = Layers(1 << 2);
+    pub const PICKUP: Layers = Layers(1 << 3);
+    pub const ORBITER: Layers = Layers(1 << 4);
+    pub const ENEMY_PROJECTILE: Layers = Layers(1 << 5);
+
+    pub fn intersects(self, other: Layers) -> bool {
+        self.0 & other.0 != 0
+    }
+}
+
+impl BitOr for Layers {
+    type Output = Layers;
+
+    fn bitor(self, rhs: Layers) -> Layers {
+        Layers(self.0 | rhs.0)
+    }
+}
+
+/// Formato da hitbox.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColliderShape {
+    Circle { radius: f32 },
+}
+
+/// Componente de colisão: formato, camada a que pertence e camadas que detecta.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Collider {
+    pub shape: ColliderShape,
+    pub layer: Layers,
+    pub mask: Layers,
+    // Posição no início do passo atual, para o teste com varredura.
+    start: Option<Vec2>,
+}
+
+impl Collider {
+    pub fn circle(radius: f32, layer: Layers, mask: Layers) -> Self {
+        Collider {
+            shape: ColliderShape::Circle { radius },
+            layer,
+            mask,
+            start: None,
+        }
+    }
+
+    pub fn radius(&self) -> f32 {
+        match self.shape {
+            ColliderShape::Circle { radius } => radius,
+        }
+    }
+
+    fn interacts_with(&self, other: &Collider) -> bool {
+        self.mask.intersects(other.layer) || other.mask.intersects(self.layer)
+    }
+}
+
+/// Evento enviado quando duas hitboxes se tocam durante um passo.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct CollisionEvent {
+    pub a: Entity,
+    pub b: Entity,
+    layers: (Layers, Layers),
+}
+
+impl CollisionEvent {
+    /// Devolve as entidades na ordem pedida se o evento envolve as duas camadas.
+    pub fn between(&self, first: Layers, second: Layers) -> Option<(Entity, Entity)> {
+        let (layer_a, layer_b) = self.layers;
+        if layer_a.intersects(first) && layer_b.intersects(second) {
+            Some((self.a, self.b))
+        } else if layer_b.intersects(first) && layer_a.intersects(second) {
+            Some((self.b, self.a))
+        } else {
+            None
+        }
+    }
+}
+
+/// Teste de varredura entre dois círculos que se movem em linha reta de
+/// `*0` até `*1` durante o passo. Verdadeiro se chegam a se sobrepor.
+pub fn swept_circles_overlap(a0: Vec2, a1: Vec2, radius_a: f32, b0: Vec2, b1: Vec2, radius_b: f32) -> bool {
+    let offset = a0 - b0;
+    let motion = (a1 - a0) - (b1 - b0);
+    let motion_sq = motion.length_squared();
+    let t = if motion_sq > 0.0 {
+        (-offset.dot(motion) / motion_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (offset + motion * t).length() < radius_a + radius_b
+}
+
+/// Empurra um círculo para fora de um retângulo pelo caminho mais curto.
+/// Sem sobreposição, devolve o centro sem mudança.
+pub fn push_circle_out_of_rect(center: Vec2, radius: f32, rect: Rect) -> Vec2 {
+    let closest = center.clamp(rect.min, rect.max);
+    let offset = center - closest;
+    let distance = offset.length();
+    if distance >= radius {
+        return center;
+    }
+    if distance > 0.0 {
+        return closest + offset / distance * radius;
+    }
+
+    // Centro dentro do retângulo: sai pelo lado mais próximo
+    let exits = [
+        (center.x - rect.min.x, Vec2::new(rect.min.x - radius, center.y)),
+        (rect.max.x - center.x, Vec2::new(rect.max.x + radius, center.y)),
+        (center.y - rect.min.y, Vec2::new(center.x, rect.min.y - radius)),
+        (rect.max.y - center.y, Vec2::new(center.x, rect.max.y + radius)),
+    ];
+    exits
+        .into_iter()
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map_or(center, |(_, exit)| exit)
+}
+
+/// Grade espacial uniforme: cada célula guarda os índices dos corpos que a tocam.
+#[derive(Default)]
+struct SpatialGrid {
+    cells: HashMap<(i32, i32), Vec<usize>>,
+}
+
+impl SpatialGrid {
+    fn cell(point: Vec2) -> (i32, i32) {
+        ((point.x / CELL_SIZE).floor() as i32, (point.y / CELL_SIZE).floor() as i32)
+    }
+
+    fn insert(&mut self, index: usize, min: Vec2, max: Vec2) {
+        let (min_x, min_y) = Self::cell(min);
+        let (max_x, max_y) = Self::cell(max);
+        for x in min_x..=max_x {
+            for y in min_y..=max_y {
+                self.cells.entry((x, y)).or_default().push(index);
+            }
+        }
+    }
+
+    /// Pares candidatos, sem repetição e em ordem determinística.
+    fn candidate_pairs(&self) -> BTreeSet<(usize, usize)> {
+        let mut pairs = BTreeSet::new();
+        for indices in self.cells.values() {
+            for (i, &a) in indices.iter().enumerate() {
+                for &b in &indices[i + 1..] {
+                    pairs.insert((a.min(b), a.max(b)));
+                }
+            }
+        }
+        pairs
+    }
+}
+
+struct Body {
+    entity: Entity,
+    start: Vec2,
+    end: Vec2,
+    collider: Collider,
+}
+
+fn record_collider_starts(mut query: Query<(&Transform, &mut Collider)>) {
+    for (transform, mut collider) in query.iter_mut() {
+        collider.start = Some(transform.translation.xy());
+    }
+}
+
+fn detect_collisions(
+    query: Query<(Entity, &Transform, &Collider)>,
+    mut events: EventWriter<CollisionEvent>,
+) {
+    let bodies: Vec<Body> = query
+        .iter()
+        .map(|(entity, transform, collider)| {
+            let end = transform.translation.xy();
+            Body {
+                entity,
+                start: collider.start.unwrap_or(end),
+                end,
+                collider: *collider,
+            }
+        })
+        .collect();
+
+    let mut grid = SpatialGrid::default();
+    for (index, body) in bodies.iter().enumerate() {
+        let reach = Vec2::splat(body.collider.radius());
+        grid.insert(index, body.start.min(body.end) - reach, body.start.max(body.end) + reach);
+    }
+
+    for (i, j) in grid.candidate_pairs() {
+        let (a, b) = (&bodies[i], &bodies[j]);
+        if !a.collider.interacts_with(&b.collider) {
+            continue;
+        }
+        if swept_circles_overlap(a.start, a.end, a.collider.radius(), b.start, b.end, b.collider.radius()) {
+            events.send(CollisionEvent {
+                a: a.entity,
+                b: b.entity,
+                layers: (a.collider.layer, b.collider.layer),
+            });
+        }
+    }
+}
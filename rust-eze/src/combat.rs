@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+use crate::collision::CollisionSet;
+
+/// Plugin com as peças comuns de dano: vida, dano e o evento de dano.
+///
+/// Quem causa dano envia `DamageEvent` no conjunto `HitSet`, depois de
+/// `CollisionSet`; quem recebe aplica no conjunto `DamageSet`, que roda em seguida.
+pub struct CombatPlugin;
+
+impl Plugin for CombatPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<DamageEvent>()
+            .configure_sets(FixedUpdate, (
+                HitSet.after(CollisionSet),
+                DamageSet.after(HitSet),
+            ));
+    }
+}
+
+/// Conjunto dos sistemas que transformam colisões em `DamageEvent`.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HitSet;
+
+/// Conjunto dos sistemas que aplicam `DamageEvent`.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DamageSet;
+
+/// Pontos de vida de uma entidade.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Health {
+    pub max: f32,
+    pub current: f32,
+}
+
+impl Health {
+    pub fn new(max: f32) -> Self {
+        Health { max, current: max }
+    }
+
+    /// Tira vida e retorna `true` se ela chegou a zero.
+    pub fn take(&mut self, amount: f32) -> bool {
+        self.current = (self.current - amount).max(0.0);
+        self.is_dead()
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0.0
+    }
+}
+
+/// Dano causado por um projétil ou outra fonte ao acertar.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Damage(pub f32);
+
+/// Origem do dano: acertos respeitam a invulnerabilidade e fazem o alvo
+/// piscar; efeitos contínuos, como queimadura, não.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DamageKind {
+    Hit,
+    Status,
+}
+
+/// Pedido de dano para uma entidade.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DamageEvent {
+    pub target: Entity,
+    pub amount: f32,
+    pub kind: DamageKind,
+}
+
+impl DamageEvent {
+    pub fn hit(target: Entity, amount: f32) -> Self {
+        DamageEvent { target, amount, kind: DamageKind::Hit }
+    }
+
+    pub fn status(target: Entity, amount: f32) -> Self {
+        DamageEvent { target, amount, kind: DamageKind::Status }
+    }
+}
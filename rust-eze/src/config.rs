@@ -1,10 +1,587 @@
-pub struct GameConfig;
-
-impl GameConfig {
-    pub const PLAYER_SIZE: f32 = 25.0;  // Raio do jogador
-    pub const ENEMY_SIZE: f32 = 20.0;  // Raio do inimigo
-    pub const WALL_THICKNESS: f32 = 20.0;  // Espessura das paredes
-    pub const PLAYER_SPEED: f32 = 300.0;  // Velocidade do jogador
-    pub const ENEMY_SPEED: f32 = 200.0;  // Velocidade dos inimigos
-    pub const COLLISION_DISTANCE: f32 = Self::PLAYER_SIZE + Self::ENEMY_SIZE;  // Distância de colisão
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Caminho padrão do arquivo de ajustes, relativo ao diretório do jogo.
+pub const TUNING_PATH: &str = "assets/tuning.ron";
+
+/// Plugin que carrega o `GameTuning` do disco e recarrega quando o arquivo muda.
+pub struct TuningPlugin;
+
+impl Plugin for TuningPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<GameTuning>()
+            .init_resource::<TuningSource>()
+            .add_systems(PreStartup, load_tuning)
+            .add_systems(Update, reload_tuning);
+    }
+}
+
+/// Recurso com todos os números de balanceamento do jogo.
+///
+/// Os campos ausentes no arquivo usam os valores de `Default`. Tamanhos e
+/// paredes só valem para entidades criadas depois da recarga. Os números de
+/// cada tipo de inimigo ficam em `assets/enemies.ron` e os de cada arma em
+/// `assets/weapons.ron`.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct GameTuning {
+    pub player_size: f32,        // Raio do jogador
+    pub wall_thickness: f32,     // Espessura das paredes
+    pub door_width: f32,         // Largura das portas entre salas
+    pub player_speed: f32,       // Velocidade do jogador
+    pub projectile_size: f32,    // Raio dos projéteis
+    pub projectile_speed: f32,   // Velocidade dos projéteis
+    pub powerup_duration: f32,   // Duração dos power-ups (s)
+    pub spawn_safe_radius: f32,  // Distância mínima do jogador ao reposicionar inimigos
+    pub starting_lives: u32,     // Vidas no começo da partida
+    pub max_lives: u32,          // Limite de vidas ao coletar vida extra
+    pub player_health: u32,      // Pontos de vida de cada vida
+    pub player_invulnerability: f32, // Tempo invulnerável após levar dano (s)
+    pub knockback_speed: f32,    // Velocidade do empurrão ao levar dano
+    pub knockback_duration: f32, // Duração do empurrão (s)
+    pub player_acceleration: f32, // Aceleração até a velocidade do jogador (px/s²)
+    pub player_deceleration: f32, // Frenagem ao soltar o movimento (px/s²)
+    pub dash: DashTuning,        // Arrancada do jogador
+    pub camera: CameraTuning,    // Enquadramento, tremor e troca de sala da câmera
+    pub enemy_invulnerability: f32, // Tempo invulnerável após levar dano (s)
+    pub projectile_damage: f32,  // Dano de cada projétil
+    pub rotating_circle_damage: f32, // Dano do círculo giratório
+    pub kill_score: u32,         // Pontos por inimigo derrotado, antes do peso do tipo
+    pub scoring: ScoringTuning,  // Pontuação por evento e combo
+    pub status: StatusTuning,    // Efeitos elementais dos projéteis
+    pub floor: FloorTuning,      // Gerador de andares
+    pub seed: Option<u64>,       // Semente fixa da partida (None = aleatória)
+    pub tick_rate: f64,          // Passos da simulação por segundo
+}
+
+impl Default for GameTuning {
+    fn default() -> Self {
+        GameTuning {
+            player_size: 25.0,
+            wall_thickness: 20.0,
+            door_width: 120.0,
+            player_speed: 300.0,
+            projectile_size: 4.0,
+            projectile_speed: 400.0,
+            powerup_duration: 10.0,
+            spawn_safe_radius: 100.0,
+            starting_lives: 3,
+            max_lives: 5,
+            player_health: 3,
+            player_invulnerability: 1.0,
+            knockback_speed: 500.0,
+            knockback_duration: 0.15,
+            player_acceleration: 3000.0,
+            player_deceleration: 2400.0,
+            dash: DashTuning::default(),
+            camera: CameraTuning::default(),
+            enemy_invulnerability: 0.15,
+            projectile_damage: 1.0,
+            rotating_circle_damage: 1.0,
+            kill_score: 100,
+            scoring: ScoringTuning::default(),
+            status: StatusTuning::default(),
+            floor: FloorTuning::default(),
+            seed: None,
+            tick_rate: 60.0,
+        }
+    }
+}
+
+impl GameTuning {
+    /// Lê, interpreta e valida um arquivo RON de ajustes.
+    pub fn load(path: &Path) -> Result<Self, TuningError> {
+        let text = std::fs::read_to_string(path).map_err(TuningError::Io)?;
+        Self::from_ron(&text)
+    }
+
+    /// Interpreta e valida ajustes a partir de um texto RON.
+    pub fn from_ron(text: &str) -> Result<Self, TuningError> {
+        let tuning: GameTuning = ron::from_str(text).map_err(TuningError::Parse)?;
+        tuning.validate()?;
+        Ok(tuning)
+    }
+
+    /// Verifica se todos os valores fazem sentido para o jogo.
+    pub fn validate(&self) -> Result<(), TuningError> {
+        let positive = [
+            ("player_size", self.player_size),
+            ("door_width", self.door_width),
+            ("player_speed", self.player_speed),
+            ("player_acceleration", self.player_acceleration),
+            ("player_deceleration", self.player_deceleration),
+            ("projectile_size", self.projectile_size),
+            ("projectile_speed", self.projectile_speed),
+            ("powerup_duration", self.powerup_duration),
+            ("projectile_damage", self.projectile_damage),
+            ("rotating_circle_damage", self.rotating_circle_damage),
+        ];
+        for (field, value) in positive {
+            if !value.is_finite() || value <= 0.0 {
+                return Err(TuningError::Invalid {
+                    field,
+                    reason: format!("deve ser maior que zero (recebido {value})"),
+                });
+            }
+        }
+
+        let non_negative = [
+            ("wall_thickness", self.wall_thickness),
+            ("spawn_safe_radius", self.spawn_safe_radius),
+            ("enemy_invulnerability", self.enemy_invulnerability),
+            ("player_invulnerability", self.player_invulnerability),
+            ("knockback_speed", self.knockback_speed),
+            ("knockback_duration", self.knockback_duration),
+        ];
+        for (field, value) in non_negative {
+            if !value.is_finite() || value < 0.0 {
+                return Err(TuningError::Invalid {
+                    field,
+                    reason: format!("não pode ser negativo (recebido {value})"),
+                });
+            }
+        }
+
+        if !self.tick_rate.is_finite() || self.tick_rate <= 0.0 {
+            return Err(TuningError::Invalid {
+                field: "tick_rate",
+                reason: format!("deve ser maior que zero (recebido {})", self.tick_rate),
+            });
+        }
+
+        self.dash.validate()?;
+        self.camera.validate()?;
+        self.status.validate()?;
+        self.scoring.validate()?;
+        self.floor.validate()?;
+
+        if self.max_lives == 0 {
+            return Err(TuningError::Invalid {
+                field: "max_lives",
+                reason: "deve ser pelo menos 1".to_string(),
+            });
+        }
+        if !(1..=self.max_lives).contains(&self.starting_lives) {
+            return Err(TuningError::Invalid {
+                field: "starting_lives",
+                reason: format!("deve estar entre 1 e `max_lives` (recebido {})", self.starting_lives),
+            });
+        }
+        if self.player_health == 0 {
+            return Err(TuningError::Invalid {
+                field: "player_health",
+                reason: "deve ser pelo menos 1".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Ajustes da arrancada do jogador.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct DashTuning {
+    pub speed: f32,           // Velocidade durante a arrancada
+    pub duration: f32,        // Duração da arrancada, que atravessa inimigos (s)
+    pub cooldown: f32,        // Intervalo entre o início de duas arrancadas (s)
+    pub invulnerability: f32, // Tempo imune a tiros a partir do início (s)
+    pub trail_interval: f32,  // Intervalo entre as cópias do rastro (s)
+    pub trail_lifetime: f32,  // Tempo até cada cópia do rastro sumir (s)
+}
+
+impl Default for DashTuning {
+    fn default() -> Self {
+        DashTuning {
+            speed: 900.0,
+            duration: 0.15,
+            cooldown: 0.75,
+            invulnerability: 0.25,
+            trail_interval: 0.03,
+            trail_lifetime: 0.3,
+        }
+    }
+}
+
+impl DashTuning {
+    fn validate(&self) -> Result<(), TuningError> {
+        let positive = [
+            ("dash.speed", self.speed),
+            ("dash.duration", self.duration),
+            ("dash.trail_interval", self.trail_interval),
+        ];
+        for (field, value) in positive {
+            if !value.is_finite() || value <= 0.0 {
+                return Err(TuningError::Invalid {
+                    field,
+                    reason: format!("deve ser maior que zero (recebido {value})"),
+                });
+            }
+        }
+
+        let non_negative = [
+            ("dash.cooldown", self.cooldown),
+            ("dash.invulnerability", self.invulnerability),
+            ("dash.trail_lifetime", self.trail_lifetime),
+        ];
+        for (field, value) in non_negative {
+            if !value.is_finite() || value < 0.0 {
+                return Err(TuningError::Invalid {
+                    field,
+                    reason: format!("não pode ser negativo (recebido {value})"),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Ajustes da câmera. O tremor é um "trauma" entre 0 e 1: cada golpe soma
+/// um pouco, ele cai com o tempo e o deslocamento cresce com o quadrado dele.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct CameraTuning {
+    pub integer_scaling: bool,  // Escala só por inteiros, com faixas pretas maiores
+    pub shake_max_offset: f32,  // Deslocamento com o trauma no máximo (px)
+    pub shake_frequency: f32,   // Rapidez do tremor (oscilações por segundo)
+    pub shake_decay: f32,       // Trauma perdido por segundo
+    pub damage_shake: f32,      // Trauma somado quando o jogador leva dano
+    pub explosion_shake: f32,   // Trauma somado a cada explosão
+    pub pan_duration: f32,      // Duração do deslizamento entre salas (s)
+}
+
+impl Default for CameraTuning {
+    fn default() -> Self {
+        CameraTuning {
+            integer_scaling: false,
+            shake_max_offset: 14.0,
+            shake_frequency: 25.0,
+            shake_decay: 1.8,
+            damage_shake: 0.6,
+            explosion_shake: 0.25,
+            pan_duration: 0.3,
+        }
+    }
+}
+
+impl CameraTuning {
+    fn validate(&self) -> Result<(), TuningError> {
+        let positive = [
+            ("camera.shake_frequency", self.shake_frequency),
+            ("camera.shake_decay", self.shake_decay),
+        ];
+        for (field, value) in positive {
+            if !value.is_finite() || value <= 0.0 {
+                return Err(TuningError::Invalid {
+                    field,
+                    reason: format!("deve ser maior que zero (recebido {value})"),
+                });
+            }
+        }
+
+        let non_negative = [
+            ("camera.shake_max_offset", self.shake_max_offset),
+            ("camera.damage_shake", self.damage_shake),
+            ("camera.explosion_shake", self.explosion_shake),
+            ("camera.pan_duration", self.pan_duration),
+        ];
+        for (field, value) in non_negative {
+            if !value.is_finite() || value < 0.0 {
+                return Err(TuningError::Invalid {
+                    field,
+                    reason: format!("não pode ser negativo (recebido {value})"),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Ajustes dos efeitos de estado aplicados pelos projéteis elementais.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct StatusTuning {
+    pub burn_damage_per_second: f32, // Dano por segundo de cada acúmulo de queimadura
+    pub burn_duration: f32,          // Duração da queimadura (s), renovada a cada acerto
+    pub burn_max_stacks: u32,        // Acúmulos máximos de queimadura
+    pub chill_slow: f32,             // Fração da velocidade perdida pelo inimigo congelado
+    pub chill_duration: f32,         // Duração do congelamento (s)
+    pub chain_radius: f32,           // Alcance do salto do choque elétrico
+    pub chain_targets: u32,          // Inimigos extras atingidos pelo choque
+    pub chain_damage: f32,           // Dano de cada salto do choque
+    pub blast_radius: f32,           // Raio da explosão
+    pub blast_damage: f32,           // Dano da explosão
+}
+
+impl Default for StatusTuning {
+    fn default() -> Self {
+        StatusTuning {
+            burn_damage_per_second: 0.5,
+            burn_duration: 3.0,
+            burn_max_stacks: 3,
+            chill_slow: 0.5,
+            chill_duration: 2.0,
+            chain_radius: 150.0,
+            chain_targets: 2,
+            chain_damage: 1.0,
+            blast_radius: 60.0,
+            blast_damage: 2.0,
+        }
+    }
+}
+
+impl StatusTuning {
+    fn validate(&self) -> Result<(), TuningError> {
+        let positive = [
+            ("status.burn_damage_per_second", self.burn_damage_per_second),
+            ("status.burn_duration", self.burn_duration),
+            ("status.chill_duration", self.chill_duration),
+            ("status.chain_radius", self.chain_radius),
+            ("status.chain_damage", self.chain_damage),
+            ("status.blast_radius", self.blast_radius),
+            ("status.blast_damage", self.blast_damage),
+        ];
+        for (field, value) in positive {
+            if !value.is_finite() || value <= 0.0 {
+                return Err(TuningError::Invalid {
+                    field,
+                    reason: format!("deve ser maior que zero (recebido {value})"),
+                });
+            }
+        }
+
+        if !(0.0..1.0).contains(&self.chill_slow) {
+            return Err(TuningError::Invalid {
+                field: "status.chill_slow",
+                reason: format!("deve estar entre 0 e 1 (recebido {})", self.chill_slow),
+            });
+        }
+
+        if self.burn_max_stacks == 0 {
+            return Err(TuningError::Invalid {
+                field: "status.burn_max_stacks",
+                reason: "deve ser pelo menos 1".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Pontos de cada evento da partida e o multiplicador de combo.
+///
+/// As mortes seguidas sobem o combo, que multiplica os pontos de todos os
+/// eventos menos o tempo vivo. Sem matar por `combo_grace` segundos, ele cai
+/// `combo_decay` por segundo até voltar a 1.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScoringTuning {
+    pub survival_per_second: f32, // Pontos por segundo vivo
+    pub room_clear: u32,          // Pontos ao limpar uma sala com inimigos
+    pub no_damage_room: u32,      // Bônus por limpar a sala sem perder vida
+    pub pickup: u32,              // Pontos por power-up coletado
+    pub combo_step: f32,          // Quanto cada morte soma ao multiplicador
+    pub combo_max: f32,           // Multiplicador máximo
+    pub combo_grace: f32,         // Tempo sem matar antes de o combo cair (s)
+    pub combo_decay: f32,         // Queda do multiplicador por segundo
+}
+
+impl Default for ScoringTuning {
+    fn default() -> Self {
+        ScoringTuning {
+            survival_per_second: 10.0,
+            room_clear: 250,
+            no_damage_room: 500,
+            pickup: 50,
+            combo_step: 0.25,
+            combo_max: 4.0,
+            combo_grace: 2.0,
+            combo_decay: 1.0,
+        }
+    }
+}
+
+impl ScoringTuning {
+    fn validate(&self) -> Result<(), TuningError> {
+        let non_negative = [
+            ("scoring.survival_per_second", self.survival_per_second),
+            ("scoring.combo_step", self.combo_step),
+            ("scoring.combo_grace", self.combo_grace),
+            ("scoring.combo_decay", self.combo_decay),
+        ];
+        for (field, value) in non_negative {
+            if !value.is_finite() || value < 0.0 {
+                return Err(TuningError::Invalid {
+                    field,
+                    reason: format!("não pode ser negativo (recebido {value})"),
+                });
+            }
+        }
+
+        if !self.combo_max.is_finite() || self.combo_max < 1.0 {
+            return Err(TuningError::Invalid {
+                field: "scoring.combo_max",
+                reason: format!("deve ser pelo menos 1 (recebido {})", self.combo_max),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Restrições do gerador de andares.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct FloorTuning {
+    pub room_count: usize,        // Total de salas, contando a inicial
+    pub max_connections: usize,   // Portas por sala durante o crescimento (2 a 4)
+    pub min_dead_ends: usize,     // Salas com uma porta só, fora a inicial
+    pub loops: usize,             // Ciclos garantidos no grafo
+    pub treasure_distance: usize, // Distância mínima, em portas, da sala inicial
+    pub shop_distance: usize,
+    pub boss_distance: usize,
+}
+
+impl Default for FloorTuning {
+    fn default() -> Self {
+        FloorTuning {
+            room_count: 12,
+            max_connections: 3,
+            min_dead_ends: 3,
+            loops: 1,
+            treasure_distance: 2,
+            shop_distance: 2,
+            boss_distance: 4,
+        }
+    }
+}
+
+impl FloorTuning {
+    fn validate(&self) -> Result<(), TuningError> {
+        if self.room_count < 5 {
+            return Err(TuningError::Invalid {
+                field: "floor.room_count",
+                reason: format!("precisa de pelo menos 5 salas para a inicial e as especiais (recebido {})", self.room_count),
+            });
+        }
+        if !(2..=4).contains(&self.max_connections) {
+            return Err(TuningError::Invalid {
+                field: "floor.max_connections",
+                reason: format!("deve estar entre 2 e 4 (recebido {})", self.max_connections),
+            });
+        }
+        if self.loops >= self.room_count / 2 {
+            return Err(TuningError::Invalid {
+                field: "floor.loops",
+                reason: format!("deve ser menor que metade de `room_count` (recebido {})", self.loops),
+            });
+        }
+        if self.min_dead_ends >= self.room_count {
+            return Err(TuningError::Invalid {
+                field: "floor.min_dead_ends",
+                reason: format!("deve ser menor que `room_count` (recebido {})", self.min_dead_ends),
+            });
+        }
+        let longest = self.treasure_distance.max(self.shop_distance).max(self.boss_distance);
+        if longest >= self.room_count {
+            return Err(TuningError::Invalid {
+                field: "floor",
+                reason: format!("distâncias das salas especiais devem ser menores que `room_count` (recebido {longest})"),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Erros ao carregar o arquivo de ajustes.
+#[derive(Debug)]
+pub enum TuningError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    Invalid { field: &'static str, reason: String },
+}
+
+impl fmt::Display for TuningError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TuningError::Io(err) => write!(f, "não foi possível ler o arquivo: {err}"),
+            TuningError::Parse(err) => write!(f, "erro de sintaxe: {err}"),
+            TuningError::Invalid { field, reason } => write!(f, "valor inválido em `{field}`: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for TuningError {}
+
+/// Recurso que indica de onde o `GameTuning` é carregado.
+#[derive(Resource)]
+pub struct TuningSource {
+    pub path: PathBuf,
+    last_modified: Option<SystemTime>,
+    poll_timer: Timer,
+}
+
+impl Default for TuningSource {
+    fn default() -> Self {
+        TuningSource::new(TUNING_PATH)
+    }
+}
+
+impl TuningSource {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        TuningSource {
+            path: path.into(),
+            last_modified: None,
+            poll_timer: Timer::from_seconds(0.5, TimerMode::Repeating),
+        }
+    }
+
+    fn modified(&self) -> Option<SystemTime> {
+        std::fs::metadata(&self.path).and_then(|meta| meta.modified()).ok()
+    }
+}
+
+/// Carrega os ajustes no início do jogo. Sem arquivo, ficam os valores padrão.
+pub(crate) fn load_tuning(mut source: ResMut<TuningSource>, mut tuning: ResMut<GameTuning>) {
+    let Some(modified) = source.modified() else {
+        info!("Arquivo de ajustes {:?} não encontrado, usando valores padrão", source.path);
+        return;
+    };
+    source.last_modified = Some(modified);
+
+    match GameTuning::load(&source.path) {
+        Ok(loaded) => *tuning = loaded,
+        Err(err) => error!("Ajustes em {:?} ignorados: {}", source.path, err),
+    }
+}
+
+/// Recarrega os ajustes quando o arquivo é modificado.
+fn reload_tuning(
+    time: Res<Time<Real>>,
+    mut source: ResMut<TuningSource>,
+    mut tuning: ResMut<GameTuning>,
+) {
+    if !source.poll_timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let modified = source.modified();
+    if modified.is_none() || modified == source.last_modified {
+        return;
+    }
+    source.last_modified = modified;
+
+    match GameTuning::load(&source.path) {
+        Ok(loaded) => {
+            info!("Ajustes recarregados de {:?}", source.path);
+            *tuning = loaded;
+        }
+        Err(err) => error!("Ajustes em {:?} ignorados, mantendo os atuais: {}", source.path, err),
+    }
 }
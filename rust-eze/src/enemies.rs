@@ -1,54 +1,259 @@
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
+use bevy::ecs::system::SystemParam;
 use bevy::sprite::MaterialMesh2dBundle;
 use bevy::math::Vec3Swizzles;
+use std::collections::HashSet;
+use std::f32::consts::TAU;
+use rand::seq::SliceRandom;
 use rand::Rng;
 use crate::player::Player;
-use crate::config::GameConfig;
-use crate::rooms::{CurrentRoom, RoomId};
+use crate::config::GameTuning;
+use crate::arena::ArenaSize;
+use crate::floor::{build_floor, rebuild_floor};
+use crate::room_templates::RoomTemplates;
+use crate::rooms::{CurrentRoom, RoomGraph, RoomId, RoomMember};
+use crate::walls::{resolve_obstacles, Obstacle};
+use crate::state::{despawn_with, AppState};
+use crate::rng::GameRng;
+use crate::simulation::Interpolated;
+use crate::movement::move_player;
+use crate::collision::{Collider, CollisionSet, Layers};
+use crate::combat::{DamageEvent, DamageKind, DamageSet, Health};
+use crate::enemy_kinds::{Behavior, EnemyKind, EnemyRegistry};
+use crate::status::{Chilled, Element};
+
+/// Sorteios de posição por inimigo antes de desistir dele, para uma sala
+/// sem lugar livre não travar o jogo.
+const MAX_SPAWN_ATTEMPTS: u32 = 32;
 
 pub struct EnemiesPlugin;
 
 impl Plugin for EnemiesPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_systems(Startup, spawn_enemies)
-            .add_systems(Update, move_enemies);
+            .add_event::<EnemyKilled>()
+            .add_systems(Startup, spawn_enemies.after(build_floor))
+            .add_systems(OnExit(AppState::GameOver), spawn_enemies.after(rebuild_floor).after(despawn_with::<RoomMember>))
+            .add_systems(FixedUpdate, (
+                // Depois do jogador, para perseguirem a posição deste passo
+                (move_enemies.after(move_player), move_enemy_bullets).before(CollisionSet),
+                damage_enemies.in_set(DamageSet),
+                split_enemies.in_set(DamageSet).after(damage_enemies),
+                update_hit_flash.before(DamageSet),
+            ).run_if(in_state(AppState::Playing)));
     }
 }
 
 #[derive(Component)]
 pub struct Enemy {
-    pub room: RoomId,
+    pub kind: String,         // Nome do tipo no `EnemyRegistry`
+    pub speed: f32,
+    pub contact_damage: u32,  // Dano ao jogador ao encostar, também usado pelos tiros
+    // Elementos cujos efeitos de estado não afetam este inimigo
+    pub immunities: Vec<Element>,
 }
 
-fn spawn_enemies(
-    mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    current_room: Res<CurrentRoom>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-) {
-    let window = window_query.single();
-    let window_width = window.width();
-    let window_height = window.height();
+impl Enemy {
+    pub fn is_immune(&self, element: Element) -> bool {
+        self.immunities.contains(&element)
+    }
+}
+
+/// Estado do comportamento de cada inimigo entre um passo e outro.
+#[derive(Component, Default, Debug, Clone, Copy)]
+pub struct BehaviorState {
+    pub phase: ChargePhase,
+    pub direction: Vec2,
+    pub timer: f32, // Segundos até a próxima troca de direção, fase ou disparo
+}
+
+impl BehaviorState {
+    pub fn new(behavior: &Behavior) -> Self {
+        let timer = match behavior {
+            // O atirador espera um intervalo antes do primeiro disparo
+            Behavior::Shooter { fire_interval, .. } => *fire_interval,
+            _ => 0.0,
+        };
+        BehaviorState { timer, ..default() }
+    }
+}
+
+/// Fases do avanço do `Charger`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChargePhase {
+    #[default]
+    Approach,
+    WindUp,
+    Charge,
+}
 
-    let x_bound = (window_width - GameConfig::WALL_THICKNESS - GameConfig::ENEMY_SIZE) / 2.0;
-    let y_bound = (window_height - GameConfig::WALL_THICKNESS - GameConfig::ENEMY_SIZE) / 2.0;
+/// Projétil disparado por inimigos; causa `damage` ao jogador.
+#[derive(Component)]
+pub struct EnemyBullet {
+    pub velocity: Vec2,
+    pub damage: u32,
+}
 
-    let mut rng = rand::thread_rng();
-    for _ in 0..3 {
-        let x = rng.gen_range(-x_bound..x_bound);
-        let y = rng.gen_range(-y_bound..y_bound);
+/// Enviado quando um inimigo fica sem vida e sai da cena.
+#[derive(Event, Debug, Clone)]
+pub struct EnemyKilled {
+    pub entity: Entity,
+    pub kind: String,
+    pub position: Vec2,
+    pub room: RoomId,
+}
+
+/// Inimigo que acabou de levar dano: fica invulnerável e piscando até o
+/// tempo acabar, quando volta à cor original.
+#[derive(Component)]
+pub struct HitFlash {
+    pub timer: Timer,
+    color: Option<Color>,
+}
 
-        commands.spawn((
-            Enemy {
-                room: current_room.id,
+/// Cria um inimigo do tipo dado, com visual, na posição indicada.
+pub fn spawn_enemy(
+    commands: &mut Commands,
+    kind: &EnemyKind,
+    room: RoomId,
+    position: Vec2,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+) -> Entity {
+    commands
+        .spawn((
+            kind.bundle(room),
+            Interpolated::default(),
+            MaterialMesh2dBundle {
+                mesh: meshes.add(Circle::new(kind.size)).into(),
+                material: materials.add(ColorMaterial::from(kind.color())),
+                transform: Transform::from_xyz(position.x, position.y, 0.0),
+                ..default()
             },
+        ))
+        .id()
+}
+
+/// Recursos para povoar uma sala: tipos e tabelas de aparição, modelo da
+/// sala, sorteio e visual dos inimigos.
+#[derive(SystemParam)]
+pub struct RoomEnemySpawner<'w> {
+    pub registry: Res<'w, EnemyRegistry>,
+    pub room_graph: Res<'w, RoomGraph>,
+    pub templates: Res<'w, RoomTemplates>,
+    pub arena: Res<'w, ArenaSize>,
+    pub tuning: Res<'w, GameTuning>,
+    pub game_rng: ResMut<'w, GameRng>,
+    pub meshes: ResMut<'w, Assets<Mesh>>,
+    pub materials: ResMut<'w, Assets<ColorMaterial>>,
+}
+
+impl RoomEnemySpawner<'_> {
+    /// Cria os inimigos de uma sala pela tabela de aparição dela, longe de `avoid`.
+    ///
+    /// Se o modelo da sala tiver pontos de aparição, os inimigos saem deles;
+    /// senão, de lugares livres sorteados na arena.
+    pub fn spawn_room(&mut self, commands: &mut Commands, room: RoomId, avoid: Vec2) {
+        let Some(table) = self.registry.table_for(room) else {
+            return;
+        };
+        let layout = self.templates.layout_for(&self.room_graph, room, &self.arena);
+        let tuning = &self.tuning;
+        let rng = &mut self.game_rng.enemies;
+        let safe_spawns: Vec<Vec2> = layout
+            .enemy_spawns
+            .iter()
+            .copied()
+            .filter(|point| point.distance(avoid) > tuning.spawn_safe_radius)
+            .collect();
+        let spawns = if safe_spawns.is_empty() { &layout.enemy_spawns } else { &safe_spawns };
+
+        for _ in 0..table.count {
+            let kind = self.registry.get(table.pick(rng)).expect("tabela validada com tipos existentes");
+            if let Some(point) = spawns.choose(rng) {
+                spawn_enemy(commands, kind, room, *point, &mut self.meshes, &mut self.materials);
+                continue;
+            }
+
+            let bounds = self.arena.bounds(tuning.wall_thickness, kind.size);
+
+            // Evita nascer em cima do jogador ou dentro de um obstáculo.
+            let position = (0..MAX_SPAWN_ATTEMPTS)
+                .map(|_| Vec2::new(rng.gen_range(-bounds.x..bounds.x), rng.gen_range(-bounds.y..bounds.y)))
+                .find(|position| position.distance(avoid) > tuning.spawn_safe_radius && !layout.is_blocked(*position, kind.size));
+            let Some(position) = position else {
+                warn!("Nenhum lugar livre para {:?} na sala {room:?} em {MAX_SPAWN_ATTEMPTS} tentativas; pulando o inimigo", kind.name);
+                continue;
+            };
+
+            spawn_enemy(commands, kind, room, position, &mut self.meshes, &mut self.materials);
+        }
+    }
+}
+
+/// Sorteia os inimigos da sala atual pela tabela de aparição dela.
+fn spawn_enemies(mut commands: Commands, current_room: Res<CurrentRoom>, mut spawner: RoomEnemySpawner) {
+    // O jogador começa na origem
+    spawner.spawn_room(&mut commands, current_room.id, Vec2::ZERO);
+}
+
+/// O que `move_enemies` lê e altera em cada inimigo.
+type MovingEnemy = (
+    &'static Enemy,
+    &'static RoomMember,
+    &'static Behavior,
+    &'static mut BehaviorState,
+    &'static Collider,
+    &'static mut Transform,
+    Option<&'static Chilled>,
+);
+
+/// Obstáculos da sala, fora de qualquer consulta que mexa no `Transform` de
+/// inimigos ou do jogador.
+type ObstacleOnly = (Without<Enemy>, Without<Player>);
+
+/// Onde os inimigos podem andar: dentro da arena e fora dos obstáculos.
+#[derive(SystemParam)]
+struct EnemyBounds<'w, 's> {
+    arena: Res<'w, ArenaSize>,
+    tuning: Res<'w, GameTuning>,
+    obstacle_query: Query<'w, 's, (&'static Transform, &'static Obstacle), ObstacleOnly>,
+}
+
+impl EnemyBounds<'_, '_> {
+    fn obstacles(&self) -> Vec<Rect> {
+        self.obstacle_query
+            .iter()
+            .map(|(transform, obstacle)| obstacle.rect(transform.translation.xy()))
+            .collect()
+    }
+
+    /// Ponto mais próximo de `target` dentro da arena e fora dos obstáculos.
+    fn resolve(&self, target: Vec2, radius: f32, obstacles: &[Rect]) -> Vec2 {
+        let bounds = self.arena.bounds(self.tuning.wall_thickness, radius);
+        resolve_obstacles(target.clamp(-bounds, bounds), radius, obstacles)
+    }
+}
+
+/// Cria os tiros dos inimigos atiradores.
+#[derive(SystemParam)]
+struct EnemyBulletSpawner<'w, 's> {
+    commands: Commands<'w, 's>,
+    meshes: ResMut<'w, Assets<Mesh>>,
+    materials: ResMut<'w, Assets<ColorMaterial>>,
+}
+
+impl EnemyBulletSpawner<'_, '_> {
+    fn spawn(&mut self, room: RoomId, position: Vec2, velocity: Vec2, damage: u32) {
+        self.commands.spawn((
+            EnemyBullet { velocity, damage },
+            RoomMember(room),
+            Interpolated::default(),
+            Collider::circle(5.0, Layers::ENEMY_PROJECTILE, Layers::PLAYER),
             MaterialMesh2dBundle {
-                mesh: meshes.add(Circle::new(GameConfig::ENEMY_SIZE)).into(),
-                material: materials.add(ColorMaterial::from(Color::GREEN)),
-                transform: Transform::from_xyz(x, y, 0.0),
+                mesh: self.meshes.add(Circle::new(5.0)).into(),
+                material: self.materials.add(ColorMaterial::from(Color::rgb(1.0, 0.4, 0.4))),
+                transform: Transform::from_xyz(position.x, position.y, 0.0),
                 ..default()
             },
         ));
@@ -56,35 +261,220 @@ fn spawn_enemies(
 }
 
 fn move_enemies(
-    mut enemy_query: Query<&mut Transform, (With<Enemy>, Without<Player>)>,
+    mut enemy_query: Query<MovingEnemy, Without<Player>>,
     player_query: Query<&Transform, (With<Player>, Without<Enemy>)>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    room_bounds: EnemyBounds,
+    tuning: Res<GameTuning>,
     time: Res<Time>,
+    mut game_rng: ResMut<GameRng>,
+    mut bullets: EnemyBulletSpawner,
 ) {
-    let window = window_query.single();
-    let window_width = window.width();
-    let window_height = window.height();
-
-    let x_bound = (window_width - GameConfig::WALL_THICKNESS - GameConfig::ENEMY_SIZE) / 2.0;
-    let y_bound = (window_height - GameConfig::WALL_THICKNESS - GameConfig::ENEMY_SIZE) / 2.0;
-
-    let player_transform = player_query.single();
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
     let player_pos = player_transform.translation.xy();
+    let delta = time.delta_seconds();
+    let obstacles = room_bounds.obstacles();
 
-    for mut transform in enemy_query.iter_mut() {
+    for (enemy, member, behavior, mut state, collider, mut transform, chilled) in enemy_query.iter_mut() {
         let enemy_pos = transform.translation.xy();
-        let direction = (player_pos - enemy_pos).normalize_or_zero();
+        let to_player = player_pos - enemy_pos;
+        let toward = to_player.normalize_or_zero();
+
+        // Inimigos congelados andam mais devagar
+        let slow = if chilled.is_some() { 1.0 - tuning.status.chill_slow } else { 1.0 };
+        let speed = enemy.speed * slow;
+        state.timer -= delta;
+
+        let velocity = match behavior {
+            Behavior::Chaser | Behavior::Splitter { .. } => toward * speed,
+            Behavior::Wanderer { turn_interval } => {
+                if state.timer <= 0.0 || state.direction == Vec2::ZERO {
+                    state.direction = Vec2::from_angle(game_rng.enemies.gen_range(0.0..TAU));
+                    state.timer = *turn_interval;
+                }
+                state.direction * speed
+            }
+            Behavior::Charger { range, wind_up, charge_speed, charge_duration } => match state.phase {
+                ChargePhase::Approach if to_player.length() <= *range => {
+                    // Para e cresce um pouco para avisar que vai avançar
+                    state.phase = ChargePhase::WindUp;
+                    state.timer = *wind_up;
+                    transform.scale = Vec3::splat(1.2);
+                    Vec2::ZERO
+                }
+                ChargePhase::Approach => toward * speed,
+                ChargePhase::WindUp => {
+                    if state.timer <= 0.0 {
+                        state.phase = ChargePhase::Charge;
+                        state.direction = toward;
+                        state.timer = *charge_duration;
+                        transform.scale = Vec3::ONE;
+                    }
+                    Vec2::ZERO
+                }
+                ChargePhase::Charge => {
+                    if state.timer <= 0.0 {
+                        state.phase = ChargePhase::Approach;
+                    }
+                    state.direction * *charge_speed * slow
+                }
+            },
+            Behavior::Shooter { preferred_distance, fire_interval, bullet_speed } => {
+                if state.timer <= 0.0 {
+                    state.timer = *fire_interval;
+                    bullets.spawn(member.0, enemy_pos, toward * *bullet_speed, enemy.contact_damage);
+                }
+                // Aproxima ou se afasta para ficar perto da distância preferida
+                let distance = to_player.length();
+                let margin = preferred_distance * 0.1;
+                if distance > preferred_distance + margin {
+                    toward * speed
+                } else if distance < preferred_distance - margin {
+                    -toward * speed
+                } else {
+                    Vec2::ZERO
+                }
+            }
+        };
+
+        let target = enemy_pos + velocity * delta;
+        let clamped = room_bounds.resolve(target, collider.radius(), &obstacles);
+        if clamped != target && matches!(behavior, Behavior::Wanderer { .. }) {
+            // Bateu na parede: escolhe outra direção no próximo passo
+            state.timer = 0.0;
+        }
+
+        transform.translation.x = clamped.x;
+        transform.translation.y = clamped.y;
+    }
+}
+
+fn move_enemy_bullets(
+    mut commands: Commands,
+    mut bullet_query: Query<(Entity, &mut Transform, &EnemyBullet)>,
+    obstacle_query: Query<(&Transform, &Obstacle), Without<EnemyBullet>>,
+    arena: Res<ArenaSize>,
+    tuning: Res<GameTuning>,
+    time: Res<Time>,
+) {
+    let bounds = arena.bounds(tuning.wall_thickness, 0.0);
+
+    for (entity, mut transform, bullet) in bullet_query.iter_mut() {
+        transform.translation += bullet.velocity.extend(0.0) * time.delta_seconds();
+
+        let position = transform.translation.xy();
+        let hit_wall = obstacle_query
+            .iter()
+            .any(|(wall, obstacle)| obstacle.blocks_projectiles && obstacle.rect(wall.translation.xy()).contains(position));
+        if hit_wall || position.x.abs() > bounds.x || position.y.abs() > bounds.y {
+            commands.entity(entity).despawn();
+        }
+    }
+}
 
-        let dx = direction.x * GameConfig::ENEMY_SPEED * time.delta_seconds();
-        let dy = direction.y * GameConfig::ENEMY_SPEED * time.delta_seconds();
+/// Inimigos do tipo `Splitter` se dividem ao morrer.
+fn split_enemies(
+    mut commands: Commands,
+    mut killed_events: EventReader<EnemyKilled>,
+    registry: Res<EnemyRegistry>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for event in killed_events.read() {
+        let Some(Behavior::Splitter { into, count }) = registry.get(&event.kind).map(|kind| &kind.behavior) else {
+            continue;
+        };
+        let Some(child) = registry.get(into) else {
+            continue;
+        };
 
-        let mut new_x = transform.translation.x + dx;
-        let mut new_y = transform.translation.y + dy;
+        // Espalha os pedaços em volta de onde o inimigo morreu
+        for i in 0..*count {
+            let angle = TAU * i as f32 / *count as f32;
+            let position = event.position + Vec2::from_angle(angle) * child.size;
+            spawn_enemy(&mut commands, child, event.room, position, &mut meshes, &mut materials);
+        }
+    }
+}
+
+/// O que `damage_enemies` lê e altera em cada inimigo atingido.
+type DamagedEnemy = (
+    &'static Transform,
+    &'static Enemy,
+    &'static RoomMember,
+    &'static mut Health,
+    Option<&'static Handle<ColorMaterial>>,
+    Has<HitFlash>,
+);
 
-        new_x = new_x.clamp(-x_bound, x_bound);
-        new_y = new_y.clamp(-y_bound, y_bound);
+/// Aplica o dano recebido. Inimigos piscando ignoram novos acertos, mas
+/// continuam sofrendo o dano dos efeitos de estado.
+fn damage_enemies(
+    mut commands: Commands,
+    mut damage_events: EventReader<DamageEvent>,
+    mut killed_events: EventWriter<EnemyKilled>,
+    mut enemy_query: Query<DamagedEnemy>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    tuning: Res<GameTuning>,
+) {
+    // O `HitFlash` só aparece no próximo passo, então marca quem já foi atingido neste
+    let mut hit = HashSet::new();
+    let mut dead = HashSet::new();
 
-        transform.translation.x = new_x;
-        transform.translation.y = new_y;
+    for event in damage_events.read() {
+        let Ok((transform, enemy, member, mut health, material, flashing)) = enemy_query.get_mut(event.target) else {
+            continue;
+        };
+        if dead.contains(&event.target) {
+            continue;
+        }
+        let is_hit = event.kind == DamageKind::Hit;
+        if is_hit && (flashing || !hit.insert(event.target)) {
+            continue;
+        }
+
+        if health.take(event.amount) {
+            dead.insert(event.target);
+            commands.entity(event.target).despawn();
+            killed_events.send(EnemyKilled {
+                entity: event.target,
+                kind: enemy.kind.clone(),
+                position: transform.translation.xy(),
+                room: member.0,
+            });
+            continue;
+        }
+        if !is_hit {
+            continue;
+        }
+
+        // Pisca em branco e guarda a cor para restaurar depois
+        let color = material.and_then(|handle| materials.get_mut(handle)).map(|material| {
+            let color = material.color;
+            material.color = Color::WHITE;
+            color
+        });
+        commands.entity(event.target).insert(HitFlash {
+            timer: Timer::from_seconds(tuning.enemy_invulnerability, TimerMode::Once),
+            color,
+        });
+    }
+}
+
+fn update_hit_flash(
+    mut commands: Commands,
+    mut flash_query: Query<(Entity, &mut HitFlash, Option<&Handle<ColorMaterial>>)>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    time: Res<Time>,
+) {
+    for (entity, mut flash, material) in flash_query.iter_mut() {
+        if !flash.timer.tick(time.delta()).finished() {
+            continue;
+        }
+        if let (Some(color), Some(material)) = (flash.color, material.and_then(|handle| materials.get_mut(handle))) {
+            material.color = color;
+        }
+        commands.entity(entity).remove::<HitFlash>();
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,263 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+use rand::Rng;
+use crate::collision::{Collider, Layers};
+use crate::combat::Health;
+use crate::enemies::{BehaviorState, Enemy};
+use crate::rooms::{RoomId, RoomMember};
+use crate::status::Element;
+
+/// Caminho padrão do arquivo com os tipos de inimigo.
+pub const ENEMY_KINDS_PATH: &str = "assets/enemies.ron";
+
+/// Cópia do arquivo embutida no executável, usada quando ele falta ou é inválido.
+const BUILTIN_ENEMY_KINDS: &str = include_str!("../assets/enemies.ron");
+
+/// Plugin que carrega o `EnemyRegistry` do disco no início do jogo.
+pub struct EnemyKindsPlugin;
+
+impl Plugin for EnemyKindsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<EnemyRegistry>()
+            .add_systems(PreStartup, load_enemy_kinds);
+    }
+}
+
+/// Comportamento de um tipo de inimigo e seus parâmetros.
+#[derive(Component, Deserialize, Debug, Clone, PartialEq)]
+pub enum Behavior {
+    /// Vai direto até o jogador.
+    Chaser,
+    /// Anda em direções aleatórias, trocando a cada `turn_interval` segundos.
+    Wanderer { turn_interval: f32 },
+    /// Persegue o jogador e, ao chegar a `range`, para por `wind_up` segundos
+    /// e avança em linha reta a `charge_speed` por `charge_duration` segundos.
+    Charger { range: f32, wind_up: f32, charge_speed: f32, charge_duration: f32 },
+    /// Mantém `preferred_distance` do jogador e atira a cada `fire_interval` segundos.
+    Shooter { preferred_distance: f32, fire_interval: f32, bullet_speed: f32 },
+    /// Persegue o jogador e, ao morrer, se divide em `count` inimigos do tipo `into`.
+    Splitter { into: String, count: u32 },
+}
+
+/// Um tipo de inimigo, como descrito no arquivo de inimigos.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct EnemyKind {
+    pub name: String,
+    pub size: f32,                // Raio
+    pub speed: f32,               // Velocidade de movimento
+    pub health: f32,              // Vida
+    pub color: (f32, f32, f32),   // Cor em RGB, de 0 a 1
+    pub contact_damage: u32,      // Dano ao jogador ao encostar ou com tiros
+    pub behavior: Behavior,
+    #[serde(default)]
+    pub immunities: Vec<Element>, // Elementos cujos efeitos não o afetam
+    #[serde(default = "default_score_weight")]
+    pub score_weight: f32,        // Multiplica `kill_score` ao derrotá-lo
+}
+
+fn default_score_weight() -> f32 {
+    1.0
+}
+
+impl EnemyKind {
+    pub fn color(&self) -> Color {
+        Color::rgb(self.color.0, self.color.1, self.color.2)
+    }
+
+    /// Componentes de jogo de um inimigo deste tipo, sem `Transform` nem visual.
+    pub fn bundle(&self, room: RoomId) -> impl Bundle {
+        (
+            Enemy {
+                kind: self.name.clone(),
+                speed: self.speed,
+                contact_damage: self.contact_damage,
+                immunities: self.immunities.clone(),
+            },
+            RoomMember(room),
+            self.behavior.clone(),
+            BehaviorState::new(&self.behavior),
+            Health::new(self.health),
+            Collider::circle(
+                self.size,
+                Layers::ENEMY,
+                Layers::PLAYER | Layers::PROJECTILE | Layers::ORBITER,
+            ),
+        )
+    }
+}
+
+/// Um tipo que pode aparecer em uma sala e seu peso no sorteio.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SpawnEntry {
+    pub kind: String,
+    pub weight: u32,
+}
+
+/// Quantos inimigos aparecem em uma sala e de quais tipos.
+///
+/// Sem `room`, a tabela vale para todas as salas que não têm uma própria.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SpawnTable {
+    #[serde(default)]
+    pub room: Option<RoomId>,
+    pub count: u32,
+    pub entries: Vec<SpawnEntry>,
+}
+
+impl SpawnTable {
+    /// Sorteia um tipo de acordo com os pesos.
+    pub fn pick(&self, rng: &mut impl Rng) -> &str {
+        let total: u32 = self.entries.iter().map(|entry| entry.weight).sum();
+        let mut roll = rng.gen_range(0..total);
+        for entry in &self.entries {
+            if roll < entry.weight {
+                return &entry.kind;
+            }
+            roll -= entry.weight;
+        }
+        unreachable!("os pesos somam {total}")
+    }
+}
+
+/// Recurso com todos os tipos de inimigo e as tabelas de aparição por sala.
+#[derive(Resource, Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct EnemyRegistry {
+    pub kinds: Vec<EnemyKind>,
+    pub spawn_tables: Vec<SpawnTable>,
+}
+
+impl Default for EnemyRegistry {
+    fn default() -> Self {
+        EnemyRegistry::from_ron(BUILTIN_ENEMY_KINDS).expect("assets/enemies.ron embutido é válido")
+    }
+}
+
+impl EnemyRegistry {
+    /// Lê, interpreta e valida um arquivo RON de inimigos.
+    pub fn load(path: &Path) -> Result<Self, EnemyKindsError> {
+        let text = std::fs::read_to_string(path).map_err(EnemyKindsError::Io)?;
+        Self::from_ron(&text)
+    }
+
+    /// Interpreta e valida inimigos a partir de um texto RON.
+    pub fn from_ron(text: &str) -> Result<Self, EnemyKindsError> {
+        let registry: EnemyRegistry = ron::from_str(text).map_err(EnemyKindsError::Parse)?;
+        registry.validate()?;
+        Ok(registry)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&EnemyKind> {
+        self.kinds.iter().find(|kind| kind.name == name)
+    }
+
+    /// Tabela da sala, ou a tabela geral se a sala não tiver uma.
+    pub fn table_for(&self, room: RoomId) -> Option<&SpawnTable> {
+        self.spawn_tables
+            .iter()
+            .find(|table| table.room == Some(room))
+            .or_else(|| self.spawn_tables.iter().find(|table| table.room.is_none()))
+    }
+
+    /// Verifica nomes repetidos, valores fora do lugar e referências a tipos inexistentes.
+    pub fn validate(&self) -> Result<(), EnemyKindsError> {
+        let mut names = HashSet::new();
+        for kind in &self.kinds {
+            let invalid = |reason: String| EnemyKindsError::Invalid { kind: kind.name.clone(), reason };
+            if !names.insert(kind.name.as_str()) {
+                return Err(invalid("nome repetido".to_string()));
+            }
+            if !kind.size.is_finite() || kind.size <= 0.0 {
+                return Err(invalid(format!("`size` deve ser maior que zero (recebido {})", kind.size)));
+            }
+            if !kind.health.is_finite() || kind.health <= 0.0 {
+                return Err(invalid(format!("`health` deve ser maior que zero (recebido {})", kind.health)));
+            }
+            if !kind.speed.is_finite() || kind.speed < 0.0 {
+                return Err(invalid(format!("`speed` não pode ser negativo (recebido {})", kind.speed)));
+            }
+            if !kind.score_weight.is_finite() || kind.score_weight < 0.0 {
+                return Err(invalid(format!("`score_weight` não pode ser negativo (recebido {})", kind.score_weight)));
+            }
+        }
+
+        for kind in &self.kinds {
+            if let Behavior::Splitter { into, .. } = &kind.behavior {
+                if into == &kind.name {
+                    return Err(EnemyKindsError::Invalid {
+                        kind: kind.name.clone(),
+                        reason: "não pode se dividir em si mesmo".to_string(),
+                    });
+                }
+                if !names.contains(into.as_str()) {
+                    return Err(EnemyKindsError::Invalid {
+                        kind: kind.name.clone(),
+                        reason: format!("tipo `{into}` não existe"),
+                    });
+                }
+            }
+        }
+
+        for table in &self.spawn_tables {
+            let table_name = match table.room {
+                Some(room) => format!("tabela da sala {room:?}"),
+                None => "tabela geral".to_string(),
+            };
+            if table.entries.iter().map(|entry| entry.weight).sum::<u32>() == 0 {
+                return Err(EnemyKindsError::Invalid {
+                    kind: table_name,
+                    reason: "precisa de pelo menos um tipo com peso maior que zero".to_string(),
+                });
+            }
+            if let Some(entry) = table.entries.iter().find(|entry| !names.contains(entry.kind.as_str())) {
+                return Err(EnemyKindsError::Invalid {
+                    kind: table_name,
+                    reason: format!("tipo `{}` não existe", entry.kind),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Erros ao carregar o arquivo de inimigos.
+#[derive(Debug)]
+pub enum EnemyKindsError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    Invalid { kind: String, reason: String },
+}
+
+impl fmt::Display for EnemyKindsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnemyKindsError::Io(err) => write!(f, "não foi possível ler o arquivo: {err}"),
+            EnemyKindsError::Parse(err) => write!(f, "erro de sintaxe: {err}"),
+            EnemyKindsError::Invalid { kind, reason } => write!(f, "`{kind}` inválido: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for EnemyKindsError {}
+
+/// Carrega os tipos de inimigo. Sem arquivo, fica a cópia embutida.
+fn load_enemy_kinds(mut registry: ResMut<EnemyRegistry>) {
+    let path = Path::new(ENEMY_KINDS_PATH);
+    if !path.exists() {
+        info!("Arquivo de inimigos {:?} não encontrado, usando os tipos padrão", path);
+        return;
+    }
+
+    match EnemyRegistry::load(path) {
+        Ok(loaded) => *registry = loaded,
+        Err(err) => error!("Inimigos em {:?} ignorados: {}", path, err),
+    }
+}
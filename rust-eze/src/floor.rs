@@ -0,0 +1,217 @@
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use crate::config::{FloorTuning, GameTuning};
+use crate::rng::GameRng;
+use crate::room_templates::RoomTemplates;
+use crate::rooms::{CurrentRoom, Direction, RoomGraph, RoomId, RoomKind};
+use crate::state::AppState;
+
+/// Tentativas de gerar um andar que cumpra todas as restrições antes de
+/// aceitar o último resultado.
+const MAX_ATTEMPTS: usize = 64;
+
+/// Plugin que gera o andar inteiro no início de cada partida.
+///
+/// O layout sai do fluxo `GameRng.rooms`, então a mesma semente gera o mesmo andar.
+pub struct FloorPlugin;
+
+impl Plugin for FloorPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Startup, build_floor)
+            .add_systems(OnExit(AppState::GameOver), rebuild_floor);
+    }
+}
+
+/// Gera um andar completo.
+///
+/// Primeiro cresce uma árvore na grade, sempre em células com um único
+/// vizinho ocupado, o que deixa corredores e becos sem saída. Depois cada
+/// ciclo pedido vira uma sala em uma célula encostada em duas salas
+/// distantes no grafo, ligada às duas. Por fim, o chefe vai para o beco
+/// mais distante e o tesouro e a loja para outros becos longe o bastante.
+/// Se o resultado não cumprir as restrições, gera de novo.
+pub fn generate_floor(params: &FloorTuning, rng: &mut impl Rng) -> RoomGraph {
+    let mut graph = RoomGraph::new();
+    for attempt in 1..=MAX_ATTEMPTS {
+        graph = RoomGraph::new();
+        let loops = grow_floor(&mut graph, params, rng);
+        if loops >= params.loops
+            && dead_ends(&graph).len() >= params.min_dead_ends
+            && place_special_rooms(&mut graph, params, rng)
+        {
+            debug!("Andar gerado na tentativa {attempt} com {} salas", graph.len());
+            return graph;
+        }
+    }
+
+    warn!("Nenhum andar cumpriu todas as restrições em {MAX_ATTEMPTS} tentativas; usando o último");
+    graph
+}
+
+/// Cresce o andar até `room_count` salas e devolve quantos ciclos criou.
+fn grow_floor(graph: &mut RoomGraph, params: &FloorTuning, rng: &mut impl Rng) -> usize {
+    let tree_rooms = params.room_count.saturating_sub(params.loops).max(1);
+    while graph.len() < tree_rooms {
+        if !add_branch(graph, params, rng) {
+            break;
+        }
+    }
+
+    let mut loops = 0;
+    while graph.len() < params.room_count {
+        if loops < params.loops && add_loop_room(graph, rng) {
+            loops += 1;
+        } else if !add_branch(graph, params, rng) {
+            break;
+        }
+    }
+    loops
+}
+
+/// Salas ocupadas ao redor de uma célula da grade.
+fn occupied_neighbors(graph: &RoomGraph, position: IVec2) -> Vec<(Direction, RoomId)> {
+    Direction::ALL
+        .into_iter()
+        .filter_map(|direction| graph.room_at(position + direction.as_ivec2()).map(|id| (direction, id)))
+        .collect()
+}
+
+/// Prende uma sala nova a uma sala existente, em uma célula livre que só
+/// encosta nela.
+fn add_branch(graph: &mut RoomGraph, params: &FloorTuning, rng: &mut impl Rng) -> bool {
+    let mut candidates = Vec::new();
+    for id in graph.room_ids() {
+        let room = graph.get_room(id).expect("id listado pelo grafo");
+        if room.connection_count() >= params.max_connections {
+            continue;
+        }
+        for direction in Direction::ALL {
+            let cell = room.position + direction.as_ivec2();
+            if graph.room_at(cell).is_none() && occupied_neighbors(graph, cell).len() == 1 {
+                candidates.push((id, direction));
+            }
+        }
+    }
+
+    let Some(&(parent, direction)) = candidates.choose(rng) else {
+        return false;
+    };
+    graph.add_room(parent, direction).is_some()
+}
+
+/// Cria uma sala em uma célula livre encostada em duas salas e liga as duas
+/// através dela, fechando um ciclo. Prefere as salas mais distantes no grafo.
+fn add_loop_room(graph: &mut RoomGraph, rng: &mut impl Rng) -> bool {
+    let mut cells: Vec<IVec2> = graph
+        .room_ids()
+        .into_iter()
+        .flat_map(|id| {
+            let position = graph.get_room(id).expect("id listado pelo grafo").position;
+            Direction::ALL.map(|direction| position + direction.as_ivec2())
+        })
+        .filter(|cell| graph.room_at(*cell).is_none())
+        .collect();
+    cells.sort_by_key(|cell| (cell.x, cell.y));
+    cells.dedup();
+
+    // Direções vistas a partir da célula livre: (sala a estender, direção dela, direção da outra)
+    let mut best: Vec<(RoomId, Direction, Direction)> = Vec::new();
+    let mut best_distance = 0;
+    for cell in cells {
+        let neighbors = occupied_neighbors(graph, cell);
+        for (i, &(first_dir, first)) in neighbors.iter().enumerate() {
+            let distances = graph.distances_from(first);
+            for &(second_dir, second) in &neighbors[i + 1..] {
+                let distance = distances.get(&second).copied().unwrap_or(0);
+                if distance > best_distance {
+                    best_distance = distance;
+                    best.clear();
+                }
+                if distance == best_distance && distance > 0 {
+                    best.push((first, first_dir, second_dir));
+                }
+            }
+        }
+    }
+
+    let Some(&(first, first_dir, second_dir)) = best.choose(rng) else {
+        return false;
+    };
+    let Some(new_id) = graph.add_room(first, first_dir.opposite()) else {
+        return false;
+    };
+    graph.connect(new_id, second_dir).is_some()
+}
+
+/// Salas com uma única porta, fora a inicial, em ordem estável.
+fn dead_ends(graph: &RoomGraph) -> Vec<RoomId> {
+    graph
+        .room_ids()
+        .into_iter()
+        .filter(|id| {
+            let room = graph.get_room(*id).expect("id listado pelo grafo");
+            room.kind != RoomKind::Start && room.connection_count() == 1
+        })
+        .collect()
+}
+
+/// Marca chefe, tesouro e loja em becos sem saída. Falha se não houver becos
+/// longe o bastante da sala inicial.
+fn place_special_rooms(graph: &mut RoomGraph, params: &FloorTuning, rng: &mut impl Rng) -> bool {
+    let distances = graph.distances_from(RoomId::Central);
+    let mut free = dead_ends(graph);
+
+    // O chefe fica no beco mais distante
+    let Some(boss_index) = (0..free.len()).max_by_key(|&i| (distances[&free[i]], std::cmp::Reverse(i))) else {
+        return false;
+    };
+    if distances[&free[boss_index]] < params.boss_distance {
+        return false;
+    }
+    let boss = free.remove(boss_index);
+
+    let mut chosen = vec![(boss, RoomKind::Boss)];
+    for (kind, min_distance) in [(RoomKind::Treasure, params.treasure_distance), (RoomKind::Shop, params.shop_distance)] {
+        let options: Vec<usize> = (0..free.len()).filter(|&i| distances[&free[i]] >= min_distance).collect();
+        let Some(&index) = options.choose(rng) else {
+            return false;
+        };
+        chosen.push((free.remove(index), kind));
+    }
+
+    for (id, kind) in chosen {
+        if let Some(room) = graph.get_room_mut(id) {
+            room.kind = kind;
+        }
+    }
+    true
+}
+
+/// Gera o andar da partida a partir da semente e sorteia o modelo de cada sala.
+pub(crate) fn build_floor(
+    mut room_graph: ResMut<RoomGraph>,
+    tuning: Res<GameTuning>,
+    templates: Res<RoomTemplates>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    *room_graph = generate_floor(&tuning.floor, &mut game_rng.rooms);
+    templates.assign(&mut room_graph, &mut game_rng.rooms);
+}
+
+/// Recomeço após o game over: semente nova, tirada da partida anterior, um
+/// andar novo sem nada da partida que acabou e o jogador na sala inicial.
+pub(crate) fn rebuild_floor(
+    mut room_graph: ResMut<RoomGraph>,
+    mut current_room: ResMut<CurrentRoom>,
+    tuning: Res<GameTuning>,
+    templates: Res<RoomTemplates>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    *game_rng = game_rng.next_run();
+    info!("Semente da nova partida: {}", game_rng.seed());
+    *current_room = CurrentRoom::default();
+    *room_graph = generate_floor(&tuning.floor, &mut game_rng.rooms);
+    templates.assign(&mut room_graph, &mut game_rng.rooms);
+}
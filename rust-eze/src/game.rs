@@ -1,162 +1,122 @@
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
-use bevy::sprite::MaterialMesh2dBundle;
-use rand::Rng;
-use crate::player::Player;
-use crate::enemies::Enemy;
-use crate::config::GameConfig;
+use bevy::ecs::system::SystemParam;
+use crate::player::{Invulnerable, Knockback, Player};
+use crate::enemies::{Enemy, EnemyBullet};
+use crate::config::GameTuning;
 use crate::lives::Lives;
-use crate::ui::{GameOverText, Score};
-use crate::rooms::CurrentRoom;
+use crate::rooms::{CurrentRoom, RoomMember};
+use crate::state::AppState;
+use crate::collision::{CollisionEvent, CollisionSet, Layers};
+use crate::movement::{Dash, Dashing};
 
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_systems(Update, (check_collision, handle_game_over));
+            .add_event::<PlayerDamaged>()
+            .add_systems(FixedUpdate, check_collision.after(CollisionSet).run_if(in_state(AppState::Playing)));
     }
 }
 
-pub fn check_collision(
-    mut lives_query: Query<&mut Lives>,
-    mut player_query: Query<(Entity, &mut Transform), (With<Player>, Without<Enemy>)>,
-    mut enemy_query: Query<(Entity, &mut Transform, &Enemy), (With<Enemy>, Without<Player>)>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    current_room: Res<CurrentRoom>,
-    mut game_over_text: Query<&mut Style, With<GameOverText>>,
-) {
-    let (_player_entity, mut player_transform) = player_query.single_mut();
-    let player_pos = player_transform.translation.xy();
-
-    for (_enemy_entity, enemy_transform, enemy) in enemy_query.iter() {
-        if enemy.room != current_room.id {
-            continue;
-        }
-
-        let enemy_pos = enemy_transform.translation.xy();
-        let distance = (player_pos - enemy_pos).length();
-
-        if distance < GameConfig::COLLISION_DISTANCE {
-            let mut lives = lives_query.single_mut();
-            let game_over = lives.lose_life();
+/// Enviado quando o jogador leva dano, com o dano e a posição de quem acertou.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PlayerDamaged {
+    pub amount: u32,
+    pub source: Vec2,
+}
 
-            if game_over {
-                let mut style = game_over_text.single_mut();
-                style.display = Display::Flex;
-            } else {
-                player_transform.translation = Vec3::new(0.0, 0.0, 0.0);
+/// Jogador que pode levar dano, com o que o protege.
+type HitPlayer = (Entity, &'static Transform, Has<Invulnerable>, Has<Dashing>, Option<&'static Dash>);
 
-                let window = window_query.single();
-                let window_width = window.width();
-                let window_height = window.height();
-                let x_bound = (window_width - GameConfig::WALL_THICKNESS - GameConfig::ENEMY_SIZE) / 2.0;
-                let y_bound = (window_height - GameConfig::WALL_THICKNESS - GameConfig::ENEMY_SIZE) / 2.0;
-                let mut rng = rand::thread_rng();
+/// Quem pode acertar o jogador: os inimigos da sala atual e os tiros deles.
+#[derive(SystemParam)]
+pub struct Attackers<'w, 's> {
+    enemies: Query<'w, 's, (&'static Transform, &'static Enemy, &'static RoomMember)>,
+    bullets: Query<'w, 's, (&'static Transform, &'static EnemyBullet)>,
+    current_room: Res<'w, CurrentRoom>,
+}
 
-                for (_, mut enemy_transform, enemy) in enemy_query.iter_mut() {
-                    if enemy.room != current_room.id {
-                        continue;
-                    }
-                    let mut new_x;
-                    let mut new_y;
-                    loop {
-                        new_x = rng.gen_range(-x_bound..x_bound);
-                        new_y = rng.gen_range(-y_bound..y_bound);
-                        let distance_from_player = Vec2::new(new_x, new_y).distance(Vec2::new(0.0, 0.0));
-                        if distance_from_player > 100.0 {
-                            break;
-                        }
-                    }
-                    enemy_transform.translation = Vec3::new(new_x, new_y, 0.0);
-                }
-            }
+/// Para onde vai o dano: as vidas, o aviso de dano e o fim de jogo.
+#[derive(SystemParam)]
+pub struct PlayerHealth<'w, 's> {
+    lives: Query<'w, 's, &'static mut Lives>,
+    damaged_events: EventWriter<'w, PlayerDamaged>,
+    next_state: ResMut<'w, NextState<AppState>>,
+}
 
-            break;
+impl PlayerHealth<'_, '_> {
+    /// Tira as vidas do golpe e avisa; devolve `true` se o jogo acabou.
+    fn take_hit(&mut self, hit: PlayerDamaged) -> bool {
+        self.damaged_events.send(hit);
+        if self.lives.single_mut().take_damage(hit.amount) {
+            self.next_state.set(AppState::GameOver);
+            return true;
         }
+        false
     }
 }
 
-pub fn handle_game_over(
+/// Aplica ao jogador o golpe mais forte do passo: tira pontos de vida, deixa
+/// o jogador invulnerável por um tempo e o empurra para longe de quem acertou.
+pub fn check_collision(
     mut commands: Commands,
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut lives_query: Query<&mut Lives>,
-    player_query: Query<(Entity, &Transform), (With<Player>, Without<Enemy>)>,
-    enemy_query: Query<(Entity, &Transform, &Enemy), (With<Enemy>, Without<Player>)>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    current_room: Res<CurrentRoom>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    mut game_over_text: Query<&mut Style, With<GameOverText>>,
-    mut time: ResMut<Time<Virtual>>,
-    mut score: ResMut<Score>,
+    mut collisions: EventReader<CollisionEvent>,
+    player_query: Query<HitPlayer, With<Player>>,
+    attackers: Attackers,
+    mut health: PlayerHealth,
+    tuning: Res<GameTuning>,
 ) {
-    let lives = lives_query.single();
-    if lives.count() > 0 {
-        time.unpause();
-        return;
-    }
-
-    time.pause();
-
-    if keyboard.just_pressed(KeyCode::Enter) {
-        let mut style = game_over_text.single_mut();
-        style.display = Display::None;
-
-        let (player_entity, _) = player_query.single();
-        commands.entity(player_entity).despawn();
-        for (enemy, _, enemy_data) in enemy_query.iter() {
-            if enemy_data.room == current_room.id {
-                commands.entity(enemy).despawn();
-            }
+    let mut hit: Option<PlayerDamaged> = None;
+    let mut consider = |amount: u32, source: Vec2| {
+        if hit.is_none_or(|hit| amount > hit.amount) {
+            hit = Some(PlayerDamaged { amount, source });
         }
-
-        commands.spawn((
-            Player,
-            MaterialMesh2dBundle {
-                mesh: meshes.add(Circle::new(GameConfig::PLAYER_SIZE)).into(),
-                material: materials.add(ColorMaterial::from(Color::RED)),
-                transform: Transform::from_xyz(0.0, 0.0, 0.0),
-                ..default()
-            },
-        ));
-
-        let window = window_query.single();
-        let window_width = window.width();
-        let window_height = window.height();
-
-        let x_bound = (window_width - GameConfig::WALL_THICKNESS - GameConfig::ENEMY_SIZE) / 2.0;
-        let y_bound = (window_height - GameConfig::WALL_THICKNESS - GameConfig::ENEMY_SIZE) / 2.0;
-
-        let mut rng = rand::thread_rng();
-        for _ in 0..3 {
-            let mut new_x;
-            let mut new_y;
-            loop {
-                new_x = rng.gen_range(-x_bound..x_bound);
-                new_y = rng.gen_range(-y_bound..y_bound);
-                let distance_from_player = Vec2::new(new_x, new_y).distance(Vec2::new(0.0, 0.0));
-                if distance_from_player > 100.0 {
-                    break;
-                }
+    };
+    // Na arrancada, o jogador passa pelos inimigos sem encostar e, no começo
+    // dela, os tiros não causam dano
+    let (dashing, shielded) = player_query.get_single().map_or((false, false), |(_, _, _, dashing, dash)| {
+        (dashing, dash.is_some_and(Dash::is_invulnerable))
+    });
+    for event in collisions.read() {
+        if !dashing
+            && let Some((_, enemy_entity)) = event.between(Layers::PLAYER, Layers::ENEMY)
+            && let Ok((transform, enemy, member)) = attackers.enemies.get(enemy_entity)
+            && member.0 == attackers.current_room.id
+        {
+            consider(enemy.contact_damage.max(1), transform.translation.xy());
+        }
+        if let Some((_, bullet_entity)) = event.between(Layers::PLAYER, Layers::ENEMY_PROJECTILE)
+            && let Ok((transform, bullet)) = attackers.bullets.get(bullet_entity)
+        {
+            // O tiro some ao acertar, mesmo com o jogador invulnerável
+            commands.entity(bullet_entity).despawn();
+            if shielded {
+                continue;
             }
-            commands.spawn((
-                Enemy {
-                    room: current_room.id,
-                },
-                MaterialMesh2dBundle {
-                    mesh: meshes.add(Circle::new(GameConfig::ENEMY_SIZE)).into(),
-                    material: materials.add(ColorMaterial::from(Color::GREEN)),
-                    transform: Transform::from_xyz(new_x, new_y, 0.0),
-                    ..default()
-                },
-            ));
+            consider(bullet.damage.max(1), transform.translation.xy());
         }
+    }
+    let Some(hit) = hit else {
+        return;
+    };
+    let Ok((player, player_transform, invulnerable, _, _)) = player_query.get_single() else {
+        return;
+    };
+    if invulnerable {
+        return;
+    }
 
-        let mut lives = lives_query.single_mut();
-        lives.reset();
-        score.value = 0;
-
-        time.unpause();
+    if health.take_hit(hit) {
+        return;
     }
+
+    let away = (player_transform.translation.xy() - hit.source).try_normalize().unwrap_or(Vec2::Y);
+    commands.entity(player).insert((
+        Invulnerable::new(tuning.player_invulnerability),
+        Knockback {
+            velocity: away * tuning.knockback_speed,
+            timer: Timer::from_seconds(tuning.knockback_duration, TimerMode::Once),
+        },
+    ));
 }
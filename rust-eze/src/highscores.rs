@@ -0,0 +1,405 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::actions::{Action, ActionState};
+use crate::arena::ArenaSize;
+use crate::enemies::EnemyKilled;
+use crate::rng::GameRng;
+use crate::rooms::RoomCleared;
+use crate::state::{despawn_with, restart_game, AppState};
+use crate::scoring::Score;
+
+/// Versão atual do arquivo de recordes.
+pub const HIGH_SCORES_VERSION: u32 = 1;
+
+/// Nome do arquivo de recordes dentro da pasta de dados do usuário.
+pub const HIGH_SCORES_FILE: &str = "highscores.ron";
+
+/// Quantas partidas a tabela guarda.
+pub const MAX_HIGH_SCORES: usize = 10;
+
+/// Tamanho máximo do nome digitado no game over.
+pub const MAX_NAME_LEN: usize = 12;
+
+/// Nome usado quando o jogador confirma sem digitar nada.
+const DEFAULT_NAME: &str = "PLAYER";
+
+/// Teclas aceitas na digitação do nome.
+const NAME_KEYS: [(KeyCode, char); 37] = [
+    (KeyCode::KeyA, 'A'), (KeyCode::KeyB, 'B'), (KeyCode::KeyC, 'C'), (KeyCode::KeyD, 'D'),
+    (KeyCode::KeyE, 'E'), (KeyCode::KeyF, 'F'), (KeyCode::KeyG, 'G'), (KeyCode::KeyH, 'H'),
+    (KeyCode::KeyI, 'I'), (KeyCode::KeyJ, 'J'), (KeyCode::KeyK, 'K'), (KeyCode::KeyL, 'L'),
+    (KeyCode::KeyM, 'M'), (KeyCode::KeyN, 'N'), (KeyCode::KeyO, 'O'), (KeyCode::KeyP, 'P'),
+    (KeyCode::KeyQ, 'Q'), (KeyCode::KeyR, 'R'), (KeyCode::KeyS, 'S'), (KeyCode::KeyT, 'T'),
+    (KeyCode::KeyU, 'U'), (KeyCode::KeyV, 'V'), (KeyCode::KeyW, 'W'), (KeyCode::KeyX, 'X'),
+    (KeyCode::KeyY, 'Y'), (KeyCode::KeyZ, 'Z'),
+    (KeyCode::Digit0, '0'), (KeyCode::Digit1, '1'), (KeyCode::Digit2, '2'), (KeyCode::Digit3, '3'),
+    (KeyCode::Digit4, '4'), (KeyCode::Digit5, '5'), (KeyCode::Digit6, '6'), (KeyCode::Digit7, '7'),
+    (KeyCode::Digit8, '8'), (KeyCode::Digit9, '9'),
+    (KeyCode::Space, ' '),
+];
+
+/// Plugin com a tabela de recordes e as estatísticas da partida.
+///
+/// No game over, a partida entra na tabela se tiver pontos para isso e o
+/// jogador digita o nome antes de recomeçar. A tabela só é lida e gravada em
+/// disco quando existe um `HighScoresFile`.
+pub struct HighScoresPlugin;
+
+impl Plugin for HighScoresPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<HighScores>()
+            .init_resource::<RunStats>()
+            .add_systems(Startup, load_high_scores.run_if(resource_exists::<HighScoresFile>))
+            .add_systems(FixedUpdate, track_run_stats.run_if(in_state(AppState::Playing)))
+            .add_systems(OnEnter(AppState::GameOver), (record_run, show_high_scores).chain())
+            .add_systems(Update, (
+                type_name.before(restart_game).run_if(resource_exists::<NameEntry>),
+                update_high_score_text,
+            ).chain().run_if(in_state(AppState::GameOver)))
+            .add_systems(OnExit(AppState::GameOver), (despawn_with::<HighScoreTable>, reset_run_stats));
+    }
+}
+
+/// Onde a tabela de recordes fica guardada.
+#[derive(Resource, Debug, Clone)]
+pub struct HighScoresFile {
+    pub path: PathBuf,
+}
+
+impl HighScoresFile {
+    /// Arquivo na pasta de dados do usuário, se ela existir.
+    pub fn in_user_data_dir() -> Option<Self> {
+        crate::profile::user_data_dir().map(|dir| HighScoresFile { path: dir.join(HIGH_SCORES_FILE) })
+    }
+}
+
+/// Números da partida em andamento, além da pontuação.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RunStats {
+    pub rooms_cleared: u32,
+    pub enemies_killed: u32,
+    pub duration: f32, // Segundos jogados, sem contar pausa
+}
+
+/// Uma partida na tabela de recordes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighScoreEntry {
+    pub name: String,
+    pub score: u32,
+    pub rooms_cleared: u32,
+    pub enemies_killed: u32,
+    pub duration: f32,
+    pub seed: u64,
+    pub timestamp: u64, // Segundos desde 1970, em UTC
+}
+
+/// Recurso com os recordes, do maior para o menor.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct HighScores {
+    entries: Vec<HighScoreEntry>,
+}
+
+/// Formato do arquivo. As entradas são lidas uma a uma para que uma
+/// entrada estragada não leve a tabela inteira junto.
+#[derive(Serialize, Deserialize)]
+struct HighScoresData<T> {
+    version: u32,
+    entries: Vec<T>,
+}
+
+/// Entrada lida do arquivo: válida, ou qualquer outro valor, que é descartado.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredEntry {
+    Valid(HighScoreEntry),
+    Invalid(serde::de::IgnoredAny),
+}
+
+impl HighScores {
+    pub fn entries(&self) -> &[HighScoreEntry] {
+        &self.entries
+    }
+
+    /// Verdadeiro se uma partida com essa pontuação entraria na tabela.
+    pub fn qualifies(&self, score: u32) -> bool {
+        self.entries.len() < MAX_HIGH_SCORES || self.entries.last().is_some_and(|last| score > last.score)
+    }
+
+    /// Coloca a partida na tabela e devolve a posição dela (0 é o primeiro
+    /// lugar). Em caso de empate, a partida mais antiga fica na frente.
+    pub fn insert(&mut self, entry: HighScoreEntry) -> Option<usize> {
+        if !self.qualifies(entry.score) {
+            return None;
+        }
+        let rank = self.entries.partition_point(|other| other.score >= entry.score);
+        self.entries.insert(rank, entry);
+        self.entries.truncate(MAX_HIGH_SCORES);
+        Some(rank)
+    }
+
+    /// Lê a tabela de um arquivo RON.
+    pub fn load(path: &Path) -> Result<Self, HighScoresError> {
+        let text = std::fs::read_to_string(path).map_err(HighScoresError::Io)?;
+        Self::from_ron(&text)
+    }
+
+    /// Interpreta a tabela. Entradas inválidas são descartadas com um aviso;
+    /// só um arquivo ilegível como um todo é erro.
+    pub fn from_ron(text: &str) -> Result<Self, HighScoresError> {
+        let data: HighScoresData<StoredEntry> = ron::from_str(text).map_err(HighScoresError::Parse)?;
+        if data.version != HIGH_SCORES_VERSION {
+            return Err(HighScoresError::UnsupportedVersion(data.version));
+        }
+
+        // Arquivo editado à mão pode vir fora de ordem ou com entradas demais
+        let mut high_scores = HighScores::default();
+        for (index, stored) in data.entries.into_iter().enumerate() {
+            match stored {
+                StoredEntry::Valid(entry) => {
+                    high_scores.insert(entry);
+                }
+                StoredEntry::Invalid(_) => warn!("Recorde {} inválido, ignorado", index + 1),
+            }
+        }
+        Ok(high_scores)
+    }
+
+    pub fn to_ron(&self) -> Result<String, HighScoresError> {
+        let data = HighScoresData {
+            version: HIGH_SCORES_VERSION,
+            entries: self.entries.clone(),
+        };
+        ron::ser::to_string_pretty(&data, ron::ser::PrettyConfig::default()).map_err(HighScoresError::Serialize)
+    }
+
+    /// Grava a tabela, criando a pasta se preciso. Escreve em um arquivo
+    /// temporário e troca no fim, para um erro no meio não estragar a tabela.
+    pub fn save(&self, path: &Path) -> Result<(), HighScoresError> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(HighScoresError::Io)?;
+        }
+        let temp = path.with_extension("ron.tmp");
+        std::fs::write(&temp, self.to_ron()?).map_err(HighScoresError::Io)?;
+        std::fs::rename(&temp, path).map_err(HighScoresError::Io)
+    }
+}
+
+/// Erros ao ler ou gravar a tabela de recordes.
+#[derive(Debug)]
+pub enum HighScoresError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    Serialize(ron::Error),
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for HighScoresError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HighScoresError::Io(err) => write!(f, "erro de arquivo: {err}"),
+            HighScoresError::Parse(err) => write!(f, "tabela inválida: {err}"),
+            HighScoresError::Serialize(err) => write!(f, "não foi possível gerar a tabela: {err}"),
+            HighScoresError::UnsupportedVersion(version) => {
+                write!(f, "versão de tabela {version} não suportada (atual {HIGH_SCORES_VERSION})")
+            }
+        }
+    }
+}
+
+impl std::error::Error for HighScoresError {}
+
+/// Recurso presente enquanto o jogador digita o nome de um recorde novo.
+#[derive(Resource, Debug, Clone)]
+pub struct NameEntry {
+    pub entry: HighScoreEntry,
+    pub name: String,
+}
+
+/// Texto com a tabela de recordes na tela de game over.
+#[derive(Component)]
+pub struct HighScoreTable;
+
+/// Lê a tabela do disco. Um arquivo estragado é guardado com a extensão
+/// `.bak` e a tabela começa vazia.
+fn load_high_scores(file: Res<HighScoresFile>, mut high_scores: ResMut<HighScores>) {
+    if !file.path.exists() {
+        return;
+    }
+    match HighScores::load(&file.path) {
+        Ok(loaded) => *high_scores = loaded,
+        Err(err) => {
+            let backup = file.path.with_extension("ron.bak");
+            error!("Recordes em {:?} ignorados: {}; cópia em {:?}", file.path, err, backup);
+            if let Err(err) = std::fs::rename(&file.path, &backup) {
+                error!("Não foi possível guardar a cópia: {}", err);
+            }
+        }
+    }
+}
+
+fn track_run_stats(
+    time: Res<Time>,
+    mut stats: ResMut<RunStats>,
+    mut killed_events: EventReader<EnemyKilled>,
+    mut cleared_events: EventReader<RoomCleared>,
+) {
+    stats.duration += time.delta_seconds();
+    stats.enemies_killed += killed_events.read().count() as u32;
+    stats.rooms_cleared += cleared_events.read().count() as u32;
+}
+
+fn reset_run_stats(mut stats: ResMut<RunStats>) {
+    *stats = RunStats::default();
+}
+
+/// Monta a entrada da partida que acabou e, se ela couber na tabela, abre
+/// a digitação do nome.
+fn record_run(
+    mut commands: Commands,
+    score: Res<Score>,
+    stats: Res<RunStats>,
+    game_rng: Res<GameRng>,
+    high_scores: Res<HighScores>,
+) {
+    if !high_scores.qualifies(score.value) {
+        return;
+    }
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+    commands.insert_resource(NameEntry {
+        entry: HighScoreEntry {
+            name: String::new(),
+            score: score.value,
+            rooms_cleared: stats.rooms_cleared,
+            enemies_killed: stats.enemies_killed,
+            duration: stats.duration,
+            seed: game_rng.seed(),
+            timestamp,
+        },
+        name: String::new(),
+    });
+}
+
+/// Digitação do nome: letras, números e espaço; Backspace apaga e Confirm confirma.
+fn type_name(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut actions: ResMut<ActionState>,
+    mut name_entry: ResMut<NameEntry>,
+    mut high_scores: ResMut<HighScores>,
+    file: Option<Res<HighScoresFile>>,
+) {
+    for (key, character) in NAME_KEYS {
+        if keyboard.just_pressed(key) && name_entry.name.chars().count() < MAX_NAME_LEN {
+            name_entry.name.push(character);
+        }
+    }
+    if keyboard.just_pressed(KeyCode::Backspace) {
+        name_entry.name.pop();
+    }
+    if !actions.just_pressed(Action::Confirm) {
+        return;
+    }
+    // O Confirm que confirma o nome não recomeça a partida
+    actions.consume(Action::Confirm);
+
+    let mut entry = name_entry.entry.clone();
+    entry.name = match name_entry.name.trim() {
+        "" => DEFAULT_NAME.to_string(),
+        name => name.to_string(),
+    };
+    high_scores.insert(entry);
+    commands.remove_resource::<NameEntry>();
+
+    if let Some(file) = file
+        && let Err(err) = high_scores.save(&file.path)
+    {
+        error!("Não foi possível salvar os recordes em {:?}: {}", file.path, err);
+    }
+}
+
+fn show_high_scores(mut commands: Commands, arena: Res<ArenaSize>) {
+    commands.spawn((
+        HighScoreTable,
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 22.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(arena.height / 2.0 + 70.0),
+            left: Val::Px(arena.width / 2.0 - 260.0),
+            ..default()
+        }),
+    ));
+}
+
+/// Reescreve a tabela, com a partida nova no lugar dela enquanto o nome é digitado.
+fn update_high_score_text(
+    high_scores: Res<HighScores>,
+    name_entry: Option<Res<NameEntry>>,
+    mut text_query: Query<&mut Text, With<HighScoreTable>>,
+) {
+    let mut table = high_scores.clone();
+    let mut typing = None;
+    if let Some(name_entry) = &name_entry {
+        let mut entry = name_entry.entry.clone();
+        entry.name = format!("{}_", name_entry.name);
+        typing = table.insert(entry);
+    }
+
+    let mut lines = Vec::new();
+    if typing.is_some() {
+        lines.push("New high score! Type your name and press Enter".to_string());
+    }
+    lines.push("High Scores".to_string());
+    for (rank, entry) in table.entries().iter().enumerate() {
+        let marker = if Some(rank) == typing { ">" } else { " " };
+        lines.push(format!(
+            "{marker}{:>2}. {:<13} {:>7}  rooms {:>2}  kills {:>3}  {}  {}",
+            rank + 1,
+            entry.name,
+            entry.score,
+            entry.rooms_cleared,
+            entry.enemies_killed,
+            format_duration(entry.duration),
+            format_date(entry.timestamp),
+        ));
+    }
+
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = lines.join("\n");
+    }
+}
+
+/// Duração como `mm:ss`.
+pub fn format_duration(seconds: f32) -> String {
+    let seconds = seconds.max(0.0) as u64;
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// Data UTC como `aaaa-mm-dd`, a partir dos segundos desde 1970.
+pub fn format_date(timestamp: u64) -> String {
+    // Conversão de dias para data civil do calendário gregoriano
+    let days = (timestamp / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
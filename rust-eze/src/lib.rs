@@ -2,34 +2,113 @@ use bevy::prelude::*;
 
 
 // declaração:
+pub mod cli;
 pub mod config;
+pub mod state;
+pub mod actions;
+pub mod options;
+pub mod rng;
+pub mod replay;
+pub mod simulation;
+pub mod collision;
+pub mod combat;
+pub mod status;
+pub mod arena;
+pub mod camera;
 pub mod player;
 pub mod movement;
+pub mod aim;
 pub mod ui;
+pub mod scoring;
 pub mod walls;
 pub mod enemies;
+pub mod enemy_kinds;
 pub mod projectiles;
+pub mod weapons;
 pub mod game;
 pub mod lives;
 pub mod rooms;
+pub mod floor;
+pub mod room_templates;
+pub mod room_state;
+pub mod save;
+pub mod profile;
+pub mod highscores;
 
+/// Plugin com o jogo em janela: `DefaultPlugins` + toda a lógica do jogo.
 pub struct RustEzePlg;
 
 impl Plugin for RustEzePlg {
     fn build(&self, app: &mut App) {
         app
             .add_plugins(DefaultPlugins)
+            .add_plugins(RustEzeCorePlugin)
+            ;
+    }
+}
+
+/// Plugin com toda a lógica do jogo, sem depender de janela.
+///
+/// O tamanho da arena vem do recurso `ArenaSize`, fixo durante a partida;
+/// a câmera amplia a arena para caber na janela.
+pub struct RustEzeCorePlugin;
+
+impl Plugin for RustEzeCorePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<arena::ArenaSize>()
+            .add_plugins(config::TuningPlugin)
+            .add_plugins(state::StatePlugin)
+            .add_plugins(rng::RngPlugin)
+            .add_plugins(replay::ReplayPlugin)
+            .add_plugins(actions::ActionsPlugin)
+            .add_plugins(simulation::SimulationPlugin)
+            .add_plugins(collision::CollisionPlugin)
+            .add_plugins(combat::CombatPlugin)
+            .add_plugins(status::StatusPlugin)
             .add_plugins(player::PlayerPlugin)
+            .add_plugins(camera::CameraPlugin)
             .add_plugins(movement::MovementPlugin)
+            .add_plugins(aim::AimPlugin)
             .add_plugins(ui::UiPlugin)
+            .add_plugins(options::OptionsPlugin)
+            .add_plugins(scoring::ScoringPlugin)
             .add_plugins(walls::WallsPlugin)
+            .add_plugins(enemy_kinds::EnemyKindsPlugin)
             .add_plugins(enemies::EnemiesPlugin)
             .add_plugins(game::GamePlugin)
             .add_plugins(lives::LivesPlugin)
             .add_plugins(projectiles::ProjectilesPlugin)
+            .add_plugins(weapons::WeaponsPlugin)
             .add_plugins(rooms::RoomsPlugin)
+            .add_plugins(room_templates::RoomTemplatesPlugin)
+            .add_plugins(floor::FloorPlugin)
+            .add_plugins(room_state::RoomStatePlugin)
+            .add_plugins(save::SavePlugin)
+            .add_plugins(highscores::HighScoresPlugin)
             ;
     }
 }
 
+/// Plugin para rodar o jogo sem janela, sobre `MinimalPlugins`.
+///
+/// Registra apenas o que os sistemas de jogo precisam (assets de malha e
+/// material, entrada do teclado, do mouse e do controle), útil para testes
+/// de integração.
+pub struct RustEzeHeadlessPlugin;
 
+impl Plugin for RustEzeHeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_plugins(MinimalPlugins)
+            .add_plugins(AssetPlugin::default())
+            .init_asset::<Mesh>()
+            .init_asset::<ColorMaterial>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<ButtonInput<GamepadButton>>()
+            .init_resource::<Axis<GamepadAxis>>()
+            .add_plugins(RustEzeCorePlugin)
+            ;
+    }
+}
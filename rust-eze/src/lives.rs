@@ -1,44 +1,83 @@
 use bevy::prelude::*;
+use crate::config::GameTuning;
+use crate::state::AppState;
 
 pub struct LivesPlugin;
 
 impl Plugin for LivesPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_systems(Startup, setup_lives);
+            .add_systems(Startup, setup_lives)
+            .add_systems(OnExit(AppState::GameOver), reset_lives);
     }
 }
 
-#[derive(Component)]
-pub struct Lives(u32);
+/// Vidas do jogador, cada uma com `max_health` pontos de vida.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Lives {
+    count: u32,
+    health: u32, // Pontos de vida da vida atual
+    max_health: u32,
+}
 
 impl Lives {
-    pub fn new(count: u32) -> Self {
-        Lives(count)
+    /// `count` vidas, a atual com a vida cheia.
+    pub fn new(count: u32, max_health: u32) -> Self {
+        let max_health = max_health.max(1);
+        Lives { count, health: max_health, max_health }
+    }
+
+    /// Tira `amount` pontos de vida. Se a vida atual acabar, perde uma vida
+    /// e a próxima começa cheia; o dano que sobra não passa para ela.
+    /// Devolve `true` quando não resta nenhuma vida.
+    pub fn take_damage(&mut self, amount: u32) -> bool {
+        if amount >= self.health {
+            return self.lose_life();
+        }
+        self.health -= amount;
+        false
     }
 
     pub fn lose_life(&mut self) -> bool {
-        if self.0 > 0 {
-            self.0 -= 1;
+        if self.count > 0 {
+            self.count -= 1;
         }
-        self.0 == 0
+        self.health = self.max_health;
+        self.count == 0
     }
 
     pub fn add_life(&mut self) {
-        self.0 += 1;
+        self.count += 1;
     }
 
     pub fn count(&self) -> u32 {
-        self.0
+        self.count
+    }
+
+    pub fn health(&self) -> u32 {
+        self.health
+    }
+
+    pub fn max_health(&self) -> u32 {
+        self.max_health
+    }
+
+    /// Muda a vida atual, entre 1 e `max_health`.
+    pub fn set_health(&mut self, health: u32) {
+        self.health = health.clamp(1, self.max_health);
     }
 
-    pub fn reset(&mut self) {
-        self.0 = 3;
+    pub fn reset(&mut self, count: u32, max_health: u32) {
+        *self = Lives::new(count, max_health);
     }
 }
 
-fn setup_lives(
-    mut commands: Commands,
-) {
-    commands.spawn(Lives::new(3));
-}
\ No newline at end of file
+fn setup_lives(mut commands: Commands, tuning: Res<GameTuning>) {
+    commands.spawn(Lives::new(tuning.starting_lives, tuning.player_health));
+}
+
+fn reset_lives(mut lives_query: Query<&mut Lives>, tuning: Res<GameTuning>) {
+    for mut lives in lives_query.iter_mut() {
+        lives.reset(tuning.starting_lives, tuning.player_health);
+    }
+}
@@ -1,8 +1,77 @@
 use bevy::prelude::*;
 use rust_eze::RustEzePlg;
+use rust_eze::cli::CliArgs;
+use rust_eze::replay::{self, Replay, DEFAULT_TIMESTEP};
+use rust_eze::rng::SeedOverride;
+use rust_eze::actions::BindingsFile;
+use rust_eze::highscores::HighScoresFile;
+use rust_eze::save::{SaveFile, DEFAULT_SAVE_PATH};
 
 fn main() {
-    App::new()
-        .add_plugins(RustEzePlg)
-        .run();
+    let args = match CliArgs::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("rust-eze: {err}");
+            eprintln!("uso: rust-eze [--seed <n>] [--record <arquivo>] [--replay <arquivo> [--headless]]");
+            std::process::exit(2);
+        }
+    };
+
+    let replay = args.replay.as_ref().map(|path| match Replay::load(path) {
+        Ok(replay) => replay,
+        Err(err) => {
+            eprintln!("rust-eze: não foi possível abrir {}: {err}", path.display());
+            std::process::exit(2);
+        }
+    });
+
+    if args.headless {
+        let replay = replay.expect("--headless exige --replay");
+        match replay::run_headless(replay) {
+            Ok(outcome) => println!("replay ok: {outcome:?}"),
+            Err(err) => {
+                eprintln!("rust-eze: {err}");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let mut app = App::new();
+    app.add_plugins(RustEzePlg);
+    if let Some(seed) = args.seed {
+        app.insert_resource(SeedOverride(seed));
+    }
+    // Replays precisam começar do zero; com `--seed`, a partida é nova mas continua salvando
+    if replay.is_none() && args.record.is_none() {
+        app.insert_resource(SaveFile {
+            path: DEFAULT_SAVE_PATH.into(),
+            resume: args.seed.is_none(),
+        });
+    }
+    if replay.is_none() {
+        match HighScoresFile::in_user_data_dir() {
+            Some(file) => {
+                app.insert_resource(file);
+            }
+            None => eprintln!("rust-eze: pasta de dados do usuário não encontrada; os recordes não serão salvos"),
+        }
+    }
+    // Replays gravam as ações já resolvidas, então valem os controles do jogador;
+    // na reprodução, o menu de opções do replay não pode gravar por cima deles
+    if replay.is_none() {
+        match BindingsFile::in_user_data_dir() {
+            Some(file) => {
+                app.insert_resource(file);
+            }
+            None => eprintln!("rust-eze: pasta de dados do usuário não encontrada; os controles não serão salvos"),
+        }
+    }
+    if let Some(path) = args.record {
+        replay::configure_recording(&mut app, Some(path), DEFAULT_TIMESTEP);
+    }
+    if let Some(replay) = replay {
+        replay::configure_playback(&mut app, replay);
+    }
+    app.run();
 }
@@ -1,50 +1,239 @@
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
-use crate::player::Player;
+use bevy::sprite::MaterialMesh2dBundle;
+use crate::actions::{Action, ActionState};
+use crate::aim::Aim;
+use crate::player::{Knockback, Player};
 use crate::enemies::Enemy;
-use crate::config::GameConfig;
+use crate::config::GameTuning;
+use crate::arena::ArenaSize;
+use crate::state::AppState;
+use crate::collision::CollisionSet;
+use crate::rooms::{CurrentRoom, RoomMember};
+use crate::walls::{resolve_obstacles, Obstacle};
+
+/// Opacidade inicial das cópias do rastro da arrancada.
+const TRAIL_ALPHA: f32 = 0.4;
 
 pub struct MovementPlugin;
 
 impl Plugin for MovementPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, move_player);
+        app
+            .add_systems(Update, request_dash.run_if(in_state(AppState::Playing)))
+            .add_systems(FixedUpdate, (start_dash, move_player, spawn_dash_trail)
+                .chain()
+                .before(CollisionSet)
+                .run_if(in_state(AppState::Playing)))
+            .add_systems(Update, fade_dash_trail);
+    }
+}
+
+/// Velocidade atual do jogador, que acelera e freia até a do controle.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq)]
+pub struct Velocity(pub Vec2);
+
+/// Arrancada do jogador: a arrancada em curso e o intervalo até a próxima.
+#[derive(Component, Debug, Default)]
+pub struct Dash {
+    requested: bool,  // Pedida no `Update`, começa no próximo passo fixo
+    direction: Vec2,  // Direção da arrancada em curso
+    remaining: f32,   // Segundos que faltam da arrancada em curso
+    cooldown: f32,    // Segundos até poder arrancar de novo
+    shield: f32,      // Segundos que faltam da proteção contra tiros
+    trail_timer: f32, // Segundos até a próxima cópia do rastro
+}
+
+impl Dash {
+    pub fn is_dashing(&self) -> bool {
+        self.remaining > 0.0
+    }
+
+    /// Se os tiros ainda passam pelo jogador sem causar dano.
+    pub fn is_invulnerable(&self) -> bool {
+        self.shield > 0.0
+    }
+
+    /// Segundos até a próxima arrancada ser possível.
+    pub fn cooldown(&self) -> f32 {
+        self.cooldown
+    }
+}
+
+/// Marca o jogador enquanto a arrancada dura: ele atravessa inimigos sem
+/// encostar neles. Contra tiros vale a proteção curta do início da arrancada.
+#[derive(Component, Debug)]
+pub struct Dashing;
+
+/// Cópia do jogador deixada pela arrancada, que some aos poucos.
+#[derive(Component)]
+pub struct DashTrail {
+    pub timer: Timer,
+}
+
+/// Aproxima `current` de `target` andando no máximo `max_delta`.
+fn approach(current: Vec2, target: Vec2, max_delta: f32) -> Vec2 {
+    let delta = target - current;
+    if delta.length() <= max_delta {
+        target
+    } else {
+        current + delta.normalize() * max_delta
+    }
+}
+
+/// Guarda o pedido de arrancada até o próximo passo fixo, para não perder
+/// o aperto em quadros sem passo.
+fn request_dash(actions: Res<ActionState>, mut query: Query<&mut Dash, With<Player>>) {
+    if actions.just_pressed(Action::Dash) {
+        for mut dash in query.iter_mut() {
+            dash.requested = true;
+        }
+    }
+}
+
+fn start_dash(
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    aim: Res<Aim>,
+    tuning: Res<GameTuning>,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Dash, &Velocity, Has<Knockback>), With<Player>>,
+) {
+    for (entity, mut dash, velocity, knocked_back) in query.iter_mut() {
+        dash.cooldown = (dash.cooldown - time.delta_seconds()).max(0.0);
+        dash.shield = (dash.shield - time.delta_seconds()).max(0.0);
+        if !std::mem::take(&mut dash.requested) || dash.cooldown > 0.0 || dash.is_dashing() || knocked_back {
+            continue;
+        }
+
+        // Para onde o jogador quer ir; parado, para onde mira
+        dash.direction = actions
+            .movement()
+            .try_normalize()
+            .or_else(|| velocity.0.try_normalize())
+            .unwrap_or(aim.direction);
+        dash.remaining = tuning.dash.duration;
+        dash.cooldown = tuning.dash.cooldown;
+        dash.shield = tuning.dash.invulnerability;
+        dash.trail_timer = 0.0;
+        commands.entity(entity).insert(Dashing);
     }
 }
 
-fn move_player(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut query: Query<&mut Transform, (With<Player>, Without<Enemy>)>,
+/// Jogador movido pelos controles, pela arrancada ou pelo empurrão.
+type MovingPlayer = (Entity, &'static mut Transform, &'static mut Velocity, &'static mut Dash, Option<&'static mut Knockback>);
+
+pub(crate) fn move_player(
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    mut query: Query<MovingPlayer, (With<Player>, Without<Enemy>)>,
+    obstacle_query: Query<(&Transform, &Obstacle), Without<Player>>,
     time: Res<Time>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    arena: Res<ArenaSize>,
+    tuning: Res<GameTuning>,
 ) {
-    let window = window_query.single();
-    let window_width = window.width();
-    let window_height = window.height();
+    let dt = time.delta_seconds();
+    let bounds = arena.bounds(tuning.wall_thickness, tuning.player_size);
+    let obstacles: Vec<Rect> = obstacle_query
+        .iter()
+        .map(|(transform, obstacle)| obstacle.rect(transform.translation.xy()))
+        .collect();
 
-    for mut transform in query.iter_mut() {
-        let mut new_x = transform.translation.x;
-        let mut new_y = transform.translation.y;
+    for (entity, mut transform, mut velocity, mut dash, knockback) in query.iter_mut() {
+        let was_dashing = dash.is_dashing();
+        let mut knockback_over = false;
+        if let Some(mut knockback) = knockback {
+            velocity.0 = knockback.velocity;
+            dash.remaining = 0.0; // Um tiro no meio interrompe a arrancada
+            if knockback.timer.tick(time.delta()).finished() {
+                commands.entity(entity).remove::<Knockback>();
+                knockback_over = true;
+            }
+        } else if dash.is_dashing() {
+            velocity.0 = dash.direction * tuning.dash.speed;
+            dash.remaining -= dt;
+        } else {
+            // Analógico no stick; nos botões, a diagonal anda o mesmo que uma reta
+            let target = actions.movement() * tuning.player_speed;
+            let rate = if target == Vec2::ZERO { tuning.player_deceleration } else { tuning.player_acceleration };
+            velocity.0 = approach(velocity.0, target, rate * dt);
+        }
+        if was_dashing && !dash.is_dashing() {
+            commands.entity(entity).remove::<Dashing>();
+        }
+
+        let wanted = transform.translation.xy() + velocity.0 * dt;
+        let clamped = wanted.clamp(-bounds, bounds);
+        let resolved = resolve_obstacles(clamped, tuning.player_size, &obstacles);
+        transform.translation.x = resolved.x;
+        transform.translation.y = resolved.y;
 
-        if keyboard.pressed(KeyCode::KeyW) {
-            new_y += GameConfig::PLAYER_SPEED * time.delta_seconds();
+        // Parede ou borda param a velocidade no eixo em que bateram
+        if resolved.x != wanted.x {
+            velocity.0.x = 0.0;
         }
-        if keyboard.pressed(KeyCode::KeyS) {
-            new_y -= GameConfig::PLAYER_SPEED * time.delta_seconds();
+        if resolved.y != wanted.y {
+            velocity.0.y = 0.0;
         }
-        if keyboard.pressed(KeyCode::KeyD) {
-            new_x += GameConfig::PLAYER_SPEED * time.delta_seconds();
+
+        if knockback_over {
+            velocity.0 = Vec2::ZERO;
+        } else if dash.direction != Vec2::ZERO && !dash.is_dashing() && velocity.0.length() > tuning.player_speed {
+            // Fim da arrancada: volta à velocidade normal sem deslizar longe
+            velocity.0 = velocity.0.clamp_length_max(tuning.player_speed);
         }
-        if keyboard.pressed(KeyCode::KeyA) {
-            new_x -= GameConfig::PLAYER_SPEED * time.delta_seconds();
+    }
+}
+
+/// Deixa cópias do jogador pelo caminho enquanto a arrancada dura.
+fn spawn_dash_trail(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut query: Query<(&Transform, &mut Dash), With<Player>>,
+    current_room: Res<CurrentRoom>,
+    tuning: Res<GameTuning>,
+    time: Res<Time>,
+) {
+    if tuning.dash.trail_lifetime <= 0.0 {
+        return;
+    }
+
+    for (transform, mut dash) in query.iter_mut() {
+        if !dash.is_dashing() {
+            continue;
+        }
+        dash.trail_timer -= time.delta_seconds();
+        if dash.trail_timer > 0.0 {
+            continue;
         }
+        dash.trail_timer += tuning.dash.trail_interval;
 
-        let x_bound = (window_width - GameConfig::WALL_THICKNESS - GameConfig::PLAYER_SIZE) / 2.0;
-        let y_bound = (window_height - GameConfig::WALL_THICKNESS - GameConfig::PLAYER_SIZE) / 2.0;
+        commands.spawn((
+            DashTrail {
+                timer: Timer::from_seconds(tuning.dash.trail_lifetime, TimerMode::Once),
+            },
+            RoomMember(current_room.id),
+            MaterialMesh2dBundle {
+                mesh: meshes.add(Circle::new(tuning.player_size)).into(),
+                material: materials.add(ColorMaterial::from(Color::RED.with_a(TRAIL_ALPHA))),
+                transform: Transform::from_xyz(transform.translation.x, transform.translation.y, -0.1),
+                ..default()
+            },
+        ));
+    }
+}
 
-        new_x = new_x.clamp(-x_bound, x_bound);
-        new_y = new_y.clamp(-y_bound, y_bound);
-        transform.translation.x = new_x;
-        transform.translation.y = new_y;
+fn fade_dash_trail(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut query: Query<(Entity, &mut DashTrail, &Handle<ColorMaterial>)>,
+    time: Res<Time>,
+) {
+    for (entity, mut trail, material) in query.iter_mut() {
+        if trail.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        } else if let Some(material) = materials.get_mut(material) {
+            material.color.set_a(TRAIL_ALPHA * trail.timer.fraction_remaining());
+        }
     }
 }
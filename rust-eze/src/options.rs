@@ -0,0 +1,232 @@
+use bevy::ecs::system::SystemParam;
+use bevy::input::gamepad::{GamepadAxis, GamepadButton};
+use bevy::prelude::*;
+
+use crate::actions::{first_gamepad, Action, ActionState, Binding, BindingsFile, InputBindings, Stick, StickAction};
+use crate::arena::ArenaSize;
+use crate::state::{despawn_with, AppState};
+
+/// Tecla que cancela a escolha de um controle novo. Fica fixa para que o
+/// jogador nunca se tranque fora do menu.
+pub const CANCEL_KEY: KeyCode = KeyCode::Escape;
+
+/// Plugin do menu de opções, aberto do menu principal ou da pausa, onde o
+/// jogador troca os controles de cada ação e os sticks. Um controle que já
+/// era de outra ação troca de lugar com o antigo. Os controles são gravados
+/// ao sair do menu, se existir um `BindingsFile`.
+pub struct OptionsPlugin;
+
+impl Plugin for OptionsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<OptionsMenu>()
+            .add_systems(Update, open_options.run_if(in_state(AppState::MainMenu).or_else(in_state(AppState::Paused))))
+            .add_systems(OnEnter(AppState::Options), (show_options, update_options_text).chain())
+            .add_systems(Update, (
+                navigate_options,
+                update_options_text
+                    .run_if(resource_changed::<OptionsMenu>.or_else(resource_changed::<InputBindings>)),
+            ).chain().run_if(in_state(AppState::Options)))
+            .add_systems(OnExit(AppState::Options), (despawn_with::<OptionsText>, save_bindings));
+    }
+}
+
+/// Estado do menu de opções, refeito a cada vez que ele abre.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct OptionsMenu {
+    pub selected: usize,         // Linha escolhida; a última é a de restaurar o padrão
+    pub listening: bool,         // Esperando o controle novo da ação escolhida
+    pub swapped: Option<String>, // Ação que trocou de controle com a escolhida
+    return_to: AppState,         // Tela de onde o menu foi aberto
+}
+
+/// O que uma linha do menu muda.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionsRow {
+    Action(Action),
+    Stick(StickAction),
+    Reset,
+}
+
+impl OptionsMenu {
+    /// Linhas do menu: uma por ação, uma por ação analógica e a de
+    /// restaurar o padrão.
+    pub const ROWS: usize = Action::ALL.len() + StickAction::ALL.len() + 1;
+
+    pub fn row(index: usize) -> OptionsRow {
+        match index.checked_sub(Action::ALL.len()) {
+            None => OptionsRow::Action(Action::ALL[index]),
+            Some(stick) if stick < StickAction::ALL.len() => OptionsRow::Stick(StickAction::ALL[stick]),
+            Some(_) => OptionsRow::Reset,
+        }
+    }
+}
+
+/// Texto do menu de opções.
+#[derive(Component)]
+pub struct OptionsText;
+
+fn open_options(
+    actions: Res<ActionState>,
+    state: Res<State<AppState>>,
+    mut menu: ResMut<OptionsMenu>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if actions.just_pressed(Action::Options) {
+        *menu = OptionsMenu {
+            selected: 0,
+            listening: false,
+            swapped: None,
+            return_to: *state.get(),
+        };
+        next_state.set(AppState::Options);
+    }
+}
+
+/// Entrada crua de todos os dispositivos, de onde sai o controle novo de
+/// uma ação.
+#[derive(SystemParam)]
+struct CapturedInput<'w> {
+    keyboard: Res<'w, ButtonInput<KeyCode>>,
+    mouse: Res<'w, ButtonInput<MouseButton>>,
+    pad_buttons: Res<'w, ButtonInput<GamepadButton>>,
+    pad_axes: Res<'w, Axis<GamepadAxis>>,
+}
+
+impl CapturedInput<'_> {
+    /// Primeiro botão apertado neste quadro em qualquer dispositivo.
+    fn binding(&self) -> Option<Binding> {
+        self.keyboard
+            .get_just_pressed()
+            .map(|key| Binding::Key(*key))
+            .chain(self.mouse.get_just_pressed().map(|button| Binding::Mouse(*button)))
+            .chain(self.pad_buttons.get_just_pressed().map(|button| Binding::Pad(button.button_type)))
+            .next()
+    }
+
+    /// Primeiro stick inclinado no controle cujos sticks valem.
+    fn stick(&self) -> Option<Stick> {
+        let pad = first_gamepad(&self.pad_axes)?;
+        Stick::ALL.into_iter().find(|stick| stick.tilt(&self.pad_axes, pad) != Vec2::ZERO)
+    }
+}
+
+/// Navega pelas ações com os controles de movimento; Confirm escolhe um
+/// controle novo para a ação e Pause volta para a tela anterior.
+fn navigate_options(
+    input: CapturedInput,
+    actions: Res<ActionState>,
+    mut bindings: ResMut<InputBindings>,
+    mut menu: ResMut<OptionsMenu>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if menu.listening {
+        if input.keyboard.just_pressed(CANCEL_KEY) {
+            menu.listening = false;
+            return;
+        }
+        let swapped = match OptionsMenu::row(menu.selected) {
+            OptionsRow::Action(action) => input.binding()
+                .map(|binding| bindings.bind(action, binding).map(|other| other.label())),
+            OptionsRow::Stick(action) => input.stick()
+                .map(|stick| bindings.bind_stick(action, stick).map(|other| other.label().to_string())),
+            OptionsRow::Reset => Some(None),
+        };
+        if let Some(swapped) = swapped {
+            menu.swapped = swapped;
+            menu.listening = false;
+        }
+        return;
+    }
+
+    if actions.just_pressed(Action::MoveUp) || actions.just_pressed(Action::AimUp) {
+        menu.selected = (menu.selected + OptionsMenu::ROWS - 1) % OptionsMenu::ROWS;
+    }
+    if actions.just_pressed(Action::MoveDown) || actions.just_pressed(Action::AimDown) {
+        menu.selected = (menu.selected + 1) % OptionsMenu::ROWS;
+    }
+    if actions.just_pressed(Action::Confirm) {
+        menu.swapped = None;
+        match OptionsMenu::row(menu.selected) {
+            OptionsRow::Reset => *bindings = InputBindings::default(),
+            _ => menu.listening = true,
+        }
+    }
+    if actions.just_pressed(Action::Pause) {
+        next_state.set(menu.return_to);
+    }
+}
+
+fn show_options(mut commands: Commands, arena: Res<ArenaSize>) {
+    commands.spawn((
+        OptionsText,
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 20.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(40.0),
+            left: Val::Px(arena.width / 2.0 - 260.0),
+            ..default()
+        }),
+    ));
+}
+
+/// Uma linha por ação com os controles dela, e as teclas do próprio menu.
+fn update_options_text(
+    menu: Res<OptionsMenu>,
+    bindings: Res<InputBindings>,
+    mut query: Query<&mut Text, With<OptionsText>>,
+) {
+    let mut lines = vec!["Controls".to_string(), String::new()];
+    for index in 0..OptionsMenu::ROWS {
+        let marker = if index == menu.selected { ">" } else { " " };
+        let listening = menu.listening && index == menu.selected;
+        let line = match OptionsMenu::row(index) {
+            OptionsRow::Action(action) => {
+                let inputs = match listening {
+                    true => format!("press a key or button... ({CANCEL_KEY:?} cancels)"),
+                    false => bindings.describe(action),
+                };
+                format!("{:<18} {inputs}", action.label())
+            }
+            OptionsRow::Stick(action) => {
+                let stick = match listening {
+                    true => format!("tilt a stick... ({CANCEL_KEY:?} cancels)"),
+                    false => bindings.stick(action).map_or("-", |stick| stick.label()).to_string(),
+                };
+                format!("{:<18} {stick}", action.label())
+            }
+            OptionsRow::Reset => "Reset to defaults".to_string(),
+        };
+        lines.push(format!("{marker} {line}"));
+    }
+    lines.push(String::new());
+    if let Some(swapped) = &menu.swapped {
+        lines.push(format!("Swapped with {swapped}"));
+    }
+    lines.push(format!(
+        "{}/{}: choose   {}: change   {}: back",
+        bindings.describe(Action::MoveUp),
+        bindings.describe(Action::MoveDown),
+        bindings.describe(Action::Confirm),
+        bindings.describe(Action::Pause),
+    ));
+
+    for mut text in query.iter_mut() {
+        text.sections[0].value = lines.join("\n");
+    }
+}
+
+fn save_bindings(bindings: Res<InputBindings>, file: Option<Res<BindingsFile>>) {
+    if let Some(file) = file
+        && let Err(err) = bindings.save(&file.path)
+    {
+        error!("Não foi possível salvar os controles em {:?}: {}", file.path, err);
+    }
+}
@@ -1,33 +1,97 @@
 use bevy::prelude::*;
 use bevy::sprite::MaterialMesh2dBundle;
-use crate::config::GameConfig;
+use crate::config::GameTuning;
+use crate::state::{despawn_with, AppState};
+use crate::simulation::Interpolated;
+use crate::collision::{Collider, CollisionSet, Layers};
+use crate::weapons::{Arsenal, WeaponRegistry};
+use crate::movement::{Dash, Velocity};
+
+/// Intervalo entre aparecer e sumir enquanto o jogador está invulnerável (s).
+const BLINK_INTERVAL: f32 = 0.1;
 
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, spawn_player);
+        app
+            .add_systems(Startup, spawn_player)
+            .add_systems(FixedUpdate, update_invulnerability.before(CollisionSet).run_if(in_state(AppState::Playing)))
+            .add_systems(Update, blink_invulnerable)
+            .add_systems(OnExit(AppState::GameOver), (despawn_with::<Player>, spawn_player).chain());
     }
 }
 
 #[derive(Component)]
 pub struct Player;
 
+/// Jogador que acabou de levar dano: não leva outro e pisca até o tempo acabar.
+#[derive(Component, Debug)]
+pub struct Invulnerable {
+    pub timer: Timer,
+}
+
+impl Invulnerable {
+    pub fn new(duration: f32) -> Self {
+        Invulnerable {
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+        }
+    }
+}
+
+/// Empurrão para longe de quem acertou o jogador. Enquanto dura, a entrada
+/// de movimento é ignorada.
+#[derive(Component, Debug)]
+pub struct Knockback {
+    pub velocity: Vec2,
+    pub timer: Timer,
+}
+
 fn spawn_player(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
+    tuning: Res<GameTuning>,
+    weapons: Res<WeaponRegistry>,
 ) {
-     commands.spawn(Camera2dBundle::default());
-
-
+    // Ao recomeçar, o jogador novo não desliza desde onde o antigo morreu
+    let transform = Transform::from_xyz(0.0, 0.0, 0.0);
+    let mut interpolated = Interpolated::default();
+    interpolated.snap(transform.translation);
     commands.spawn((
         Player,
+        weapons.starting_weapon(),
+        Arsenal::default(),
+        Velocity::default(),
+        Dash::default(),
+        interpolated,
+        Collider::circle(tuning.player_size, Layers::PLAYER, Layers::ENEMY | Layers::ENEMY_PROJECTILE | Layers::PICKUP),
         MaterialMesh2dBundle {
-            mesh: meshes.add(Circle::new(GameConfig::PLAYER_SIZE)).into(),
+            mesh: meshes.add(Circle::new(tuning.player_size)).into(),
             material: materials.add(ColorMaterial::from(Color::RED)),
-            transform: Transform::from_xyz(0.0, 0.0, 0.0),
+            transform,
             ..default()
         },
     ));
 }
+
+fn update_invulnerability(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Invulnerable, &mut Visibility)>,
+    time: Res<Time>,
+) {
+    for (entity, mut invulnerable, mut visibility) in query.iter_mut() {
+        if invulnerable.timer.tick(time.delta()).finished() {
+            *visibility = Visibility::Inherited;
+            commands.entity(entity).remove::<Invulnerable>();
+        }
+    }
+}
+
+/// Faz o jogador invulnerável piscar.
+fn blink_invulnerable(mut query: Query<(&Invulnerable, &mut Visibility)>) {
+    for (invulnerable, mut visibility) in query.iter_mut() {
+        let hidden = (invulnerable.timer.elapsed_secs() / BLINK_INTERVAL) as u32 % 2 == 1;
+        *visibility = if hidden { Visibility::Hidden } else { Visibility::Inherited };
+    }
+}
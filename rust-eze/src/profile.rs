@@ -0,0 +1,22 @@
+use std::path::PathBuf;
+
+/// Nome da pasta do jogo dentro da pasta de dados do usuário.
+const APP_DIR: &str = "rust-eze";
+
+/// Pasta onde o jogo guarda os dados do jogador (recordes, preferências).
+///
+/// Segue a convenção de cada sistema: `$XDG_DATA_HOME` ou `~/.local/share`
+/// no Linux, `~/Library/Application Support` no macOS e `%APPDATA%` no
+/// Windows. `None` se nenhuma das variáveis de ambiente existir.
+pub fn user_data_dir() -> Option<PathBuf> {
+    let env_dir = |name: &str| std::env::var_os(name).filter(|value| !value.is_empty()).map(PathBuf::from);
+
+    let base = if cfg!(target_os = "windows") {
+        env_dir("APPDATA")?
+    } else if cfg!(target_os = "macos") {
+        env_dir("HOME")?.join("Library").join("Application Support")
+    } else {
+        env_dir("XDG_DATA_HOME").or_else(|| env_dir("HOME").map(|home| home.join(".local").join("share")))?
+    };
+    Some(base.join(APP_DIR))
+}
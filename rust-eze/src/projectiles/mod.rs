@@ -1,14 +1,21 @@
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
-use crate::enemies::Enemy;
+use bevy::ecs::system::SystemParam;
+use bevy::sprite::MaterialMesh2dBundle;
+use std::collections::HashSet;
+use crate::aim::{Aim, SelectedElement};
+use crate::arena::ArenaSize;
+use crate::config::GameTuning;
+use crate::state::AppState;
+use crate::collision::{Collider, CollisionEvent, CollisionSet, Layers};
+use crate::combat::{Damage, DamageEvent, DamageSet, HitSet};
+use crate::simulation::Interpolated;
+use crate::rooms::{check_room_cleared, CurrentRoom, RoomMember};
+use crate::status::{Element, ElementHit};
+use crate::walls::Obstacle;
+use crate::weapons::Weapon;
 
-// Importa módulos relacionados aos power-ups e tipos de projéteis
+// Importa módulos relacionados aos power-ups
 pub mod powerups;
-mod standard_projectile;
-mod fire_projectile;
-mod ice_projectile;
-mod electric_projectile;
-mod explosive_projectile;
 
 pub struct ProjectilesPlugin;
 
@@ -17,18 +24,26 @@ impl Plugin for ProjectilesPlugin {
         app
             // Inicializa os recursos de power-ups
             .init_resource::<powerups::PlayerPowerUpState>()
-            .init_resource::<powerups::PowerUpSpawnState>()
+            .add_event::<powerups::PowerUpCollected>()
             // Adiciona os sistemas responsáveis pelos projéteis e power-ups
-            .add_systems(Update, (
-                spawn_projectiles,       // Sistema de spawn de projéteis
-                move_projectiles,        // Sistema de movimentação de projéteis
-                check_projectile_collision, // Sistema de detecção de colisão
-                powerups::spawn_powerups,
-                powerups::collect_powerups,
+            .add_systems(FixedUpdate, (
+                // Movimento antes da detecção de colisões
+                (
+                    fire_weapon.before(move_projectiles), // A mira vem do `Update` do quadro anterior
+                    move_projectiles,        // Sistema de movimentação de projéteis
+                    powerups::update_rotating_circle,
+                ).before(CollisionSet),
+                // Reações às colisões detectadas
+                (
+                    check_projectile_collision,
+                    powerups::rotating_circle_hits,
+                ).in_set(HitSet),
+                powerups::collect_powerups.after(CollisionSet),
+                powerups::drop_loot.after(DamageSet),
+                powerups::spawn_room_reward.after(check_room_cleared),
                 powerups::update_powerup_timers,
-                powerups::update_rotating_circle,
-                powerups::reset_powerup_spawn_state, // Sistema de reset de spawn de power-ups
-            ));
+            ).run_if(in_state(AppState::Playing)))
+            .add_systems(OnExit(AppState::GameOver), powerups::reset_powerups);
     }
 }
 
@@ -37,127 +52,218 @@ impl Plugin for ProjectilesPlugin {
 pub struct Projectile {
     direction: Vec2,
     speed: f32,
-    explosive: bool,
+    explosive: bool, // Power-up explosivo ativo no disparo
+    pub element: Element,
 }
 
-// Constantes definindo tamanho e velocidade padrão dos projéteis
-const PROJECTILE_SIZE: f32 = 4.0;
-const PROJECTILE_SPEED: f32 = 400.0;
+/// Tempo até o projétil sumir, vindo da arma que o disparou.
+#[derive(Component, Debug)]
+pub struct ProjectileLifetime(pub Timer);
 
-// Função para spawnar projéteis
-fn spawn_projectiles(
-    mut commands: Commands,
-    keyboard: Res<ButtonInput<KeyCode>>,
-    player_query: Query<&Transform, With<crate::player::Player>>, // Obtém a posição do jogador
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    time: Res<Time>,
-    player_powerup_state: Res<powerups::PlayerPowerUpState>,
-) {
-    let player_transform = player_query.single(); // <-- PODE CAUSAR PANIC SE NÃO HOUVER UM PLAYER
-    let player_pos = player_transform.translation.xy();
+/// Inimigos que o projétil ainda pode atravessar e os que já acertou, para
+/// não acertar o mesmo duas vezes.
+#[derive(Component, Debug, Default)]
+pub struct Piercing {
+    pub remaining: u32,
+    hit: Vec<Entity>,
+}
 
-    static mut LAST_SHOT: f32 = 0.0;
-    let current_time = time.elapsed_seconds();
-    if current_time - unsafe { LAST_SHOT } < 0.2 {
-        return; // Limita a taxa de disparo dos projéteis
+impl Piercing {
+    pub fn new(remaining: u32) -> Self {
+        Piercing { remaining, hit: Vec::new() }
     }
+}
 
-    let mut direction = Vec2::ZERO;
-    let mut projectile_type = None;
-
-    // Determina a direção e tipo do projétil baseado nas teclas pressionadas
-    if keyboard.just_pressed(KeyCode::ArrowUp) {
-        direction = Vec2::new(0.0, 1.0);
-        projectile_type = Some("standard");
-    } else if keyboard.just_pressed(KeyCode::ArrowDown) {
-        direction = Vec2::new(0.0, -1.0);
-        projectile_type = Some("fire");
-    } else if keyboard.just_pressed(KeyCode::ArrowLeft) {
-        direction = Vec2::new(-1.0, 0.0);
-        projectile_type = Some("ice");
-    } else if keyboard.just_pressed(KeyCode::ArrowRight) {
-        direction = Vec2::new(1.0, 0.0);
-        projectile_type = Some("electric");
-    } else if keyboard.just_pressed(KeyCode::Space) {
-        direction = Vec2::new(1.0, 0.0);
-        projectile_type = Some("explosive");
-    }
+/// O que decide o próximo disparo: a mira, o elemento escolhido e os
+/// power-ups ativos.
+#[derive(SystemParam)]
+struct ShotSettings<'w> {
+    aim: Res<'w, Aim>,
+    selected_element: Res<'w, SelectedElement>,
+    player_powerup_state: Res<'w, powerups::PlayerPowerUpState>,
+}
 
-    if direction != Vec2::ZERO && projectile_type.is_some() {
-        unsafe { LAST_SHOT = current_time; }
+/// Assets para a malha e a cor dos projéteis.
+#[derive(SystemParam)]
+struct ProjectileAssets<'w> {
+    meshes: ResMut<'w, Assets<Mesh>>,
+    materials: ResMut<'w, Assets<ColorMaterial>>,
+}
 
-        let speed = if player_powerup_state.faster_projectile_timer.is_some() {
-            PROJECTILE_SPEED * 1.25
-        } else {
-            PROJECTILE_SPEED
-        };
+// Dispara a arma do jogador na direção da mira com o elemento escolhido
+fn fire_weapon(
+    mut commands: Commands,
+    shot: ShotSettings,
+    mut player_query: Query<(&Transform, &mut Weapon), With<crate::player::Player>>,
+    mut assets: ProjectileAssets,
+    time: Res<Time>,
+    tuning: Res<GameTuning>,
+    current_room: Res<CurrentRoom>,
+) {
+    let ShotSettings { aim, selected_element, player_powerup_state } = shot;
+    let Ok((player_transform, mut weapon)) = player_query.get_single_mut() else {
+        return;
+    };
+    let shots = weapon.update(time.delta_seconds(), aim.firing);
+    if shots == 0 {
+        return;
+    }
+    let player_pos = player_transform.translation.xy();
 
-        let explosive = player_powerup_state.explosive_projectile_timer.is_some();
+    let speed = if player_powerup_state.faster_projectile_timer.is_some() {
+        tuning.projectile_speed * 1.25
+    } else {
+        tuning.projectile_speed
+    };
+    let explosive = player_powerup_state.explosive_projectile_timer.is_some();
+    let damage = tuning.projectile_damage * weapon.kind.damage;
 
-        // Spawna projéteis de acordo com o tipo selecionado
-        match projectile_type.unwrap() {
-            "standard" => standard_projectile::spawn(&mut commands, player_pos, direction, speed, explosive, &mut meshes, &mut materials),
-            "fire" => fire_projectile::spawn(&mut commands, player_pos, direction, speed, explosive, &mut meshes, &mut materials),
-            "ice" => ice_projectile::spawn(&mut commands, player_pos, direction, speed, explosive, &mut meshes, &mut materials),
-            "electric" => electric_projectile::spawn(&mut commands, player_pos, direction, speed, explosive, &mut meshes, &mut materials),
-            "explosive" => explosive_projectile::spawn(&mut commands, player_pos, direction, speed, explosive, &mut meshes, &mut materials),
-            _ => {}
+    for _ in 0..shots {
+        for direction in weapon.kind.shot_directions(aim.direction) {
+            let projectile = spawn_projectile(
+                &mut commands,
+                ProjectileSpawn {
+                    element: selected_element.0,
+                    position: player_pos,
+                    direction,
+                    speed,
+                    size: tuning.projectile_size,
+                    explosive,
+                    damage,
+                },
+                &mut assets.meshes,
+                &mut assets.materials,
+            );
+            commands.entity(projectile).insert((
+                RoomMember(current_room.id),
+                ProjectileLifetime(Timer::from_seconds(weapon.kind.lifetime, TimerMode::Once)),
+                Piercing::new(weapon.kind.piercing),
+            ));
         }
     }
 }
 
+/// Parâmetros de um disparo: de onde sai, para onde vai e com que elemento.
+#[derive(Debug, Clone, Copy)]
+pub struct ProjectileSpawn {
+    pub element: Element,
+    pub position: Vec2,
+    pub direction: Vec2,
+    pub speed: f32,
+    pub size: f32,
+    pub explosive: bool, // Power-up explosivo ativo no disparo
+    pub damage: f32,
+}
+
+/// Cria um projétil; todo disparo do jogador passa por aqui. Os elementos só
+/// mudam a cor.
+pub fn spawn_projectile(
+    commands: &mut Commands,
+    spawn: ProjectileSpawn,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+) -> Entity {
+    commands.spawn((
+        Projectile {
+            direction: spawn.direction,
+            speed: spawn.speed,
+            explosive: spawn.explosive,
+            element: spawn.element,
+        },
+        Damage(spawn.damage),
+        Interpolated::default(),
+        Collider::circle(spawn.size, Layers::PROJECTILE, Layers::ENEMY),
+        MaterialMesh2dBundle {
+            mesh: meshes.add(Circle::new(spawn.size)).into(),
+            material: materials.add(ColorMaterial::from(spawn.element.color())),
+            transform: Transform::from_xyz(spawn.position.x, spawn.position.y, 0.0),
+            ..default()
+        },
+    )).id()
+}
+
 // Função para mover projéteis
 fn move_projectiles(
     mut commands: Commands,
-    mut projectile_query: Query<(Entity, &mut Transform, &Projectile)>,
+    mut projectile_query: Query<(Entity, &mut Transform, &Projectile, Option<&mut ProjectileLifetime>)>,
+    obstacle_query: Query<(&Transform, &Obstacle), Without<Projectile>>,
     time: Res<Time>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    arena: Res<ArenaSize>,
+    tuning: Res<GameTuning>,
 ) {
-    let window = window_query.single(); // <-- PODE CAUSAR PANIC SE NÃO HOUVER UMA JANELA
-    let window_width = window.width();
-    let window_height = window.height();
-    let x_bound = (window_width - crate::config::GameConfig::WALL_THICKNESS) / 2.0;
-    let y_bound = (window_height - crate::config::GameConfig::WALL_THICKNESS) / 2.0;
+    let bounds = arena.bounds(tuning.wall_thickness, 0.0);
+
+    for (entity, mut transform, projectile, lifetime) in projectile_query.iter_mut() {
+        if let Some(mut lifetime) = lifetime
+            && lifetime.0.tick(time.delta()).finished()
+        {
+            commands.entity(entity).despawn(); // Alcance da arma esgotado
+            continue;
+        }
 
-    for (entity, mut transform, projectile) in projectile_query.iter_mut() {
         transform.translation += Vec3::new(
             projectile.direction.x * projectile.speed * time.delta_seconds(),
             projectile.direction.y * projectile.speed * time.delta_seconds(),
             0.0,
         );
 
-        if transform.translation.x.abs() > x_bound || transform.translation.y.abs() > y_bound {
-            commands.entity(entity).despawn(); // Remove projéteis que saem da tela
+        // Buracos não param projéteis, só paredes
+        let position = transform.translation.xy();
+        let hit_wall = obstacle_query
+            .iter()
+            .any(|(wall, obstacle)| obstacle.blocks_projectiles && obstacle.rect(wall.translation.xy()).contains(position));
+        if hit_wall || position.x.abs() > bounds.x || position.y.abs() > bounds.y {
+            commands.entity(entity).despawn(); // Remove projéteis que saem da tela ou batem em paredes
         }
     }
 }
 
-// Verifica colisão entre projéteis e inimigos
+// Cada projétil acerta um inimigo, mais um por ponto de `Piercing`
 fn check_projectile_collision(
     mut commands: Commands,
-    mut transform_queries: ParamSet<(
-        Query<(Entity, &Transform, &Projectile), Without<Enemy>>,
-        Query<(Entity, &Transform), (With<Enemy>, Without<Projectile>)>,
-    )>,
-    meshes: ResMut<Assets<Mesh>>,
-    materials: ResMut<Assets<ColorMaterial>>,
+    mut collisions: EventReader<CollisionEvent>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut element_hits: EventWriter<ElementHit>,
+    mut projectile_query: Query<(&Projectile, &Damage, &Transform, Option<&mut Piercing>)>,
 ) {
-    let enemies: Vec<(Entity, Vec2)> = transform_queries.p1()
-        .iter()
-        .map(|(entity, transform)| (entity, transform.translation.xy()))
-        .collect();
-
-    for (projectile_entity, projectile_transform, projectile) in transform_queries.p0().iter_mut() {
-        let projectile_pos = projectile_transform.translation.xy();
-        for (enemy_entity, enemy_pos) in &enemies {
-            let distance = (projectile_pos - *enemy_pos).length();
-
-            if distance < crate::config::GameConfig::ENEMY_SIZE + PROJECTILE_SIZE {
-                commands.entity(projectile_entity).despawn();
-                commands.entity(*enemy_entity).despawn();
-                break;
+    let mut spent = HashSet::new();
+
+    for event in collisions.read() {
+        let Some((projectile, enemy)) = event.between(Layers::PROJECTILE, Layers::ENEMY) else {
+            continue;
+        };
+        let Ok((projectile_data, damage, transform, piercing)) = projectile_query.get_mut(projectile) else {
+            continue;
+        };
+        if spent.contains(&projectile) {
+            continue;
+        }
+        match piercing {
+            Some(mut piercing) => {
+                // Ainda encostando em quem já atravessou
+                if piercing.hit.contains(&enemy) {
+                    continue;
+                }
+                piercing.hit.push(enemy);
+                if piercing.remaining == 0 {
+                    spent.insert(projectile);
+                    commands.entity(projectile).despawn();
+                } else {
+                    piercing.remaining -= 1;
+                }
             }
+            None => {
+                spent.insert(projectile);
+                commands.entity(projectile).despawn();
+            }
+        }
+        damage_events.send(DamageEvent::hit(enemy, damage.0));
+
+        let position = transform.translation.xy();
+        element_hits.send(ElementHit { target: enemy, element: projectile_data.element, position });
+        // Com o power-up explosivo, qualquer projétil também explode
+        if projectile_data.explosive && projectile_data.element != Element::Explosive {
+            element_hits.send(ElementHit { target: enemy, element: Element::Explosive, position });
         }
     }
 }
@@ -1,22 +1,33 @@
 use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use bevy::sprite::MaterialMesh2dBundle;
 use rand::Rng;
 use crate::player::Player;
 use crate::lives::Lives;
-use crate::enemies::Enemy;
+use crate::config::GameTuning;
+use crate::rng::GameRng;
+use crate::simulation::Interpolated;
+use crate::collision::{Collider, CollisionEvent, Layers};
+use crate::combat::{Damage, DamageEvent};
+use crate::enemies::EnemyKilled;
+use crate::room_templates::RoomLayouts;
+use crate::rooms::{RoomCleared, RoomId, RoomMember};
 
 #[derive(Component)]
 pub struct PowerUp {
     pub powerup_type: PowerUpType,
 }
 
-#[derive(Resource, Default)]
-pub struct PowerUpSpawnState {
-    pub powerup_spawned: bool,
-    pub current_room_id: Option<crate::rooms::RoomId>, // Rastreia a sala atual
+/// Enviado quando o jogador pega um power-up.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PowerUpCollected {
+    pub powerup_type: PowerUpType,
+    pub position: Vec2,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum PowerUpType {
     FasterProjectile,
     ExplosiveProjectile,
@@ -32,31 +43,24 @@ pub struct RotatingCircle {
     pub timer: Timer,
 }
 
-#[derive(Resource, Default)]
+#[derive(Resource, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PlayerPowerUpState {
     pub faster_projectile_timer: Option<Timer>,
     pub explosive_projectile_timer: Option<Timer>,
 }
 
-pub fn spawn_powerups(
+/// Quando uma sala é limpa, a recompensa aparece no primeiro ponto de
+/// recompensa do modelo dela, ou no centro.
+pub fn spawn_room_reward(
     mut commands: Commands,
-    powerup_query: Query<&PowerUp>,
-    enemy_query: Query<&crate::enemies::Enemy>,
-    current_room: Res<crate::rooms::CurrentRoom>,
+    mut cleared_events: EventReader<RoomCleared>,
+    layouts: RoomLayouts,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
-    mut powerup_spawn_state: ResMut<PowerUpSpawnState>,
+    mut game_rng: ResMut<GameRng>,
 ) {
-    let enemy_count = enemy_query.iter().filter(|enemy| enemy.room == current_room.id).count();
-    let powerup_count = powerup_query.iter().filter(|powerup| powerup.powerup_type != PowerUpType::ExtraLife).count();
-
-    // Só spawna um power-up se:
-    // 1. Não houver inimigos na sala
-    // 2. Não houver power-ups na sala
-    // 3. Um power-up ainda não foi spawnado nesta sala
-    if enemy_count == 0 && powerup_count == 0 && !powerup_spawn_state.powerup_spawned {
-        let mut rng = rand::thread_rng();
-        let powerup_type = match rng.gen_range(0..4) {
+    for event in cleared_events.read() {
+        let powerup_type = match game_rng.loot.gen_range(0..4) {
             0 => PowerUpType::FasterProjectile,
             1 => PowerUpType::ExplosiveProjectile,
             2 => PowerUpType::RotatingCircle,
@@ -64,140 +68,159 @@ pub fn spawn_powerups(
             _ => unreachable!(),
         };
 
-        let color = match powerup_type {
-            PowerUpType::FasterProjectile => Color::BLUE,
-            PowerUpType::ExplosiveProjectile => Color::PURPLE,
-            PowerUpType::RotatingCircle => Color::ORANGE,
-            PowerUpType::ExtraLife => Color::RED,
-        };
-
-        commands.spawn((
-            PowerUp { powerup_type },
-            MaterialMesh2dBundle {
-                mesh: meshes.add(Circle::new(10.0)).into(),
-                material: materials.add(ColorMaterial::from(color)),
-                transform: Transform::from_xyz(0.0, 0.0, 0.0),
-                ..default()
-            },
-        ));
-        powerup_spawn_state.powerup_spawned = true;
-        powerup_spawn_state.current_room_id = Some(current_room.id); // Marca a sala atual
-    }
-}
-
-pub fn reset_powerup_spawn_state(
-    current_room: Res<crate::rooms::CurrentRoom>,
-    mut powerup_spawn_state: ResMut<PowerUpSpawnState>,
-) {
-    // Se a sala atual mudou, reseta o estado de spawn de power-up
-    if powerup_spawn_state.current_room_id != Some(current_room.id) {
-        powerup_spawn_state.powerup_spawned = false;
-        powerup_spawn_state.current_room_id = Some(current_room.id);
+        let position = layouts
+            .get(event.room)
+            .pickup_spawns
+            .first()
+            .copied()
+            .unwrap_or(Vec2::ZERO);
+        spawn_powerup(&mut commands, powerup_type, position, event.room, &mut meshes, &mut materials);
     }
 }
 
 // ... (o resto do arquivo permanece o mesmo, incluindo try_spawn_powerup, collect_powerups, etc.)
 
-pub fn try_spawn_powerup(
+/// Cria um power-up no chão de uma sala.
+pub fn spawn_powerup(
     commands: &mut Commands,
+    powerup_type: PowerUpType,
     position: Vec2,
+    room: RoomId,
     meshes: &mut Assets<Mesh>,
     materials: &mut Assets<ColorMaterial>,
-) {
-    let mut rng = rand::thread_rng();
-    if rng.gen_range(0.0..1.0) < 0.2 {
-        let powerup_type = PowerUpType::ExtraLife;
-        let color = Color::RED;
+) -> Entity {
+    let color = match powerup_type {
+        PowerUpType::FasterProjectile => Color::BLUE,
+        PowerUpType::ExplosiveProjectile => Color::PURPLE,
+        PowerUpType::RotatingCircle => Color::ORANGE,
+        PowerUpType::ExtraLife => Color::RED,
+    };
 
-        commands.spawn((
+    commands
+        .spawn((
             PowerUp { powerup_type },
+            RoomMember(room),
+            Collider::circle(10.0, Layers::PICKUP, Layers::PLAYER),
             MaterialMesh2dBundle {
                 mesh: meshes.add(Circle::new(10.0)).into(),
                 material: materials.add(ColorMaterial::from(color)),
                 transform: Transform::from_xyz(position.x, position.y, 0.0),
                 ..default()
             },
-        ));
+        ))
+        .id()
+}
+
+pub fn try_spawn_powerup(
+    commands: &mut Commands,
+    position: Vec2,
+    room: RoomId,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    rng: &mut impl Rng,
+) {
+    if rng.gen_range(0.0..1.0) < 0.2 {
+        spawn_powerup(commands, PowerUpType::ExtraLife, position, room, meshes, materials);
     }
 }
 
+/// O que um power-up coletado pode alterar: os timers do jogador, as vidas
+/// e os assets do círculo giratório.
+#[derive(SystemParam)]
+pub struct PowerUpEffects<'w, 's> {
+    state: ResMut<'w, PlayerPowerUpState>,
+    lives: Query<'w, 's, &'static mut Lives>,
+    meshes: ResMut<'w, Assets<Mesh>>,
+    materials: ResMut<'w, Assets<ColorMaterial>>,
+}
+
 pub fn collect_powerups(
     mut commands: Commands,
-    mut transform_queries: ParamSet<(
-        Query<&mut Transform, With<Player>>,
-        Query<(Entity, &Transform, &PowerUp), Without<Player>>,
-    )>,
-    mut lives_query: Query<&mut Lives>,
-    mut player_powerup_state: ResMut<PlayerPowerUpState>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    mut powerup_spawn_state: ResMut<PowerUpSpawnState>,
+    mut collisions: EventReader<CollisionEvent>,
+    player_query: Query<&Transform, With<Player>>,
+    powerup_query: Query<(&PowerUp, &Transform)>,
+    mut effects: PowerUpEffects,
+    mut collected_events: EventWriter<PowerUpCollected>,
+    tuning: Res<GameTuning>,
 ) {
-    // Verifica se há exatamente um jogador
-    let player_pos = {
-        let mut player_query = transform_queries.p0();
-        let player_transform = if let Some(player_transform) = player_query.iter_mut().next() {
-            player_transform
-        } else {
-            eprintln!("Erro: Nenhum jogador encontrado!"); // Log de erro
-            return; // Retorna se não houver um jogador
-        };
-        player_transform.translation.xy()
-    };
+    let duration = tuning.powerup_duration;
 
-    // Itera pelos power-ups e verifica a distância
-    let mut powerup_query = transform_queries.p1();
-    for (powerup_entity, powerup_transform, powerup) in powerup_query.iter_mut() {
-        let powerup_pos = powerup_transform.translation.xy();
-        let distance = (player_pos - powerup_pos).length();
+    let mut collected = HashSet::new();
 
-        if distance < 20.0 {
-            match powerup.powerup_type {
-                PowerUpType::FasterProjectile => {
-                    player_powerup_state.faster_projectile_timer = Some(Timer::from_seconds(10.0, TimerMode::Once));
-                }
-                PowerUpType::ExplosiveProjectile => {
-                    player_powerup_state.explosive_projectile_timer = Some(Timer::from_seconds(10.0, TimerMode::Once));
-                }
-                PowerUpType::RotatingCircle => {
-                    commands.spawn((
-                        RotatingCircle {
-                            angle: 0.0,
-                            speed: 2.0,
-                            radius: 50.0,
-                            timer: Timer::from_seconds(10.0, TimerMode::Once),
-                        },
-                        MaterialMesh2dBundle {
-                            mesh: meshes.add(Circle::new(5.0)).into(),
-                            material: materials.add(ColorMaterial::from(Color::PURPLE)),
-                            transform: Transform::from_xyz(player_pos.x, player_pos.y, 0.0),
-                            ..default()
-                        },
-                    ));
-                }
-                PowerUpType::ExtraLife => {
-                    // Verifica se há exatamente um componente Lives
-                    let mut lives = if let Some(lives) = lives_query.iter_mut().next() {
-                        lives
-                    } else {
-                        eprintln!("Erro: Nenhum componente Lives encontrado!"); // Log de erro
-                        return; // Retorna se não houver o componente Lives
-                    };
+    // Itera pelos power-ups tocados pelo jogador neste passo
+    for event in collisions.read() {
+        let Some((player_entity, powerup_entity)) = event.between(Layers::PLAYER, Layers::PICKUP) else {
+            continue;
+        };
+        let (Ok(player_transform), Ok((powerup, powerup_transform))) = (player_query.get(player_entity), powerup_query.get(powerup_entity)) else {
+            continue;
+        };
+        if !collected.insert(powerup_entity) {
+            continue;
+        }
+        let player_pos = player_transform.translation.xy();
+        collected_events.send(PowerUpCollected {
+            powerup_type: powerup.powerup_type,
+            position: powerup_transform.translation.xy(),
+        });
 
-                    if lives.count() < 5 {
-                        lives.add_life();
-                    }
-                }
+        match powerup.powerup_type {
+            PowerUpType::FasterProjectile => {
+                effects.state.faster_projectile_timer = Some(Timer::from_seconds(duration, TimerMode::Once));
+            }
+            PowerUpType::ExplosiveProjectile => {
+                effects.state.explosive_projectile_timer = Some(Timer::from_seconds(duration, TimerMode::Once));
+            }
+            PowerUpType::RotatingCircle => {
+                commands.spawn((
+                    RotatingCircle {
+                        angle: 0.0,
+                        speed: 2.0,
+                        radius: 50.0,
+                        timer: Timer::from_seconds(duration, TimerMode::Once),
+                    },
+                    Damage(tuning.rotating_circle_damage),
+                    Interpolated::default(),
+                    Collider::circle(5.0, Layers::ORBITER, Layers::ENEMY),
+                    MaterialMesh2dBundle {
+                        mesh: effects.meshes.add(Circle::new(5.0)).into(),
+                        material: effects.materials.add(ColorMaterial::from(Color::PURPLE)),
+                        transform: Transform::from_xyz(player_pos.x, player_pos.y, 0.0),
+                        ..default()
+                    },
+                ));
             }
+            PowerUpType::ExtraLife => {
+                // Sem `Lives` não há onde pôr a vida extra; os outros power-ups do passo seguem
+                let Some(mut lives) = effects.lives.iter_mut().next() else {
+                    warn!("Nenhum componente Lives encontrado para a vida extra");
+                    continue;
+                };
 
-            // Remove o power-up da cena
-            commands.entity(powerup_entity).despawn();
-            powerup_spawn_state.powerup_spawned = true;
+                if lives.count() < tuning.max_lives {
+                    lives.add_life();
+                }
+            }
         }
+
+        // Remove o power-up da cena
+        commands.entity(powerup_entity).despawn();
     }
 }
 
 
+/// Uma partida nova começa sem os power-ups da anterior.
+pub fn reset_powerups(
+    mut commands: Commands,
+    mut player_powerup_state: ResMut<PlayerPowerUpState>,
+    circle_query: Query<Entity, With<RotatingCircle>>,
+) {
+    *player_powerup_state = PlayerPowerUpState::default();
+    for entity in circle_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
 pub fn update_powerup_timers(
     mut player_powerup_state: ResMut<PlayerPowerUpState>,
     time: Res<Time>,
@@ -219,38 +242,21 @@ pub fn update_powerup_timers(
 
 pub fn update_rotating_circle(
     mut commands: Commands,
-    mut transform_queries: ParamSet<(
-        Query<(Entity, &mut Transform, &mut RotatingCircle)>,
-        Query<&Transform, With<Player>>,
-        Query<(Entity, &Transform), (With<Enemy>, Without<Player>)>,
-    )>,
+    mut circle_query: Query<(Entity, &mut Transform, &mut RotatingCircle), Without<Player>>,
+    player_query: Query<&Transform, With<Player>>,
     time: Res<Time>,
 ) {
-    let player_pos = {
-        let player_query = transform_queries.p1();
-        let player_transform = player_query.get_single().expect("Falha ao obter o transform do jogador!");
-        player_transform.translation.xy()
-    };
-
-    let enemies: Vec<(Entity, Vec2)> = {
-        let enemy_query = transform_queries.p2();
-        enemy_query.iter().map(|(entity, transform)| (entity, transform.translation.xy())).collect()
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
     };
+    let player_pos = player_transform.translation.xy();
 
-    let mut circle_query = transform_queries.p0();
     for (entity, mut transform, mut circle) in circle_query.iter_mut() {
         circle.angle += circle.speed * time.delta_seconds();
         let x = player_pos.x + circle.radius * circle.angle.cos();
         let y = player_pos.y + circle.radius * circle.angle.sin();
         transform.translation = Vec3::new(x, y, 0.0);
 
-        for (enemy_entity, enemy_pos) in enemies.iter() {
-            let distance = (Vec2::new(x, y) - *enemy_pos).length();
-            if distance < 10.0 {
-                commands.entity(*enemy_entity).despawn();
-            }
-        }
-
         circle.timer.tick(time.delta());
         if circle.timer.finished() {
             commands.entity(entity).despawn();
@@ -258,4 +264,30 @@ pub fn update_rotating_circle(
     }
 }
 
+// Inimigos tocados pelo círculo giratório levam dano
+pub fn rotating_circle_hits(
+    mut collisions: EventReader<CollisionEvent>,
+    mut damage_events: EventWriter<DamageEvent>,
+    circle_query: Query<&Damage, With<RotatingCircle>>,
+) {
+    for event in collisions.read() {
+        if let Some((circle, enemy)) = event.between(Layers::ORBITER, Layers::ENEMY)
+            && let Ok(damage) = circle_query.get(circle)
+        {
+            damage_events.send(DamageEvent::hit(enemy, damage.0));
+        }
+    }
+}
 
+// Inimigos derrotados às vezes deixam uma vida extra
+pub fn drop_loot(
+    mut commands: Commands,
+    mut killed_events: EventReader<EnemyKilled>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    for event in killed_events.read() {
+        try_spawn_powerup(&mut commands, event.position, event.room, &mut meshes, &mut materials, &mut game_rng.loot);
+    }
+}
@@ -0,0 +1,439 @@
+use bevy::prelude::*;
+use bevy::app::AppExit;
+use bevy::input::InputSystem;
+use bevy::time::TimeUpdateStrategy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::actions::{ActionSet, ActionSnapshot, ActionState};
+use crate::aim::{update_aim, Aim};
+use crate::arena::ArenaSize;
+use crate::config::GameTuning;
+use crate::lives::Lives;
+use crate::rng::{GameRng, SeedOverride};
+use crate::rooms::{CurrentRoom, RoomId};
+use crate::scoring::Score;
+
+/// Versão atual do formato de replay.
+pub const REPLAY_VERSION: u32 = 1;
+
+/// Passo de tempo usado ao gravar, se nenhum outro for informado.
+pub const DEFAULT_TIMESTEP: Duration = Duration::from_nanos(16_666_667);
+
+/// Plugin que grava e reproduz a partida quadro a quadro.
+///
+/// Grava as ações já resolvidas e a mira de cada quadro, que é o que os
+/// passos fixos do quadro leem, então mouse, controle e controles trocados
+/// no menu de opções são reproduzidos. As teclas também são gravadas, para
+/// o que lê o teclado direto, como o nome do recorde.
+///
+/// Só faz algo quando existe um `ReplayRecorder` ou um `ReplayPlayer`.
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ReplayFrame>()
+            .add_systems(PreUpdate, (
+                reject_other_tuning.run_if(resource_exists::<ReplayPlayer>),
+                play_inputs.run_if(resource_exists::<ReplayPlayer>),
+                record_inputs.run_if(resource_exists::<ReplayRecorder>),
+            ).chain().after(InputSystem).before(ActionSet))
+            .add_systems(PreUpdate, (
+                play_actions.run_if(resource_exists::<ReplayPlayer>),
+                record_actions.run_if(resource_exists::<ReplayRecorder>),
+            ).chain().after(ActionSet))
+            .add_systems(Update, (
+                play_aim.run_if(resource_exists::<ReplayPlayer>),
+                record_aim.run_if(resource_exists::<ReplayRecorder>),
+            ).chain().after(update_aim))
+            .add_systems(Last, (
+                advance_frame,
+                finish_playback.run_if(resource_exists::<ReplayPlayer>),
+                save_recording_on_exit.run_if(resource_exists::<ReplayRecorder>),
+            ).chain());
+    }
+}
+
+/// Uma partida gravada: semente, ajustes, passo de tempo e, por quadro, as
+/// mudanças de tecla, de ações e de mira.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Replay {
+    pub version: u32,
+    pub seed: u64,
+    pub tuning: GameTuning, // A reprodução com outros ajustes é recusada
+    pub timestep: Duration,
+    pub arena: (f32, f32),
+    pub frames: u32,
+    pub inputs: Vec<InputChange>,
+    pub actions: Vec<ActionChange>,
+    pub aim: Vec<AimChange>,
+    pub outcome: Option<ReplayOutcome>,
+}
+
+/// Tecla pressionada (`pressed: true`) ou solta em um quadro.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct InputChange {
+    pub frame: u32,
+    pub key: KeyCode,
+    pub pressed: bool,
+}
+
+/// Ações do quadro, gravadas só quando mudam.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ActionChange {
+    pub frame: u32,
+    pub actions: ActionSnapshot,
+}
+
+/// Mira no fim do `Update` do quadro, gravada só quando muda.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct AimChange {
+    pub frame: u32,
+    pub aim: Aim,
+}
+
+/// Estado final da partida, usado para conferir a reprodução.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct ReplayOutcome {
+    pub score: u32,
+    pub lives: u32,
+    pub room: RoomId,
+}
+
+impl ReplayOutcome {
+    /// Lê o estado atual da partida.
+    pub fn capture(world: &mut World) -> Self {
+        let lives = world
+            .query::<&Lives>()
+            .get_single(world)
+            .map(|lives| lives.count())
+            .unwrap_or(0);
+        ReplayOutcome {
+            score: world.resource::<Score>().value,
+            lives,
+            room: world.resource::<CurrentRoom>().id,
+        }
+    }
+}
+
+impl Replay {
+    pub fn load(path: &Path) -> Result<Self, ReplayError> {
+        let text = std::fs::read_to_string(path).map_err(ReplayError::Io)?;
+        let replay: Replay = ron::from_str(&text).map_err(ReplayError::Parse)?;
+        if replay.version != REPLAY_VERSION {
+            return Err(ReplayError::UnsupportedVersion(replay.version));
+        }
+        Ok(replay)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), ReplayError> {
+        let text = ron::to_string(self).map_err(ReplayError::Serialize)?;
+        std::fs::write(path, text).map_err(ReplayError::Io)
+    }
+
+    /// Confere o estado final com o que foi gravado.
+    pub fn verify(&self, actual: ReplayOutcome) -> Result<ReplayOutcome, ReplayError> {
+        match self.outcome {
+            None => Err(ReplayError::MissingOutcome),
+            Some(expected) if expected != actual => Err(ReplayError::Mismatch { expected, actual }),
+            Some(_) => Ok(actual),
+        }
+    }
+}
+
+/// Erros ao ler, gravar ou conferir um replay.
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    Serialize(ron::Error),
+    UnsupportedVersion(u32),
+    TuningMismatch,
+    MissingOutcome,
+    Mismatch { expected: ReplayOutcome, actual: ReplayOutcome },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Io(err) => write!(f, "erro de arquivo: {err}"),
+            ReplayError::Parse(err) => write!(f, "replay inválido: {err}"),
+            ReplayError::Serialize(err) => write!(f, "não foi possível gerar o replay: {err}"),
+            ReplayError::UnsupportedVersion(version) => {
+                write!(f, "versão de replay {version} não suportada (esperada {REPLAY_VERSION})")
+            }
+            ReplayError::TuningMismatch => write!(f, "o replay foi gravado com outros ajustes de jogo"),
+            ReplayError::MissingOutcome => write!(f, "o replay não tem o estado final gravado"),
+            ReplayError::Mismatch { expected, actual } => {
+                write!(f, "reprodução divergiu: esperado {expected:?}, obtido {actual:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+/// Número de quadros já simulados desde o início do app.
+#[derive(Resource, Default, Debug, Clone, Copy)]
+pub struct ReplayFrame(pub u32);
+
+/// Recurso presente enquanto uma partida está sendo gravada.
+#[derive(Resource)]
+pub struct ReplayRecorder {
+    pub path: Option<PathBuf>,
+    timestep: Duration,
+    tuning: Option<GameTuning>, // Ajustes do primeiro quadro gravado
+    held: HashSet<KeyCode>,
+    inputs: Vec<InputChange>,
+    actions: Vec<ActionChange>,
+    aim: Vec<AimChange>,
+}
+
+impl ReplayRecorder {
+    pub fn new(path: Option<PathBuf>, timestep: Duration) -> Self {
+        ReplayRecorder {
+            path,
+            timestep,
+            tuning: None,
+            held: HashSet::new(),
+            inputs: Vec::new(),
+            actions: Vec::new(),
+            aim: Vec::new(),
+        }
+    }
+}
+
+/// Recurso presente enquanto um replay está sendo reproduzido.
+#[derive(Resource)]
+pub struct ReplayPlayer {
+    replay: Replay,
+    cursor: usize,
+    held: HashSet<KeyCode>,
+    action_cursor: usize,
+    actions: ActionSnapshot, // Ações gravadas do quadro atual
+    aim_cursor: usize,
+    aim: Option<Aim>,        // Mira gravada do quadro atual
+}
+
+/// Recurso presente quando o replay foi recusado por ter sido gravado com
+/// outros ajustes de jogo.
+#[derive(Resource, Debug)]
+pub struct ReplayRejected;
+
+/// Prepara o app para gravar a partida com passo de tempo fixo.
+pub fn configure_recording(app: &mut App, path: Option<PathBuf>, timestep: Duration) {
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(timestep))
+        .insert_resource(ReplayRecorder::new(path, timestep));
+}
+
+/// Prepara o app para reproduzir um replay: mesma semente, arena e passo de tempo.
+pub fn configure_playback(app: &mut App, replay: Replay) {
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(replay.timestep))
+        .insert_resource(SeedOverride(replay.seed))
+        .insert_resource(ArenaSize::new(replay.arena.0, replay.arena.1))
+        .insert_resource(ReplayPlayer {
+            replay,
+            cursor: 0,
+            held: HashSet::new(),
+            action_cursor: 0,
+            actions: ActionSnapshot::default(),
+            aim_cursor: 0,
+            aim: None,
+        });
+}
+
+/// Reproduz um replay sem janela e confere o estado final.
+pub fn run_headless(replay: Replay) -> Result<ReplayOutcome, ReplayError> {
+    let frames = replay.frames;
+    let mut app = App::new();
+    app.add_plugins(crate::RustEzeHeadlessPlugin);
+    configure_playback(&mut app, replay.clone());
+
+    for _ in 0..frames {
+        app.update();
+        if app.world.contains_resource::<ReplayRejected>() {
+            return Err(ReplayError::TuningMismatch);
+        }
+    }
+
+    replay.verify(ReplayOutcome::capture(&mut app.world))
+}
+
+/// Encerra a gravação e monta o replay com o estado final da partida.
+pub fn finish_recording(world: &mut World) -> Option<Replay> {
+    let recorder = world.remove_resource::<ReplayRecorder>()?;
+    let arena = *world.resource::<ArenaSize>();
+    Some(Replay {
+        version: REPLAY_VERSION,
+        seed: world.resource::<GameRng>().seed(),
+        tuning: recorder.tuning.unwrap_or_else(|| world.resource::<GameTuning>().clone()),
+        timestep: recorder.timestep,
+        arena: (arena.width, arena.height),
+        frames: world.resource::<ReplayFrame>().0,
+        inputs: recorder.inputs,
+        actions: recorder.actions,
+        aim: recorder.aim,
+        outcome: Some(ReplayOutcome::capture(world)),
+    })
+}
+
+/// Registra as teclas que mudaram de estado neste quadro.
+fn record_inputs(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    frame: Res<ReplayFrame>,
+    mut recorder: ResMut<ReplayRecorder>,
+) {
+    let frame = frame.0;
+    let pressed: HashSet<KeyCode> = keyboard.get_pressed().copied().collect();
+
+    // Uma tecla apertada e solta no mesmo quadro aparece só em `just_pressed`.
+    let mut changes = Vec::new();
+    for &key in keyboard.get_just_pressed() {
+        if !recorder.held.contains(&key) {
+            changes.push(InputChange { frame, key, pressed: true });
+        }
+    }
+    for &key in pressed.iter() {
+        if !recorder.held.contains(&key) && !keyboard.just_pressed(key) {
+            changes.push(InputChange { frame, key, pressed: true });
+        }
+    }
+    for &key in recorder.held.iter().chain(keyboard.get_just_pressed()) {
+        if !pressed.contains(&key) {
+            changes.push(InputChange { frame, key, pressed: false });
+        }
+    }
+
+    recorder.inputs.extend(changes);
+    recorder.held = pressed;
+}
+
+/// Substitui a entrada real pela entrada gravada para este quadro.
+fn play_inputs(
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    frame: Res<ReplayFrame>,
+    mut player: ResMut<ReplayPlayer>,
+) {
+    keyboard.reset_all();
+    for &key in player.held.iter() {
+        keyboard.press(key);
+        keyboard.clear_just_pressed(key);
+    }
+
+    while let Some(change) = player.replay.inputs.get(player.cursor).copied() {
+        if change.frame != frame.0 {
+            break;
+        }
+        if change.pressed {
+            keyboard.press(change.key);
+            player.held.insert(change.key);
+        } else {
+            keyboard.release(change.key);
+            player.held.remove(&change.key);
+        }
+        player.cursor += 1;
+    }
+}
+
+/// Grava as ações do quadro, se mudaram desde o último quadro gravado.
+fn record_actions(
+    actions: Res<ActionState>,
+    tuning: Res<GameTuning>,
+    frame: Res<ReplayFrame>,
+    mut recorder: ResMut<ReplayRecorder>,
+) {
+    if recorder.tuning.is_none() {
+        recorder.tuning = Some(tuning.clone());
+    }
+    let snapshot = actions.snapshot();
+    let unchanged = match recorder.actions.last() {
+        Some(last) => last.actions == snapshot,
+        None => snapshot == ActionSnapshot::default(),
+    };
+    if !unchanged {
+        recorder.actions.push(ActionChange { frame: frame.0, actions: snapshot });
+    }
+}
+
+/// Troca as ações resolvidas dos controles reais pelas gravadas.
+fn play_actions(mut actions: ResMut<ActionState>, frame: Res<ReplayFrame>, mut player: ResMut<ReplayPlayer>) {
+    while let Some(change) = player.replay.actions.get(player.action_cursor) {
+        if change.frame != frame.0 {
+            break;
+        }
+        player.actions = change.actions.clone();
+        player.action_cursor += 1;
+    }
+    actions.restore(&player.actions);
+}
+
+/// Grava a mira que os passos fixos do próximo quadro vão usar.
+fn record_aim(aim: Res<Aim>, frame: Res<ReplayFrame>, mut recorder: ResMut<ReplayRecorder>) {
+    if recorder.aim.last().map(|change| change.aim) != Some(*aim) {
+        recorder.aim.push(AimChange { frame: frame.0, aim: *aim });
+    }
+}
+
+/// Troca a mira do cursor ou do stick pela gravada.
+fn play_aim(mut aim: ResMut<Aim>, frame: Res<ReplayFrame>, mut player: ResMut<ReplayPlayer>) {
+    while let Some(change) = player.replay.aim.get(player.aim_cursor).copied() {
+        if change.frame != frame.0 {
+            break;
+        }
+        player.aim = Some(change.aim);
+        player.aim_cursor += 1;
+    }
+    if let Some(recorded) = player.aim
+        && *aim != recorded
+    {
+        *aim = recorded;
+    }
+}
+
+/// Recusa o replay quando os ajustes atuais não são os da gravação, porque
+/// a partida não teria como terminar igual. Vale também para uma recarga
+/// do `tuning.ron` no meio da reprodução.
+fn reject_other_tuning(mut commands: Commands, tuning: Res<GameTuning>, player: Res<ReplayPlayer>) {
+    if *tuning != player.replay.tuning {
+        error!("Replay interrompido: {}", ReplayError::TuningMismatch);
+        commands.remove_resource::<ReplayPlayer>();
+        commands.insert_resource(ReplayRejected);
+    }
+}
+
+/// Ao fim do replay, confere o resultado e devolve o controle ao jogador.
+fn finish_playback(world: &mut World) {
+    let frame = world.resource::<ReplayFrame>().0;
+    if frame < world.resource::<ReplayPlayer>().replay.frames {
+        return;
+    }
+
+    let player = world.remove_resource::<ReplayPlayer>().expect("ReplayPlayer presente");
+    match player.replay.verify(ReplayOutcome::capture(world)) {
+        Ok(outcome) => info!("Replay terminou igual ao gravado: {:?}", outcome),
+        Err(err) => warn!("Replay terminou: {}", err),
+    }
+}
+
+/// Salva a gravação quando o app é encerrado.
+fn save_recording_on_exit(world: &mut World) {
+    if world.resource::<Events<AppExit>>().is_empty() {
+        return;
+    }
+    let Some(path) = world.resource::<ReplayRecorder>().path.clone() else {
+        return;
+    };
+    let replay = finish_recording(world).expect("ReplayRecorder presente");
+    match replay.save(&path) {
+        Ok(()) => info!("Replay salvo em {:?} ({} quadros)", path, replay.frames),
+        Err(err) => error!("Não foi possível salvar o replay em {:?}: {}", path, err),
+    }
+}
+
+fn advance_frame(mut frame: ResMut<ReplayFrame>) {
+    frame.0 += 1;
+}
@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use crate::config::{load_tuning, GameTuning};
+
+/// Plugin que cria o `GameRng` e escolhe a semente da partida.
+pub struct RngPlugin;
+
+impl Plugin for RngPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<GameRng>()
+            .add_systems(PreStartup, apply_seed.after(load_tuning));
+    }
+}
+
+/// Semente escolhida na linha de comando (`--seed <n>`). Tem prioridade
+/// sobre o campo `seed` do arquivo de ajustes.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct SeedOverride(pub u64);
+
+/// Recurso com toda a aleatoriedade do jogo.
+///
+/// Cada assunto tem o seu próprio fluxo, derivado da mesma semente, para
+/// que mudar o consumo de um (ex.: mais sorteios de loot) não altere os outros.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameRng {
+    seed: u64,
+    /// Posição e escolha de inimigos.
+    pub enemies: ChaCha8Rng,
+    /// Power-ups e recompensas.
+    pub loot: ChaCha8Rng,
+    /// Geração de salas.
+    pub rooms: ChaCha8Rng,
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        GameRng::new(rand::random())
+    }
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        GameRng {
+            seed,
+            enemies: stream(seed, 0),
+            loot: stream(seed, 1),
+            rooms: stream(seed, 2),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Gerador da partida seguinte, com a semente tirada deste: cada recomeço
+    /// tem outro andar, mas a mesma semente inicial repete a sequência.
+    pub fn next_run(&mut self) -> Self {
+        GameRng::new(self.rooms.next_u64())
+    }
+}
+
+fn stream(seed: u64, id: u64) -> ChaCha8Rng {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    rng.set_stream(id);
+    rng
+}
+
+/// Aplica a semente da linha de comando ou do arquivo de ajustes, se houver.
+fn apply_seed(
+    seed_override: Option<Res<SeedOverride>>,
+    tuning: Res<GameTuning>,
+    mut rng: ResMut<GameRng>,
+) {
+    let seed = seed_override.map(|seed| seed.0).or(tuning.seed);
+    if let Some(seed) = seed {
+        *rng = GameRng::new(seed);
+    }
+    info!("Semente da partida: {}", rng.seed());
+}
@@ -0,0 +1,193 @@
+use bevy::prelude::*;
+use bevy::sprite::Mesh2dHandle;
+use serde::{Deserialize, Serialize};
+use crate::arena::ArenaSize;
+use crate::combat::Health;
+use crate::enemies::{spawn_enemy, Enemy, RoomEnemySpawner};
+use crate::player::Player;
+use crate::projectiles::powerups::{spawn_powerup, PowerUp, PowerUpType};
+use crate::rooms::{place_player_at_entrance, CurrentRoom, Door, RoomGraph, RoomId, RoomMember};
+use crate::state::{despawn_with, AppState};
+use crate::walls::{Pit, Wall};
+
+/// Plugin que guarda e restaura o conteúdo das salas na troca de sala.
+///
+/// Ao sair de uma sala, os inimigos vivos, os power-ups no chão e se a sala
+/// já foi limpa ficam salvos no `RoomGraph`. Tudo o que tem `RoomMember` da
+/// sala antiga sai do jogo; o que se vê dela fica parado ao lado da sala nova
+/// até a câmera terminar de deslizar. Ao entrar, a sala volta como foi deixada; na
+/// primeira visita, os inimigos vêm da tabela de aparição, menos no tesouro
+/// e na loja.
+pub struct RoomStatePlugin;
+
+impl Plugin for RoomStatePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(
+                OnEnter(AppState::RoomTransition),
+                (save_left_room, despawn_left_room, restore_entered_room)
+                    .chain()
+                    .after(place_player_at_entrance),
+            )
+            .add_systems(OnExit(AppState::GameOver), (despawn_with::<RoomMember>, despawn_with::<LeftRoom>));
+    }
+}
+
+/// Imagem da sala que o jogador acabou de deixar, sem nada de jogo: fica do
+/// lado da porta por onde ele entrou enquanto a câmera desliza e sai do mundo
+/// quando o deslizamento termina.
+#[derive(Component, Debug)]
+pub struct LeftRoom;
+
+/// Componentes que desenham uma entidade; o resto sai da sala deixada.
+type Visuals = (
+    Transform,
+    GlobalTransform,
+    Visibility,
+    InheritedVisibility,
+    ViewVisibility,
+    Sprite,
+    Handle<Image>,
+    Mesh2dHandle,
+    Handle<ColorMaterial>,
+);
+
+/// O que a sala deixada tem na tela: seus membros, paredes e portas.
+type LeftRoomFilter = Or<(With<RoomMember>, With<Wall>, With<Pit>, With<Door>)>;
+
+/// Conteúdo salvo de uma sala que o jogador deixou.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RoomState {
+    pub enemies: Vec<SavedEnemy>,
+    pub pickups: Vec<SavedPickup>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedEnemy {
+    pub kind: String,
+    pub position: Vec2,
+    pub health: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedPickup {
+    pub powerup_type: PowerUpType,
+    pub position: Vec2,
+}
+
+impl RoomState {
+    /// Lê os inimigos vivos e os power-ups no chão de uma sala.
+    pub fn capture(
+        room: RoomId,
+        enemy_query: &Query<(&Enemy, &RoomMember, &Transform, &Health)>,
+        pickup_query: &Query<(&PowerUp, &RoomMember, &Transform)>,
+    ) -> Self {
+        let enemies = enemy_query
+            .iter()
+            .filter(|(_, member, _, _)| member.0 == room)
+            .map(|(enemy, _, transform, health)| SavedEnemy {
+                kind: enemy.kind.clone(),
+                position: transform.translation.xy(),
+                health: health.current,
+            })
+            .collect();
+        let pickups = pickup_query
+            .iter()
+            .filter(|(_, member, _)| member.0 == room)
+            .map(|(powerup, _, transform)| SavedPickup {
+                powerup_type: powerup.powerup_type,
+                position: transform.translation.xy(),
+            })
+            .collect();
+        RoomState { enemies, pickups }
+    }
+}
+
+/// Versão de `RoomState::capture` para rodar direto no mundo.
+pub(crate) fn capture_room(
+    In(room): In<RoomId>,
+    enemy_query: Query<(&Enemy, &RoomMember, &Transform, &Health)>,
+    pickup_query: Query<(&PowerUp, &RoomMember, &Transform)>,
+) -> RoomState {
+    RoomState::capture(room, &enemy_query, &pickup_query)
+}
+
+fn save_left_room(
+    current_room: Res<CurrentRoom>,
+    mut room_graph: ResMut<RoomGraph>,
+    enemy_query: Query<(&Enemy, &RoomMember, &Transform, &Health)>,
+    pickup_query: Query<(&PowerUp, &RoomMember, &Transform)>,
+) {
+    let Some(left) = current_room.previous else {
+        return;
+    };
+
+    if let Some(room) = room_graph.get_room_mut(left) {
+        room.saved = Some(RoomState::capture(left, &enemy_query, &pickup_query));
+    }
+}
+
+/// Tira do jogo tudo o que pertence a outra sala que não a atual, junto com
+/// as paredes e portas dela. O que aparece na tela vira `LeftRoom`, uma sala
+/// adiante, do lado por onde o jogador entrou, para a câmera deslizar por ela.
+fn despawn_left_room(
+    mut commands: Commands,
+    current_room: Res<CurrentRoom>,
+    arena: Res<ArenaSize>,
+    mut left_query: Query<(Entity, Option<&RoomMember>, &mut Transform), LeftRoomFilter>,
+    previous_query: Query<Entity, With<LeftRoom>>,
+) {
+    // Uma troca no meio do deslizamento anterior descarta a sala mais antiga
+    for entity in previous_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    let Some(entered_from) = current_room.entered_from else {
+        return;
+    };
+    let offset = entered_from.as_vec2() * Vec2::new(arena.width, arena.height);
+
+    for (entity, member, mut transform) in left_query.iter_mut() {
+        if member.is_some_and(|member| member.0 == current_room.id) {
+            continue;
+        }
+        transform.translation += offset.extend(0.0);
+        commands.entity(entity).retain::<Visuals>().insert(LeftRoom);
+    }
+}
+
+pub(crate) fn restore_entered_room(
+    mut commands: Commands,
+    current_room: Res<CurrentRoom>,
+    player_query: Query<&Transform, With<Player>>,
+    mut spawner: RoomEnemySpawner,
+) {
+    let room_id = current_room.id;
+    let Some(room) = spawner.room_graph.get_room(room_id) else {
+        return;
+    };
+
+    let Some(saved) = &room.saved else {
+        // Primeira visita: inimigos novos, longe de onde o jogador entrou
+        if room.kind.is_peaceful() {
+            return;
+        }
+        let player_pos = player_query.get_single().map(|transform| transform.translation.xy()).unwrap_or_default();
+        spawner.spawn_room(&mut commands, room_id, player_pos);
+        return;
+    };
+
+    for enemy in &saved.enemies {
+        let Some(kind) = spawner.registry.get(&enemy.kind) else {
+            warn!("Tipo de inimigo {:?} salvo na sala {:?} não existe mais", enemy.kind, room_id);
+            continue;
+        };
+        let entity = spawn_enemy(&mut commands, kind, room_id, enemy.position, &mut spawner.meshes, &mut spawner.materials);
+        commands.entity(entity).insert(Health {
+            max: kind.health,
+            current: enemy.health,
+        });
+    }
+    for pickup in &saved.pickups {
+        spawn_powerup(&mut commands, pickup.powerup_type, pickup.position, room_id, &mut spawner.meshes, &mut spawner.materials);
+    }
+}
@@ -0,0 +1,308 @@
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+use crate::arena::ArenaSize;
+use crate::rooms::{Direction, Room, RoomGraph, RoomId, RoomKind};
+
+/// Caminho padrão do arquivo com os modelos de sala.
+pub const ROOM_TEMPLATES_PATH: &str = "assets/rooms.ron";
+
+/// Cópia do arquivo embutida no executável, usada quando ele falta ou é inválido.
+const BUILTIN_ROOM_TEMPLATES: &str = include_str!("../assets/rooms.ron");
+
+/// Modelo usado por salas criadas fora do gerador de andares: sala vazia.
+pub const DEFAULT_TEMPLATE: &str = "open";
+
+/// Faixa livre obrigatória na frente de cada porta (largura, profundidade),
+/// em frações da arena, para o jogador sempre conseguir entrar e sair.
+const DOOR_LANE: (f32, f32) = (0.25, 0.2);
+
+/// Plugin que carrega os `RoomTemplates` do disco no início do jogo.
+pub struct RoomTemplatesPlugin;
+
+impl Plugin for RoomTemplatesPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<RoomTemplates>()
+            .add_systems(PreStartup, load_room_templates);
+    }
+}
+
+/// Retângulo de um modelo, em frações da arena: o centro vai de -0.5 a 0.5
+/// e o tamanho de 0 a 1 em cada eixo.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Block {
+    pub center: (f32, f32),
+    pub size: (f32, f32),
+}
+
+impl Block {
+    /// Retângulo em coordenadas do mundo para a arena dada.
+    pub fn rect(&self, arena: &ArenaSize) -> Rect {
+        let scale = Vec2::new(arena.width, arena.height);
+        Rect::from_center_size(Vec2::from(self.center) * scale, Vec2::from(self.size) * scale)
+    }
+
+    fn fraction_rect(&self) -> Rect {
+        Rect::from_center_size(Vec2::from(self.center), Vec2::from(self.size))
+    }
+}
+
+fn normal_only() -> Vec<RoomKind> {
+    vec![RoomKind::Normal]
+}
+
+fn all_doors() -> Vec<Direction> {
+    Direction::ALL.to_vec()
+}
+
+/// Um modelo de sala, como descrito no arquivo de salas.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RoomTemplate {
+    pub name: String,
+    #[serde(default = "normal_only")]
+    pub kinds: Vec<RoomKind>,         // Tipos de sala que podem usar o modelo
+    #[serde(default)]
+    pub walls: Vec<Block>,            // Paredes internas: bloqueiam tudo
+    #[serde(default)]
+    pub pits: Vec<Block>,             // Buracos: bloqueiam quem anda, não os projéteis
+    #[serde(default)]
+    pub enemy_spawns: Vec<(f32, f32)>,  // Onde os inimigos aparecem na primeira visita
+    #[serde(default)]
+    pub pickup_spawns: Vec<(f32, f32)>, // Onde aparece a recompensa da sala
+    #[serde(default = "all_doors")]
+    pub doors: Vec<Direction>,        // Lados em que pode haver porta
+}
+
+impl RoomTemplate {
+    /// Verdadeiro se o modelo serve para o tipo da sala e tem vaga para todas as portas dela.
+    pub fn fits(&self, room: &Room) -> bool {
+        self.kinds.contains(&room.kind)
+            && Direction::ALL
+                .into_iter()
+                .filter(|direction| room.has_connection(*direction))
+                .all(|direction| self.doors.contains(&direction))
+    }
+
+    /// Obstáculos e pontos de aparição em coordenadas do mundo.
+    pub fn layout(&self, arena: &ArenaSize) -> RoomLayout {
+        let scale = Vec2::new(arena.width, arena.height);
+        let points = |points: &[(f32, f32)]| points.iter().map(|point| Vec2::from(*point) * scale).collect();
+        RoomLayout {
+            walls: self.walls.iter().map(|block| block.rect(arena)).collect(),
+            pits: self.pits.iter().map(|block| block.rect(arena)).collect(),
+            enemy_spawns: points(&self.enemy_spawns),
+            pickup_spawns: points(&self.pickup_spawns),
+        }
+    }
+
+    fn validate(&self) -> Result<(), RoomTemplatesError> {
+        let invalid = |reason: String| RoomTemplatesError::Invalid { template: self.name.clone(), reason };
+        let arena = Rect::from_center_size(Vec2::ZERO, Vec2::ONE);
+
+        if self.kinds.is_empty() {
+            return Err(invalid("`kinds` precisa de pelo menos um tipo de sala".to_string()));
+        }
+
+        let lanes: Vec<Rect> = self
+            .doors
+            .iter()
+            .map(|direction| {
+                let normal = direction.as_vec2();
+                let (width, depth) = DOOR_LANE;
+                let size = normal.abs() * depth + normal.perp().abs() * width;
+                Rect::from_center_size(normal * (0.5 - depth / 2.0), size)
+            })
+            .collect();
+
+        let blocks = self.walls.iter().chain(&self.pits);
+        let mut rects = Vec::new();
+        for block in blocks {
+            let rect = block.fraction_rect();
+            if !(block.size.0 > 0.0 && block.size.1 > 0.0) {
+                return Err(invalid(format!("bloco em {:?} precisa de tamanho positivo", block.center)));
+            }
+            if !(arena.min.cmple(rect.min + 1e-4).all() && rect.max.cmple(arena.max + 1e-4).all()) {
+                return Err(invalid(format!("bloco em {:?} sai da arena", block.center)));
+            }
+            if rect.contains(Vec2::ZERO) {
+                return Err(invalid(format!("bloco em {:?} cobre o centro da sala", block.center)));
+            }
+            if lanes.iter().any(|lane| !lane.intersect(rect).is_empty()) {
+                return Err(invalid(format!("bloco em {:?} fecha a passagem de uma porta", block.center)));
+            }
+            rects.push(rect);
+        }
+
+        for point in self.enemy_spawns.iter().chain(&self.pickup_spawns) {
+            let point = Vec2::from(*point);
+            if !arena.contains(point) {
+                return Err(invalid(format!("ponto {point} fora da arena")));
+            }
+            if rects.iter().any(|rect| rect.contains(point)) {
+                return Err(invalid(format!("ponto {point} dentro de um bloco")));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Modelo de uma sala já convertido para coordenadas do mundo.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RoomLayout {
+    pub walls: Vec<Rect>,
+    pub pits: Vec<Rect>,
+    pub enemy_spawns: Vec<Vec2>,
+    pub pickup_spawns: Vec<Vec2>,
+}
+
+impl RoomLayout {
+    /// Verdadeiro se um círculo nessa posição encosta em parede ou buraco.
+    pub fn is_blocked(&self, position: Vec2, radius: f32) -> bool {
+        self.walls.iter().chain(&self.pits).any(|rect| {
+            let closest = position.clamp(rect.min, rect.max);
+            closest.distance(position) < radius
+        })
+    }
+}
+
+/// Recurso com todos os modelos de sala.
+#[derive(Resource, Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RoomTemplates {
+    pub templates: Vec<RoomTemplate>,
+}
+
+impl Default for RoomTemplates {
+    fn default() -> Self {
+        RoomTemplates::from_ron(BUILTIN_ROOM_TEMPLATES).expect("assets/rooms.ron embutido é válido")
+    }
+}
+
+impl RoomTemplates {
+    /// Lê, interpreta e valida um arquivo RON de modelos de sala.
+    pub fn load(path: &Path) -> Result<Self, RoomTemplatesError> {
+        let text = std::fs::read_to_string(path).map_err(RoomTemplatesError::Io)?;
+        Self::from_ron(&text)
+    }
+
+    /// Interpreta e valida modelos de sala a partir de um texto RON.
+    pub fn from_ron(text: &str) -> Result<Self, RoomTemplatesError> {
+        let templates: RoomTemplates = ron::from_str(text).map_err(RoomTemplatesError::Parse)?;
+        templates.validate()?;
+        Ok(templates)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&RoomTemplate> {
+        self.templates.iter().find(|template| template.name == name)
+    }
+
+    /// Layout da sala no mundo. Salas com modelo desconhecido ficam vazias.
+    pub fn layout_for(&self, graph: &RoomGraph, room: RoomId, arena: &ArenaSize) -> RoomLayout {
+        graph
+            .get_room(room)
+            .and_then(|room| self.get(&room.template))
+            .map(|template| template.layout(arena))
+            .unwrap_or_default()
+    }
+
+    /// Sorteia, para cada sala do andar, um modelo que sirva para ela.
+    pub fn assign(&self, graph: &mut RoomGraph, rng: &mut impl Rng) {
+        for id in graph.room_ids() {
+            let Some(room) = graph.get_room_mut(id) else {
+                continue;
+            };
+            let options: Vec<&RoomTemplate> = self.templates.iter().filter(|template| template.fits(room)).collect();
+            match options.choose(rng) {
+                Some(template) => room.template = template.name.clone(),
+                None => warn!("Nenhum modelo serve para a sala {:?}; ela fica vazia", id),
+            }
+        }
+    }
+
+    /// Verifica nomes repetidos, blocos fora do lugar e se todo tipo de sala
+    /// tem um modelo com as quatro portas.
+    pub fn validate(&self) -> Result<(), RoomTemplatesError> {
+        let mut names = HashSet::new();
+        for template in &self.templates {
+            if !names.insert(template.name.as_str()) {
+                return Err(RoomTemplatesError::Invalid {
+                    template: template.name.clone(),
+                    reason: "nome repetido".to_string(),
+                });
+            }
+            template.validate()?;
+        }
+
+        for kind in [RoomKind::Start, RoomKind::Normal, RoomKind::Treasure, RoomKind::Shop, RoomKind::Boss] {
+            let covered = self
+                .templates
+                .iter()
+                .any(|template| template.kinds.contains(&kind) && Direction::ALL.iter().all(|direction| template.doors.contains(direction)));
+            if !covered {
+                return Err(RoomTemplatesError::Invalid {
+                    template: format!("{kind:?}"),
+                    reason: "precisa de um modelo com as quatro portas".to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Layout de qualquer sala do andar, para sistemas que só precisam dele.
+#[derive(SystemParam)]
+pub struct RoomLayouts<'w> {
+    templates: Res<'w, RoomTemplates>,
+    room_graph: Res<'w, RoomGraph>,
+    arena: Res<'w, ArenaSize>,
+}
+
+impl RoomLayouts<'_> {
+    pub fn get(&self, room: RoomId) -> RoomLayout {
+        self.templates.layout_for(&self.room_graph, room, &self.arena)
+    }
+}
+
+/// Erros ao carregar o arquivo de modelos de sala.
+#[derive(Debug)]
+pub enum RoomTemplatesError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    Invalid { template: String, reason: String },
+}
+
+impl fmt::Display for RoomTemplatesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RoomTemplatesError::Io(err) => write!(f, "não foi possível ler o arquivo: {err}"),
+            RoomTemplatesError::Parse(err) => write!(f, "erro de sintaxe: {err}"),
+            RoomTemplatesError::Invalid { template, reason } => write!(f, "`{template}` inválido: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for RoomTemplatesError {}
+
+/// Carrega os modelos de sala. Sem arquivo, fica a cópia embutida.
+fn load_room_templates(mut templates: ResMut<RoomTemplates>) {
+    let path = Path::new(ROOM_TEMPLATES_PATH);
+    if !path.exists() {
+        info!("Arquivo de salas {:?} não encontrado, usando os modelos padrão", path);
+        return;
+    }
+
+    match RoomTemplates::load(path) {
+        Ok(loaded) => *templates = loaded,
+        Err(err) => error!("Salas em {:?} ignoradas: {}", path, err),
+    }
+}
@@ -1,8 +1,16 @@
 use bevy::prelude::*;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
 
-use crate::player::Player;
+use crate::arena::ArenaSize;
+use crate::combat::DamageSet;
+use crate::config::GameTuning;
 use crate::enemies::Enemy;
+use crate::player::Player;
+use crate::room_state::RoomState;
+use crate::room_templates::DEFAULT_TEMPLATE;
+use crate::simulation::Interpolated;
+use crate::state::AppState;
 
 /// Plugin responsável por gerenciar as salas do jogo.
 pub struct RoomsPlugin;
@@ -12,17 +20,25 @@ impl Plugin for RoomsPlugin {
         app
             .init_resource::<RoomGraph>()
             .init_resource::<CurrentRoom>()
-            .add_systems(Update, check_room_transition.before(generate_new_rooms))
-            .add_systems(Update, generate_new_rooms)
-            .add_systems(Update, spawn_doors);
+            .init_resource::<PendingTransition>()
+            .add_event::<RoomCleared>()
+            .add_systems(FixedUpdate, (
+                check_room_transition,
+                check_room_cleared.after(DamageSet),
+                open_doors.after(check_room_cleared),
+            ).run_if(in_state(AppState::Playing)))
+            .add_systems(Update, (spawn_doors, explore_current_room).run_if(room_layout_changed))
+            .add_systems(OnEnter(AppState::RoomTransition), (enter_pending_room, place_player_at_entrance).chain())
+            .add_systems(Update, finish_room_transition.run_if(in_state(AppState::RoomTransition)));
     }
 }
 
 /// Recurso que mantém o controle da sala atual em que o jogador se encontra.
-#[derive(Resource)]
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CurrentRoom {
     pub id: RoomId,
     pub entered_from: Option<Direction>,
+    pub previous: Option<RoomId>, // Sala de onde o jogador acabou de sair
 }
 
 impl Default for CurrentRoom {
@@ -30,25 +46,39 @@ impl Default for CurrentRoom {
         CurrentRoom {
             id: RoomId::Central,
             entered_from: None,
+            previous: None,
         }
     }
 }
 
+/// Porta atravessada no passo fixo, aplicada ao `CurrentRoom` só ao entrar em
+/// `RoomTransition`, para que os passos seguintes do mesmo quadro ainda vejam
+/// a sala antiga.
+#[derive(Resource, Default)]
+struct PendingTransition(Option<(RoomId, Direction)>);
+
+/// Evento emitido quando morre o último inimigo de uma sala, ou ao entrar
+/// em uma sala que nunca teve inimigos. Abre as portas e libera a recompensa.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct RoomCleared {
+    pub room: RoomId,
+}
+
+/// Componente das entidades que pertencem a uma sala e saem do mundo
+/// quando o jogador a deixa.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoomMember(pub RoomId);
+
 /// Enum que identifica as salas. Pode ser a sala central ou salas geradas dinamicamente.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
 pub enum RoomId {
+    #[default]
     Central,
     Generated(usize),
 }
 
-impl Default for RoomId {
-    fn default() -> Self {
-        RoomId::Central
-    }
-}
-
 /// Enum para especificar direções.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Direction {
     North,
     South,
@@ -56,39 +86,165 @@ pub enum Direction {
     West,
 }
 
+impl Direction {
+    pub const ALL: [Direction; 4] = [Direction::North, Direction::South, Direction::East, Direction::West];
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::East => Direction::West,
+            Direction::West => Direction::East,
+        }
+    }
+
+    /// Passo na grade do andar para a sala vizinha deste lado.
+    pub fn as_ivec2(self) -> IVec2 {
+        match self {
+            Direction::North => IVec2::Y,
+            Direction::South => IVec2::NEG_Y,
+            Direction::East => IVec2::X,
+            Direction::West => IVec2::NEG_X,
+        }
+    }
+
+    /// Vetor unitário apontando para este lado da sala.
+    pub fn as_vec2(self) -> Vec2 {
+        match self {
+            Direction::North => Vec2::Y,
+            Direction::South => Vec2::NEG_Y,
+            Direction::East => Vec2::X,
+            Direction::West => Vec2::NEG_X,
+        }
+    }
+}
+
+/// Papel de uma sala no andar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum RoomKind {
+    #[default]
+    Normal,
+    Start,
+    Treasure,
+    Shop,
+    Boss,
+}
+
+impl RoomKind {
+    /// Salas sem inimigos na primeira visita.
+    pub fn is_peaceful(self) -> bool {
+        matches!(self, RoomKind::Treasure | RoomKind::Shop)
+    }
+}
+
 /// Componente que representa uma sala com suas conexões em cada direção.
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Room {
     pub id: RoomId,
+    pub position: IVec2, // Célula na grade do andar
+    pub kind: RoomKind,
+    pub template: String, // Nome do modelo em assets/rooms.ron
     pub north: Option<RoomId>,
     pub south: Option<RoomId>,
     pub east: Option<RoomId>,
     pub west: Option<RoomId>,
+    pub saved: Option<RoomState>, // Conteúdo deixado na última visita
+    pub visited: bool,  // O jogador já entrou
+    pub revealed: bool, // Aparece no mapa: visitada ou vizinha de uma visitada
+    pub cleared: bool,  // Todos os inimigos morreram; as portas ficam abertas
 }
 
 impl Room {
+    fn new(id: RoomId, position: IVec2) -> Self {
+        Room {
+            id,
+            position,
+            kind: RoomKind::Normal,
+            template: DEFAULT_TEMPLATE.to_string(),
+            north: None,
+            south: None,
+            east: None,
+            west: None,
+            saved: None,
+            visited: false,
+            revealed: false,
+            cleared: false,
+        }
+    }
+
+    /// Sala vizinha na direção dada, se houver conexão.
+    pub fn neighbor(&self, direction: Direction) -> Option<RoomId> {
+        match direction {
+            Direction::North => self.north,
+            Direction::South => self.south,
+            Direction::East => self.east,
+            Direction::West => self.west,
+        }
+    }
+
     /// Verifica se uma sala já possui conexão em uma determinada direção.
     pub fn has_connection(&self, direction: Direction) -> bool {
+        self.neighbor(direction).is_some()
+    }
+
+    /// Quantas portas a sala tem.
+    pub fn connection_count(&self) -> usize {
+        Direction::ALL.into_iter().filter(|direction| self.has_connection(*direction)).count()
+    }
+
+    fn link(&mut self, direction: Direction, other: RoomId) {
         match direction {
-            Direction::North => self.north.is_some(),
-            Direction::South => self.south.is_some(),
-            Direction::East => self.east.is_some(),
-            Direction::West => self.west.is_some(),
+            Direction::North => self.north = Some(other),
+            Direction::South => self.south = Some(other),
+            Direction::East => self.east = Some(other),
+            Direction::West => self.west = Some(other),
         }
     }
 }
 
 /// Componente que representa uma porta para transição entre salas.
+///
+/// Fica na abertura da parede do lado `direction` e leva à sala `room_id`.
+/// Enquanto `locked`, encostar nela não faz nada.
 #[derive(Component, Debug)]
 pub struct Door {
     pub direction: Direction,
     pub room_id: RoomId,
+    pub locked: bool,
+}
+
+impl Door {
+    /// Cor da porta: marrom quando aberta, vermelho escuro quando trancada.
+    pub fn color(&self) -> Color {
+        if self.locked {
+            Color::rgb(0.45, 0.1, 0.1)
+        } else {
+            Color::rgb(0.55, 0.35, 0.15)
+        }
+    }
+
+    /// Verdadeiro se o jogador, em `position`, encostou na parede dentro da abertura da porta.
+    pub fn is_touching(&self, position: Vec2, bounds: Vec2, door_width: f32) -> bool {
+        let normal = self.direction.as_vec2();
+        let across = position.dot(normal);
+        let along = position.dot(normal.perp());
+        across >= bounds.dot(normal.abs()) - 0.5 && along.abs() <= door_width / 2.0
+    }
+}
+
+/// Centro da abertura na parede do lado `direction`.
+pub fn door_position(direction: Direction, arena: &ArenaSize) -> Vec2 {
+    direction.as_vec2() * Vec2::new(arena.width / 2.0, arena.height / 2.0)
 }
 
 /// Recurso que armazena o grafo de salas e o próximo ID a ser usado para uma nova sala.
-#[derive(Resource)]
+///
+/// Cada sala ocupa uma célula de uma grade 2D; salas vizinhas na grade só se
+/// ligam por uma porta quando há conexão entre elas.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RoomGraph {
     rooms: HashMap<RoomId, Room>,
+    grid: HashMap<IVec2, RoomId>,
     next_id: usize,
 }
 
@@ -99,22 +255,18 @@ impl Default for RoomGraph {
 }
 
 impl RoomGraph {
-    /// Inicializa o grafo de salas com a sala central.
+    /// Inicializa o grafo de salas com a sala central, na origem da grade.
     pub fn new() -> Self {
         let mut graph = RoomGraph {
             rooms: HashMap::new(),
+            grid: HashMap::new(),
             next_id: 0,
         };
 
-        let central_room = Room {
-            id: RoomId::Central,
-            north: None,
-            south: None,
-            east: None,
-            west: None,
-        };
-        graph.rooms.insert(RoomId::Central, central_room);
-        info!("RoomGraph inicializado com sala central!");
+        let mut central = Room::new(RoomId::Central, IVec2::ZERO);
+        central.kind = RoomKind::Start;
+        graph.rooms.insert(RoomId::Central, central);
+        graph.grid.insert(IVec2::ZERO, RoomId::Central);
         graph
     }
 
@@ -123,118 +275,284 @@ impl RoomGraph {
         self.rooms.get(&id)
     }
 
+    pub fn get_room_mut(&mut self, id: RoomId) -> Option<&mut Room> {
+        self.rooms.get_mut(&id)
+    }
+
+    /// Sala que ocupa uma célula da grade.
+    pub fn room_at(&self, position: IVec2) -> Option<RoomId> {
+        self.grid.get(&position).copied()
+    }
+
+    /// IDs de todas as salas, em ordem estável.
+    pub fn room_ids(&self) -> Vec<RoomId> {
+        let mut ids: Vec<RoomId> = self.rooms.keys().copied().collect();
+        ids.sort();
+        ids
+    }
+
+    pub fn rooms(&self) -> impl Iterator<Item = &Room> {
+        self.rooms.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.rooms.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rooms.is_empty()
+    }
+
     /// Adiciona uma nova sala conectada à sala pai na direção especificada.
+    ///
+    /// Falha se a sala pai já tiver porta nessa direção ou se a célula da
+    /// grade já estiver ocupada; para ligar duas salas existentes, use `connect`.
     pub fn add_room(&mut self, parent_id: RoomId, direction: Direction) -> Option<RoomId> {
-        if let Some(parent_room) = self.rooms.get_mut(&parent_id) {
-            // Garantir que não há outra sala na direção especificada.
-            if parent_room.has_connection(direction) {
-                error!(
-                    "A sala {:?} já possui uma conexão na direção {:?}",
-                    parent_id, direction
-                );
-                return None;
+        let Some(parent_room) = self.rooms.get_mut(&parent_id) else {
+            error!("Sala pai não encontrada: {:?}", parent_id);
+            return None;
+        };
+
+        // Garantir que não há outra sala na direção especificada.
+        if parent_room.has_connection(direction) {
+            error!(
+                "A sala {:?} já possui uma conexão na direção {:?}",
+                parent_id, direction
+            );
+            return None;
+        }
+        let position = parent_room.position + direction.as_ivec2();
+        if let Some(occupant) = self.grid.get(&position) {
+            error!("A posição {:?} já está ocupada pela sala {:?}", position, occupant);
+            return None;
+        }
+
+        // Criar novo ID para a sala.
+        let new_id = RoomId::Generated(self.next_id);
+        self.next_id += 1;
+
+        // Criar nova sala e estabelecer as conexões.
+        let mut new_room = Room::new(new_id, position);
+        parent_room.link(direction, new_id);
+        new_room.link(direction.opposite(), parent_id);
+
+        // Adicionar a nova sala ao grafo.
+        self.rooms.insert(new_id, new_room);
+        self.grid.insert(position, new_id);
+        debug!(
+            "Nova sala criada: {:?}, conectada com {:?} pela direção {:?}",
+            new_id, parent_id, direction
+        );
+        Some(new_id)
+    }
+
+    /// Abre uma porta entre a sala e a vizinha que já existe na direção dada.
+    pub fn connect(&mut self, id: RoomId, direction: Direction) -> Option<RoomId> {
+        let room = self.rooms.get(&id)?;
+        if room.has_connection(direction) {
+            return None;
+        }
+        let other = self.room_at(room.position + direction.as_ivec2())?;
+
+        self.rooms.get_mut(&id)?.link(direction, other);
+        self.rooms.get_mut(&other)?.link(direction.opposite(), id);
+        Some(other)
+    }
+
+    /// Marca a sala como visitada e revela as vizinhas ligadas a ela.
+    pub fn visit(&mut self, id: RoomId) {
+        let Some(room) = self.rooms.get_mut(&id) else {
+            return;
+        };
+        room.visited = true;
+        room.revealed = true;
+        let neighbors: Vec<RoomId> = Direction::ALL.into_iter().filter_map(|direction| room.neighbor(direction)).collect();
+        for neighbor in neighbors {
+            if let Some(room) = self.rooms.get_mut(&neighbor) {
+                room.revealed = true;
             }
+        }
+    }
 
-            // Criar novo ID para a sala.
-            let new_id = RoomId::Generated(self.next_id);
-            self.next_id += 1;
-
-            // Criar nova sala.
-            let mut new_room = Room {
-                id: new_id,
-                north: None,
-                south: None,
-                east: None,
-                west: None,
-            };
-
-            // Estabelecer as conexões.
-            match direction {
-                Direction::North => {
-                    parent_room.north = Some(new_id);
-                    new_room.south = Some(parent_id);
-                }
-                Direction::South => {
-                    parent_room.south = Some(new_id);
-                    new_room.north = Some(parent_id);
-                }
-                Direction::East => {
-                    parent_room.east = Some(new_id);
-                    new_room.west = Some(parent_id);
-                }
-                Direction::West => {
-                    parent_room.west = Some(new_id);
-                    new_room.east = Some(parent_id);
+    /// Distância, em portas, de `start` até cada sala alcançável.
+    pub fn distances_from(&self, start: RoomId) -> HashMap<RoomId, usize> {
+        let mut distances = HashMap::new();
+        if !self.rooms.contains_key(&start) {
+            return distances;
+        }
+        distances.insert(start, 0);
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(id) = queue.pop_front() {
+            let distance = distances[&id];
+            for direction in Direction::ALL {
+                if let Some(next) = self.rooms[&id].neighbor(direction)
+                    && !distances.contains_key(&next)
+                {
+                    distances.insert(next, distance + 1);
+                    queue.push_back(next);
                 }
             }
-
-            // Adicionar a nova sala ao grafo.
-            self.rooms.insert(new_id, new_room);
-            info!(
-                "Nova sala criada: {:?}, conectada com {:?} pela direção {:?}",
-                new_id, parent_id, direction
-            );
-            Some(new_id)
-        } else {
-            error!("Sala pai não encontrada: {:?}", parent_id);
-            None
         }
+        distances
     }
 }
 
-/// Sistema para verificar transições de sala.
+/// Sistema para verificar transições de sala: encostar em uma porta leva à
+/// sala vizinha, entrando pela porta do lado oposto.
 fn check_room_transition(
-    _commands: Commands, // Prefixado com `_` para evitar warnings
-    mut current_room: ResMut<CurrentRoom>,
-    player_query: Query<(Entity, &Transform), With<Player>>,
-    room_graph: Res<RoomGraph>,
+    mut pending: ResMut<PendingTransition>,
+    player_query: Query<&Transform, With<Player>>,
+    door_query: Query<&Door>,
+    arena: Res<ArenaSize>,
+    tuning: Res<GameTuning>,
+    mut next_state: ResMut<NextState<AppState>>,
 ) {
-    for (_player_entity, transform) in player_query.iter() {
-        let position = transform.translation;
-
-        if position.x < -100.0 {
-            if let Some(next_room) = room_graph.rooms.get(&current_room.id).and_then(|room| room.west) {
-                current_room.id = next_room;
-                current_room.entered_from = Some(Direction::East);
-                info!("Transição para a sala: {:?}", current_room.id);
-            }
-        }
+    let Ok(transform) = player_query.get_single() else {
+        return;
+    };
+    let position = transform.translation.xy();
+    let bounds = arena.bounds(tuning.wall_thickness, tuning.player_size);
+
+    if let Some(door) = door_query
+        .iter()
+        .find(|door| !door.locked && door.is_touching(position, bounds, tuning.door_width))
+    {
+        pending.0 = Some((door.room_id, door.direction.opposite()));
+        next_state.set(AppState::RoomTransition);
     }
 }
 
-/// Sistema para criar novas salas dinamicamente.
-fn generate_new_rooms(
+/// Sistema que marca a sala atual como limpa quando não resta inimigo nela.
+///
+/// Não dispara a detecção de mudança do `RoomGraph`: as portas já criadas
+/// são abertas por `open_doors`.
+pub fn check_room_cleared(
+    current_room: Res<CurrentRoom>,
     mut room_graph: ResMut<RoomGraph>,
+    enemy_query: Query<&RoomMember, With<Enemy>>,
+    mut cleared_events: EventWriter<RoomCleared>,
+) {
+    let already_cleared = room_graph.get_room(current_room.id).is_none_or(|room| room.cleared);
+    if already_cleared || enemy_query.iter().any(|member| member.0 == current_room.id) {
+        return;
+    }
+
+    if let Some(room) = room_graph.bypass_change_detection().get_room_mut(current_room.id) {
+        room.cleared = true;
+    }
+    info!("Sala {:?} limpa", current_room.id);
+    cleared_events.send(RoomCleared { room: current_room.id });
+}
+
+/// Destranca as portas da sala que acabou de ser limpa.
+fn open_doors(
+    mut cleared_events: EventReader<RoomCleared>,
     current_room: Res<CurrentRoom>,
-    _enemy_query: Query<&Enemy>, // Prefixado com `_` para evitar warnings
+    mut door_query: Query<(&mut Door, &mut Sprite)>,
 ) {
-    let current_room_id = current_room.id;
+    if !cleared_events.read().any(|event| event.room == current_room.id) {
+        return;
+    }
+    for (mut door, mut sprite) in door_query.iter_mut() {
+        door.locked = false;
+        sprite.color = door.color();
+    }
+}
 
-    if room_graph
-        .get_room(current_room_id)
-        .map(|room| room.east.is_none())
-        .unwrap_or(false)
-    {
-        room_graph.add_room(current_room_id, Direction::East);
+/// Troca a sala atual pela da porta atravessada.
+fn enter_pending_room(mut pending: ResMut<PendingTransition>, mut current_room: ResMut<CurrentRoom>) {
+    let Some((room_id, entered_from)) = pending.0.take() else {
+        return;
+    };
+    current_room.previous = Some(current_room.id);
+    current_room.id = room_id;
+    current_room.entered_from = Some(entered_from);
+    info!("Transição para a sala: {:?}", current_room.id);
+}
+
+/// Coloca o jogador logo depois da porta por onde ele entrou.
+pub(crate) fn place_player_at_entrance(
+    current_room: Res<CurrentRoom>,
+    mut player_query: Query<(&mut Transform, &mut Interpolated), With<Player>>,
+    arena: Res<ArenaSize>,
+    tuning: Res<GameTuning>,
+) {
+    let Some(entered_from) = current_room.entered_from else {
+        return;
+    };
+    let bounds = arena.bounds(tuning.wall_thickness, tuning.player_size);
+    // Afastado da parede para não voltar pela mesma porta
+    let inset = tuning.player_size * 2.0;
+    let normal = entered_from.as_vec2();
+    let position = normal * (bounds.dot(normal.abs()) - inset);
+
+    for (mut transform, mut interpolated) in player_query.iter_mut() {
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+        interpolated.snap(transform.translation);
+    }
+}
+
+/// Sistema que encerra a transição de sala e devolve o controle ao jogador.
+fn finish_room_transition(mut next_state: ResMut<NextState<AppState>>) {
+    next_state.set(AppState::Playing);
+}
+
+/// Marca a sala atual como visitada para o mapa.
+///
+/// Não dispara a detecção de mudança do `RoomGraph`, que recriaria paredes e
+/// portas sem necessidade; quem desenha o mapa roda depois deste sistema.
+pub fn explore_current_room(current_room: Res<CurrentRoom>, mut room_graph: ResMut<RoomGraph>) {
+    let explored = room_graph.get_room(current_room.id).is_some_and(|room| room.visited);
+    if !explored {
+        room_graph.bypass_change_detection().visit(current_room.id);
     }
 }
 
-/// Sistema para gerar portas na sala atual.
+/// Condição: a sala atual ou suas conexões mudaram.
+pub fn room_layout_changed(current_room: Res<CurrentRoom>, room_graph: Res<RoomGraph>) -> bool {
+    current_room.is_changed() || room_graph.is_changed()
+}
+
+/// Sistema que recria as portas da sala atual, uma em cada conexão.
 fn spawn_doors(
-    _commands: Commands, // Prefixado com `_` para evitar warnings
+    mut commands: Commands,
     current_room: Res<CurrentRoom>,
     room_graph: Res<RoomGraph>,
-    _door_query: Query<(Entity, &Door)>, // Prefixado com `_` para evitar warnings
-    _meshes: ResMut<Assets<Mesh>>,      // Prefixado com `_` para evitar warnings
-    _materials: ResMut<Assets<ColorMaterial>>, // Prefixado com `_` para evitar warnings
+    door_query: Query<Entity, With<Door>>,
+    arena: Res<ArenaSize>,
+    tuning: Res<GameTuning>,
 ) {
-    if let Some(room) = room_graph.get_room(current_room.id) {
-        if let Some(north_id) = room.north {
-            info!("Porta para o norte na sala {:?}", north_id);
-        }
+    for entity in door_query.iter() {
+        commands.entity(entity).despawn();
+    }
 
-        if let Some(south_id) = room.south {
-            info!("Porta para o sul na sala {:?}", south_id);
-        }
+    let Some(room) = room_graph.get_room(current_room.id) else {
+        return;
+    };
+    for direction in Direction::ALL {
+        let Some(room_id) = room.neighbor(direction) else {
+            continue;
+        };
+        let position = door_position(direction, &arena);
+        let size = match direction {
+            Direction::North | Direction::South => Vec2::new(tuning.door_width, tuning.wall_thickness),
+            Direction::East | Direction::West => Vec2::new(tuning.wall_thickness, tuning.door_width),
+        };
+        let door = Door { direction, room_id, locked: !room.cleared };
+        let color = door.color();
+        commands.spawn((
+            door,
+            SpriteBundle {
+                transform: Transform::from_xyz(position.x, position.y, 0.0),
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(size),
+                    ..default()
+                },
+                ..default()
+            },
+        ));
     }
-}
\ No newline at end of file
+}
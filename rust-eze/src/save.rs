@@ -0,0 +1,290 @@
+use bevy::prelude::*;
+use bevy::ecs::system::RunSystemOnce;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::actions::{Action, ActionState};
+use crate::config::GameTuning;
+use crate::highscores::RunStats;
+use crate::lives::Lives;
+use crate::player::Player;
+use crate::projectiles::powerups::PlayerPowerUpState;
+use crate::rng::GameRng;
+use crate::room_state::{capture_room, restore_entered_room};
+use crate::rooms::{CurrentRoom, RoomGraph, RoomMember};
+use crate::state::AppState;
+use crate::scoring::Score;
+use crate::weapons::{Arsenal, SavedWeapon, Weapon, WeaponRegistry};
+use crate::movement::Velocity;
+use crate::simulation::Interpolated;
+
+/// Versão atual do formato de save.
+pub const SAVE_VERSION: u32 = 1;
+
+/// Arquivo de save do jogo em janela.
+pub const DEFAULT_SAVE_PATH: &str = "save.ron";
+
+/// Plugin que salva a partida em disco e a retoma ao abrir o jogo.
+///
+/// Só faz algo quando existe um `SaveFile`. Salva a cada troca de sala e
+/// com a ação `QuickSave`; no game over, o save é apagado.
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<SaveRequested>()
+            .add_systems(PostStartup, load_on_startup.run_if(resource_exists::<SaveFile>))
+            .add_systems(Update, request_save_on_key.run_if(in_state(AppState::Playing)))
+            .add_systems(OnEnter(AppState::RoomTransition), request_save)
+            .add_systems(OnEnter(AppState::GameOver), delete_save.run_if(resource_exists::<SaveFile>))
+            .add_systems(Last, write_save.run_if(resource_exists::<SaveFile>));
+    }
+}
+
+/// Onde a partida é salva. `resume: false` começa uma partida nova mesmo
+/// que o arquivo exista, mas continua salvando nele.
+#[derive(Resource, Debug, Clone)]
+pub struct SaveFile {
+    pub path: PathBuf,
+    pub resume: bool,
+}
+
+/// Evento que pede para salvar a partida no fim do quadro.
+#[derive(Event, Debug, Clone, Copy, Default)]
+pub struct SaveRequested;
+
+/// Tudo o que é preciso para retomar uma partida.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SaveGame {
+    pub version: u32,
+    pub rng: GameRng,
+    pub floor: RoomGraph, // Com o conteúdo de todas as salas, inclusive a atual
+    pub current_room: CurrentRoom,
+    pub player_position: Vec2,
+    pub player_velocity: Vec2, // Embalo do jogador
+    pub lives: u32,
+    pub health: Option<u32>, // Pontos de vida da vida atual; cheia se `None`
+    pub weapon: Option<SavedWeapon>, // Arma equipada; a inicial se `None`
+    pub holstered: Vec<SavedWeapon>, // Armas guardadas no `Arsenal`
+    pub score: Score,
+    pub powerups: PlayerPowerUpState,
+    pub stats: RunStats,
+}
+
+/// Só a versão, lida antes do resto para escolher a migração.
+#[derive(Deserialize)]
+struct SaveHeader {
+    version: u32,
+}
+
+impl SaveGame {
+    /// Lê o estado atual da partida.
+    pub fn capture(world: &mut World) -> Self {
+        let current_room = world.resource::<CurrentRoom>().clone();
+        let contents = world.run_system_once_with(current_room.id, capture_room);
+        let mut floor = world.resource::<RoomGraph>().clone();
+        if let Some(room) = floor.get_room_mut(current_room.id) {
+            room.saved = Some(contents);
+        }
+
+        let player_position = world
+            .query_filtered::<&Transform, With<Player>>()
+            .get_single(world)
+            .map(|transform| transform.translation.xy())
+            .unwrap_or_default();
+        let player_velocity = world
+            .query_filtered::<&Velocity, With<Player>>()
+            .get_single(world)
+            .map(|velocity| velocity.0)
+            .unwrap_or_default();
+        let (lives, health) = world
+            .query::<&Lives>()
+            .get_single(world)
+            .map(|lives| (lives.count(), Some(lives.health())))
+            .unwrap_or((0, None));
+        let weapon = world
+            .query_filtered::<&Weapon, With<Player>>()
+            .get_single(world)
+            .ok()
+            .map(Weapon::to_saved);
+        let holstered = world
+            .query_filtered::<&Arsenal, With<Player>>()
+            .get_single(world)
+            .map(|arsenal| arsenal.holstered().iter().map(Weapon::to_saved).collect())
+            .unwrap_or_default();
+
+        SaveGame {
+            version: SAVE_VERSION,
+            rng: world.resource::<GameRng>().clone(),
+            floor,
+            current_room,
+            player_position,
+            player_velocity,
+            lives,
+            health,
+            weapon,
+            holstered,
+            score: world.resource::<Score>().clone(),
+            powerups: world.resource::<PlayerPowerUpState>().clone(),
+            stats: world.resource::<RunStats>().clone(),
+        }
+    }
+
+    /// Troca a partida atual pela salva: o conteúdo de todas as salas é
+    /// descartado e a sala atual volta como estava.
+    pub fn apply(self, world: &mut World) {
+        let members: Vec<Entity> = world.query_filtered::<Entity, With<RoomMember>>().iter(world).collect();
+        for entity in members {
+            world.entity_mut(entity).despawn_recursive();
+        }
+
+        world.insert_resource(self.rng);
+        world.insert_resource(self.floor);
+        world.insert_resource(self.current_room);
+        world.insert_resource(self.score);
+        world.insert_resource(self.powerups);
+        world.insert_resource(self.stats);
+
+        for (mut transform, mut interpolated) in world.query_filtered::<(&mut Transform, &mut Interpolated), With<Player>>().iter_mut(world) {
+            transform.translation = self.player_position.extend(transform.translation.z);
+            interpolated.snap(transform.translation);
+        }
+        for mut velocity in world.query_filtered::<&mut Velocity, With<Player>>().iter_mut(world) {
+            velocity.0 = self.player_velocity;
+        }
+        let max_health = world.resource::<GameTuning>().player_health;
+        for mut lives in world.query::<&mut Lives>().iter_mut(world) {
+            *lives = Lives::new(self.lives, max_health);
+            if let Some(health) = self.health {
+                lives.set_health(health);
+            }
+        }
+
+        // Armas que sumiram do `weapons.ron` desde o save ficam de fora
+        let registry = world.resource::<WeaponRegistry>();
+        let restore = |saved: &SavedWeapon| registry.get(&saved.name).map(|kind| Weapon::from_saved(kind.clone(), saved));
+        let equipped = self.weapon.as_ref().and_then(restore);
+        let holstered: Vec<Weapon> = self.holstered.iter().filter_map(restore).collect();
+        for (mut weapon, mut arsenal) in world.query_filtered::<(&mut Weapon, &mut Arsenal), With<Player>>().iter_mut(world) {
+            if let Some(equipped) = &equipped {
+                *weapon = equipped.clone();
+            }
+            arsenal.clear();
+            for holstered in &holstered {
+                arsenal.insert(holstered.clone());
+            }
+        }
+
+        world.run_system_once(restore_entered_room);
+    }
+
+    pub fn load(path: &Path) -> Result<Self, SaveError> {
+        let text = std::fs::read_to_string(path).map_err(SaveError::Io)?;
+        Self::from_ron(&text)
+    }
+
+    /// Interpreta um save em RON, migrando versões antigas para a atual.
+    pub fn from_ron(text: &str) -> Result<Self, SaveError> {
+        let header: SaveHeader = ron::from_str(text).map_err(SaveError::Parse)?;
+        migrate(header.version, text)
+    }
+
+    pub fn to_ron(&self) -> Result<String, SaveError> {
+        ron::to_string(self).map_err(SaveError::Serialize)
+    }
+
+    /// Grava num arquivo temporário e o renomeia por cima do save, para uma
+    /// queda no meio da gravação não deixar um save pela metade.
+    pub fn save(&self, path: &Path) -> Result<(), SaveError> {
+        let temp = path.with_extension("ron.tmp");
+        std::fs::write(&temp, self.to_ron()?).map_err(SaveError::Io)?;
+        std::fs::rename(&temp, path).map_err(SaveError::Io)
+    }
+}
+
+/// Lê um save da versão dada no formato atual.
+///
+/// Quando o formato mudar, a versão antiga ganha um tipo próprio com o
+/// formato de então e um braço aqui que o lê e converte para o `SaveGame`
+/// atual.
+fn migrate(version: u32, text: &str) -> Result<SaveGame, SaveError> {
+    match version {
+        SAVE_VERSION => ron::from_str(text).map_err(SaveError::Parse),
+        _ => Err(SaveError::UnsupportedVersion(version)),
+    }
+}
+
+/// Erros ao ler ou gravar um save.
+#[derive(Debug)]
+pub enum SaveError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    Serialize(ron::Error),
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(err) => write!(f, "erro de arquivo: {err}"),
+            SaveError::Parse(err) => write!(f, "save inválido: {err}"),
+            SaveError::Serialize(err) => write!(f, "não foi possível gerar o save: {err}"),
+            SaveError::UnsupportedVersion(version) => {
+                write!(f, "versão de save {version} não suportada (atual {SAVE_VERSION})")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+/// Retoma a partida salva, se houver, depois que o início montou uma nova.
+fn load_on_startup(world: &mut World) {
+    let save_file = world.resource::<SaveFile>().clone();
+    if !save_file.resume || !save_file.path.exists() {
+        return;
+    }
+
+    match SaveGame::load(&save_file.path) {
+        Ok(save) => {
+            info!("Partida retomada de {:?}, na sala {:?}", save_file.path, save.current_room.id);
+            save.apply(world);
+        }
+        Err(err) => error!("Save em {:?} ignorado: {}", save_file.path, err),
+    }
+}
+
+fn request_save_on_key(actions: Res<ActionState>, mut save_events: EventWriter<SaveRequested>) {
+    if actions.just_pressed(Action::QuickSave) {
+        save_events.send(SaveRequested);
+    }
+}
+
+fn request_save(mut save_events: EventWriter<SaveRequested>) {
+    save_events.send(SaveRequested);
+}
+
+/// Grava a partida se alguém pediu neste quadro.
+fn write_save(world: &mut World) {
+    let requested = world.resource_mut::<Events<SaveRequested>>().drain().count() > 0;
+    if !requested {
+        return;
+    }
+
+    let path = world.resource::<SaveFile>().path.clone();
+    match SaveGame::capture(world).save(&path) {
+        Ok(()) => info!("Partida salva em {:?}", path),
+        Err(err) => error!("Não foi possível salvar a partida em {:?}: {}", path, err),
+    }
+}
+
+/// Uma partida perdida não pode ser retomada.
+fn delete_save(save_file: Res<SaveFile>) {
+    if save_file.path.exists()
+        && let Err(err) = std::fs::remove_file(&save_file.path)
+    {
+        error!("Não foi possível apagar o save em {:?}: {}", save_file.path, err);
+    }
+}
@@ -0,0 +1,293 @@
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use serde::{Deserialize, Serialize};
+use crate::config::{GameTuning, ScoringTuning};
+use crate::enemies::EnemyKilled;
+use crate::enemy_kinds::EnemyRegistry;
+use crate::game::{check_collision, PlayerDamaged};
+use crate::player::Player;
+use crate::projectiles::powerups::{collect_powerups, PowerUpCollected};
+use crate::rooms::{check_room_cleared, CurrentRoom, RoomCleared, RoomId, RoomMember};
+use crate::state::AppState;
+
+/// Quanto tempo os pontos ficam subindo na cena (s).
+const POPUP_LIFETIME: f32 = 0.8;
+/// Velocidade de subida dos pontos (px/s).
+const POPUP_SPEED: f32 = 60.0;
+
+/// Plugin da pontuação: tempo vivo, eventos da partida e combo.
+pub struct ScoringPlugin;
+
+impl Plugin for ScoringPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Score>()
+            .init_resource::<RoomScoring>()
+            .add_systems(FixedUpdate, (
+                score_survival,
+                update_combo,
+                score_events
+                    .after(update_combo)
+                    .after(check_collision)
+                    .after(collect_powerups)
+                    .after(check_room_cleared),
+            ).run_if(in_state(AppState::Playing)))
+            .add_systems(Update, float_popups)
+            .add_systems(OnExit(AppState::GameOver), reset_score);
+    }
+}
+
+/// Pontuação da partida, com a origem de cada ponto.
+#[derive(Resource, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Score {
+    pub value: u32,
+    #[serde(default)]
+    pub breakdown: ScoreBreakdown,
+    #[serde(default)]
+    pub combo: Combo,
+    // Fração de ponto acumulada por tempo de sobrevivência
+    survival_carry: f32,
+}
+
+impl Score {
+    /// Soma `base` pontos vindos de `source`, multiplicados pelo combo, e
+    /// devolve quanto entrou. O que o combo acrescenta vai para `breakdown.combo`.
+    pub fn award(&mut self, source: ScoreSource, base: u32) -> u32 {
+        let total = (base as f32 * self.combo.multiplier).round() as u32;
+        *self.breakdown.get_mut(source) += base;
+        self.breakdown.combo += total.saturating_sub(base);
+        self.value += total;
+        total
+    }
+}
+
+/// De onde vêm os pontos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoreSource {
+    Survival,
+    Kill,
+    RoomClear,
+    NoDamageRoom,
+    Pickup,
+}
+
+/// Pontos por origem, mostrados no game over. A soma dá `Score::value`.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreBreakdown {
+    pub survival: u32,
+    pub kills: u32,
+    pub rooms: u32,
+    pub no_damage: u32,
+    pub pickups: u32,
+    pub combo: u32, // Extra dado pelo multiplicador
+}
+
+impl ScoreBreakdown {
+    fn get_mut(&mut self, source: ScoreSource) -> &mut u32 {
+        match source {
+            ScoreSource::Survival => &mut self.survival,
+            ScoreSource::Kill => &mut self.kills,
+            ScoreSource::RoomClear => &mut self.rooms,
+            ScoreSource::NoDamageRoom => &mut self.no_damage,
+            ScoreSource::Pickup => &mut self.pickups,
+        }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.survival + self.kills + self.rooms + self.no_damage + self.pickups + self.combo
+    }
+
+    /// Linhas do resumo, com o nome mostrado na tela.
+    pub fn lines(&self) -> [(&'static str, u32); 6] {
+        [
+            ("Survival", self.survival),
+            ("Kills", self.kills),
+            ("Rooms cleared", self.rooms),
+            ("No-damage rooms", self.no_damage),
+            ("Pickups", self.pickups),
+            ("Combo bonus", self.combo),
+        ]
+    }
+}
+
+/// Multiplicador de combo: sobe a cada morte e cai quando o jogador passa
+/// um tempo sem matar ou é atingido.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Combo {
+    pub multiplier: f32,
+    pub idle: f32, // Segundos desde a última morte
+}
+
+impl Default for Combo {
+    fn default() -> Self {
+        Combo { multiplier: 1.0, idle: 0.0 }
+    }
+}
+
+impl Combo {
+    fn hit(&mut self, tuning: &ScoringTuning) {
+        self.multiplier = (self.multiplier + tuning.combo_step).min(tuning.combo_max);
+        self.idle = 0.0;
+    }
+
+    fn tick(&mut self, delta: f32, tuning: &ScoringTuning) {
+        self.idle += delta;
+        if self.idle > tuning.combo_grace {
+            self.multiplier = (self.multiplier - tuning.combo_decay * delta).max(1.0);
+        }
+    }
+}
+
+/// O que aconteceu na sala atual, para os bônus de sala limpa.
+#[derive(Resource, Default)]
+pub struct RoomScoring {
+    room: Option<RoomId>,
+    kills: u32,
+    damaged: bool,
+}
+
+/// Número de pontos que sobe e some no lugar do evento.
+#[derive(Component)]
+pub struct ScorePopup {
+    timer: Timer,
+}
+
+fn reset_score(mut score: ResMut<Score>, mut room_scoring: ResMut<RoomScoring>) {
+    *score = Score::default();
+    *room_scoring = RoomScoring::default();
+}
+
+/// Dá pontos por segundo vivo, guardando a fração entre os passos. O combo
+/// não vale para eles.
+fn score_survival(time: Res<Time>, tuning: Res<GameTuning>, mut score: ResMut<Score>) {
+    score.survival_carry += time.delta_seconds() * tuning.scoring.survival_per_second;
+    let whole = score.survival_carry.floor();
+    score.survival_carry -= whole;
+    score.value += whole as u32;
+    score.breakdown.survival += whole as u32;
+}
+
+fn update_combo(time: Res<Time>, tuning: Res<GameTuning>, mut score: ResMut<Score>) {
+    score.combo.tick(time.delta_seconds(), &tuning.scoring);
+}
+
+/// Eventos do passo que valem pontos ou quebram o combo.
+#[derive(SystemParam)]
+struct ScoredEvents<'w, 's> {
+    killed: EventReader<'w, 's, EnemyKilled>,
+    collected: EventReader<'w, 's, PowerUpCollected>,
+    cleared: EventReader<'w, 's, RoomCleared>,
+    damaged: EventReader<'w, 's, PlayerDamaged>,
+}
+
+/// Quanto vale cada evento: a pontuação dos ajustes e o peso de cada tipo
+/// de inimigo.
+#[derive(SystemParam)]
+struct ScoreValues<'w> {
+    tuning: Res<'w, GameTuning>,
+    registry: Res<'w, EnemyRegistry>,
+}
+
+impl ScoreValues<'_> {
+    /// Pontos de uma morte antes do combo. Tipos desconhecidos pesam 1.
+    fn kill(&self, kind: &str) -> u32 {
+        let weight = self.registry.get(kind).map_or(1.0, |kind| kind.score_weight);
+        (self.tuning.kill_score as f32 * weight).round() as u32
+    }
+}
+
+/// Pontua os eventos do passo: mortes pesadas pelo tipo do inimigo,
+/// power-ups e salas limpas, com bônus se o jogador não foi atingido nelas.
+/// Salas que já estavam vazias não valem pontos.
+fn score_events(
+    mut commands: Commands,
+    mut events: ScoredEvents,
+    mut score: ResMut<Score>,
+    mut room_scoring: ResMut<RoomScoring>,
+    current_room: Res<CurrentRoom>,
+    values: ScoreValues,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    if room_scoring.room != Some(current_room.id) {
+        *room_scoring = RoomScoring {
+            room: Some(current_room.id),
+            ..default()
+        };
+    }
+    if events.damaged.read().count() > 0 {
+        room_scoring.damaged = true;
+        score.combo = Combo::default();
+    }
+
+    let scoring = &values.tuning.scoring;
+    for event in events.killed.read() {
+        let points = score.award(ScoreSource::Kill, values.kill(&event.kind));
+        score.combo.hit(scoring);
+        if event.room == current_room.id {
+            room_scoring.kills += 1;
+        }
+        spawn_popup(&mut commands, points, event.position, event.room, Color::YELLOW);
+    }
+
+    for event in events.collected.read() {
+        let points = score.award(ScoreSource::Pickup, scoring.pickup);
+        spawn_popup(&mut commands, points, event.position, current_room.id, Color::CYAN);
+    }
+
+    let player_position = player_query.get_single().map(|transform| transform.translation.xy()).unwrap_or_default();
+    for event in events.cleared.read() {
+        if event.room != current_room.id || room_scoring.kills == 0 {
+            continue;
+        }
+        let points = score.award(ScoreSource::RoomClear, scoring.room_clear);
+        spawn_popup(&mut commands, points, player_position + Vec2::Y * 30.0, event.room, Color::GOLD);
+        if !room_scoring.damaged {
+            let points = score.award(ScoreSource::NoDamageRoom, scoring.no_damage_room);
+            spawn_popup(&mut commands, points, player_position + Vec2::Y * 55.0, event.room, Color::GREEN);
+        }
+    }
+}
+
+fn spawn_popup(commands: &mut Commands, points: u32, position: Vec2, room: RoomId, color: Color) {
+    if points == 0 {
+        return;
+    }
+    commands.spawn((
+        ScorePopup {
+            timer: Timer::from_seconds(POPUP_LIFETIME, TimerMode::Once),
+        },
+        RoomMember(room),
+        Text2dBundle {
+            text: Text::from_section(
+                format!("+{points}"),
+                TextStyle {
+                    font_size: 20.0,
+                    color,
+                    ..default()
+                },
+            ),
+            transform: Transform::from_xyz(position.x, position.y, 10.0),
+            ..default()
+        },
+    ));
+}
+
+/// Faz os pontos subirem e sumirem aos poucos.
+fn float_popups(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut popup_query: Query<(Entity, &mut ScorePopup, &mut Transform, &mut Text)>,
+) {
+    for (entity, mut popup, mut transform, mut text) in popup_query.iter_mut() {
+        popup.timer.tick(time.delta());
+        if popup.timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        transform.translation.y += POPUP_SPEED * time.delta_seconds();
+        let alpha = popup.timer.fraction_remaining();
+        for section in text.sections.iter_mut() {
+            section.style.color.set_a(alpha);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use bevy::prelude::*;
+use bevy::transform::TransformSystem;
+use crate::config::{load_tuning, GameTuning};
+
+/// Plugin do passo fixo da simulação.
+///
+/// A lógica de jogo roda em `FixedUpdate` na frequência de `tick_rate`. Para
+/// o movimento continuar suave, as entidades com `Interpolated` são
+/// desenhadas entre os dois últimos passos e voltam à posição simulada em
+/// `Last`, antes do próximo quadro.
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(PreStartup, apply_tick_rate.after(load_tuning))
+            .add_systems(Update, apply_tick_rate.run_if(resource_changed::<GameTuning>))
+            .add_systems(FixedFirst, store_previous_position)
+            .add_systems(PostUpdate, interpolate_transforms.before(TransformSystem::TransformPropagate))
+            .add_systems(Last, restore_simulated_position);
+    }
+}
+
+/// Componente para entidades que se movem na simulação e devem ser
+/// interpoladas na tela.
+#[derive(Component, Default, Debug, Clone, Copy)]
+pub struct Interpolated {
+    previous: Option<Vec3>,
+    current: Vec3,
+}
+
+impl Interpolated {
+    /// Para teletransportes: a entidade aparece direto em `translation`, sem
+    /// deslizar desde a posição do passo anterior.
+    pub fn snap(&mut self, translation: Vec3) {
+        self.previous = Some(translation);
+        self.current = translation;
+    }
+}
+
+fn apply_tick_rate(tuning: Res<GameTuning>, mut fixed_time: ResMut<Time<Fixed>>) {
+    let timestep = 1.0 / tuning.tick_rate;
+    if fixed_time.timestep().as_secs_f64() != timestep {
+        fixed_time.set_timestep_hz(tuning.tick_rate);
+    }
+}
+
+/// Guarda a posição no início de cada passo fixo.
+fn store_previous_position(mut query: Query<(&Transform, &mut Interpolated)>) {
+    for (transform, mut interpolated) in query.iter_mut() {
+        interpolated.previous = Some(transform.translation);
+    }
+}
+
+/// Troca a posição simulada pela posição interpolada só para o desenho.
+fn interpolate_transforms(
+    fixed_time: Res<Time<Fixed>>,
+    mut query: Query<(&mut Transform, &mut Interpolated)>,
+) {
+    let alpha = fixed_time.overstep_fraction();
+    for (mut transform, mut interpolated) in query.iter_mut() {
+        interpolated.current = transform.translation;
+        if let Some(previous) = interpolated.previous {
+            transform.translation = previous.lerp(interpolated.current, alpha);
+        }
+    }
+}
+
+/// Devolve a posição simulada para que a lógica nunca veja a interpolada.
+fn restore_simulated_position(mut query: Query<(&mut Transform, &Interpolated)>) {
+    for (mut transform, interpolated) in query.iter_mut() {
+        transform.bypass_change_detection().translation = interpolated.current;
+    }
+}
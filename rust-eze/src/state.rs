@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+use crate::actions::{Action, ActionState};
+
+/// Plugin com a máquina de estados do jogo e as ações que mudam de estado.
+pub struct StatePlugin;
+
+impl Plugin for StatePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_state::<AppState>()
+            .add_systems(Update, start_game.run_if(in_state(AppState::MainMenu)))
+            .add_systems(Update, pause_game.run_if(in_state(AppState::Playing)))
+            .add_systems(Update, resume_game.run_if(in_state(AppState::Paused)))
+            .add_systems(Update, restart_game.run_if(in_state(AppState::GameOver)));
+    }
+}
+
+/// Estados do jogo. Os sistemas de gameplay só rodam em `Playing`.
+#[derive(States, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AppState {
+    #[default]
+    MainMenu,
+    Playing,
+    Paused,
+    RoomTransition,
+    GameOver,
+    Options,
+}
+
+/// Sai do menu principal e começa a partida com Confirm.
+fn start_game(actions: Res<ActionState>, mut next_state: ResMut<NextState<AppState>>) {
+    if actions.just_pressed(Action::Confirm) {
+        next_state.set(AppState::Playing);
+    }
+}
+
+/// Pausa a partida com Pause.
+fn pause_game(actions: Res<ActionState>, mut next_state: ResMut<NextState<AppState>>) {
+    if actions.just_pressed(Action::Pause) {
+        next_state.set(AppState::Paused);
+    }
+}
+
+/// Volta para a partida com Pause.
+fn resume_game(actions: Res<ActionState>, mut next_state: ResMut<NextState<AppState>>) {
+    if actions.just_pressed(Action::Pause) {
+        next_state.set(AppState::Playing);
+    }
+}
+
+/// Recomeça após o game over com Confirm. O reset da partida roda em
+/// `OnExit(AppState::GameOver)` em cada plugin. Enquanto o jogador digita o
+/// nome de um recorde, o Confirm é dele.
+pub(crate) fn restart_game(actions: Res<ActionState>, mut next_state: ResMut<NextState<AppState>>) {
+    if actions.just_pressed(Action::Confirm) {
+        next_state.set(AppState::Playing);
+    }
+}
+
+/// Remove todas as entidades com o componente `T`. Útil em `OnExit`.
+pub fn despawn_with<T: Component>(mut commands: Commands, query: Query<Entity, With<T>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,226 @@
+use bevy::prelude::*;
+use bevy::sprite::MaterialMesh2dBundle;
+use serde::{Deserialize, Serialize};
+use crate::combat::{DamageEvent, DamageSet, HitSet};
+use crate::config::GameTuning;
+use crate::enemies::Enemy;
+use crate::state::AppState;
+
+/// Plugin dos efeitos de estado dos projéteis elementais.
+///
+/// Os projéteis enviam `ElementHit` no `HitSet`. Fogo queima com dano ao
+/// longo do tempo, gelo desacelera, elétrico salta para inimigos próximos e
+/// explosivo atinge todos dentro do raio. Inimigos imunes a um elemento
+/// ignoram o efeito dele.
+pub struct StatusPlugin;
+
+impl Plugin for StatusPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<ElementHit>()
+            .add_systems(FixedUpdate, (
+                (apply_element_hits, tick_burning, tick_chilled)
+                    .after(HitSet)
+                    .before(DamageSet),
+                fade_blasts,
+            ).run_if(in_state(AppState::Playing)));
+    }
+}
+
+/// Elemento de um projétil.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Element {
+    Standard,
+    Fire,
+    Ice,
+    Electric,
+    Explosive,
+}
+
+impl Element {
+    /// Cor dos projéteis e do texto do elemento.
+    pub fn color(self) -> Color {
+        match self {
+            Element::Standard => Color::WHITE,
+            Element::Fire => Color::ORANGE,
+            Element::Ice => Color::CYAN,
+            Element::Electric => Color::YELLOW,
+            Element::Explosive => Color::PURPLE,
+        }
+    }
+}
+
+/// Enviado quando um projétil elemental acerta um inimigo.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ElementHit {
+    pub target: Entity,
+    pub element: Element,
+    pub position: Vec2,
+}
+
+/// Queimadura: cada acúmulo causa dano por segundo. Novos acertos somam um
+/// acúmulo, até o limite, e renovam a duração.
+#[derive(Component, Debug)]
+pub struct Burning {
+    pub stacks: u32,
+    pub timer: Timer,
+}
+
+/// Congelamento: o inimigo anda mais devagar. Novos acertos só renovam a duração.
+#[derive(Component, Debug)]
+pub struct Chilled {
+    pub timer: Timer,
+}
+
+/// Círculo que mostra a área de uma explosão enquanto some.
+#[derive(Component)]
+pub struct Blast {
+    pub timer: Timer,
+}
+
+/// Inimigo que um efeito elemental pode afetar, com os efeitos que já tem.
+type AffectedEnemy = (
+    Entity,
+    &'static Transform,
+    &'static Enemy,
+    Option<&'static mut Burning>,
+    Option<&'static mut Chilled>,
+);
+
+fn apply_element_hits(
+    mut commands: Commands,
+    mut hits: EventReader<ElementHit>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut enemy_query: Query<AffectedEnemy>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    tuning: Res<GameTuning>,
+) {
+    let status = &tuning.status;
+
+    for hit in hits.read() {
+        match hit.element {
+            Element::Standard => {}
+            Element::Fire => {
+                let Ok((_, _, enemy, burning, _)) = enemy_query.get_mut(hit.target) else {
+                    continue;
+                };
+                if enemy.is_immune(Element::Fire) {
+                    continue;
+                }
+                match burning {
+                    Some(mut burning) => {
+                        burning.stacks = (burning.stacks + 1).min(status.burn_max_stacks);
+                        burning.timer.reset();
+                    }
+                    None => {
+                        commands.entity(hit.target).insert(Burning {
+                            stacks: 1,
+                            timer: Timer::from_seconds(status.burn_duration, TimerMode::Once),
+                        });
+                    }
+                }
+            }
+            Element::Ice => {
+                let Ok((_, _, enemy, _, chilled)) = enemy_query.get_mut(hit.target) else {
+                    continue;
+                };
+                if enemy.is_immune(Element::Ice) {
+                    continue;
+                }
+                match chilled {
+                    Some(mut chilled) => chilled.timer.reset(),
+                    None => {
+                        commands.entity(hit.target).insert(Chilled {
+                            timer: Timer::from_seconds(status.chill_duration, TimerMode::Once),
+                        });
+                    }
+                }
+            }
+            Element::Electric => {
+                // Salta para os inimigos mais próximos do alvo, dentro do alcance
+                let Ok((_, target_transform, _, _, _)) = enemy_query.get(hit.target) else {
+                    continue;
+                };
+                let origin = target_transform.translation.xy();
+                let mut nearby: Vec<(f32, Entity)> = enemy_query
+                    .iter()
+                    .filter(|(entity, _, enemy, _, _)| *entity != hit.target && !enemy.is_immune(Element::Electric))
+                    .map(|(entity, transform, _, _, _)| (transform.translation.xy().distance(origin), entity))
+                    .filter(|(distance, _)| *distance <= status.chain_radius)
+                    .collect();
+                nearby.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+                for (_, entity) in nearby.into_iter().take(status.chain_targets as usize) {
+                    damage_events.send(DamageEvent::hit(entity, status.chain_damage));
+                }
+            }
+            Element::Explosive => {
+                for (entity, transform, enemy, _, _) in enemy_query.iter() {
+                    if enemy.is_immune(Element::Explosive) {
+                        continue;
+                    }
+                    if transform.translation.xy().distance(hit.position) <= status.blast_radius {
+                        damage_events.send(DamageEvent::hit(entity, status.blast_damage));
+                    }
+                }
+
+                commands.spawn((
+                    Blast {
+                        timer: Timer::from_seconds(0.25, TimerMode::Once),
+                    },
+                    MaterialMesh2dBundle {
+                        mesh: meshes.add(Circle::new(status.blast_radius)).into(),
+                        material: materials.add(ColorMaterial::from(Color::rgba(1.0, 0.5, 0.0, 0.5))),
+                        transform: Transform::from_xyz(hit.position.x, hit.position.y, -0.5),
+                        ..default()
+                    },
+                ));
+            }
+        }
+    }
+}
+
+fn tick_burning(
+    mut commands: Commands,
+    mut burning_query: Query<(Entity, &mut Burning)>,
+    mut damage_events: EventWriter<DamageEvent>,
+    tuning: Res<GameTuning>,
+    time: Res<Time>,
+) {
+    for (entity, mut burning) in burning_query.iter_mut() {
+        let amount = tuning.status.burn_damage_per_second * burning.stacks as f32 * time.delta_seconds();
+        damage_events.send(DamageEvent::status(entity, amount));
+
+        if burning.timer.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<Burning>();
+        }
+    }
+}
+
+fn tick_chilled(
+    mut commands: Commands,
+    mut chilled_query: Query<(Entity, &mut Chilled)>,
+    time: Res<Time>,
+) {
+    for (entity, mut chilled) in chilled_query.iter_mut() {
+        if chilled.timer.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<Chilled>();
+        }
+    }
+}
+
+fn fade_blasts(
+    mut commands: Commands,
+    mut blast_query: Query<(Entity, &mut Blast, &Handle<ColorMaterial>)>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    time: Res<Time>,
+) {
+    for (entity, mut blast, material) in blast_query.iter_mut() {
+        blast.timer.tick(time.delta());
+        if blast.timer.finished() {
+            commands.entity(entity).despawn();
+        } else if let Some(material) = materials.get_mut(material) {
+            material.color.set_a(0.5 * blast.timer.fraction_remaining());
+        }
+    }
+}
@@ -1,5 +1,23 @@
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
+use crate::actions::{Action, ActionState};
+use crate::aim::SelectedElement;
+use crate::arena::ArenaSize;
+use crate::game::PlayerDamaged;
+use crate::player::Player;
+use crate::rooms::{explore_current_room, room_layout_changed, CurrentRoom, Room, RoomGraph, RoomId, RoomKind};
+use crate::scoring::Score;
+use crate::state::{despawn_with, AppState};
+use crate::status::Element;
+use crate::weapons::Weapon;
+
+/// Tamanho de cada sala no minimapa e no mapa em tela cheia, em pixels.
+const MINIMAP_CELL: Vec2 = Vec2::new(18.0, 12.0);
+const FULL_MAP_CELL: Vec2 = Vec2::new(48.0, 32.0);
+/// Tamanho do quadro do minimapa, no canto superior direito.
+const MINIMAP_SIZE: Vec2 = Vec2::new(160.0, 110.0);
+/// Duração e opacidade inicial do véu vermelho ao levar dano.
+const DAMAGE_OVERLAY_TIME: f32 = 0.3;
+const DAMAGE_OVERLAY_ALPHA: f32 = 0.35;
 
 #[derive(Component)]
 pub struct ScoreText;
@@ -7,12 +25,47 @@ pub struct ScoreText;
 #[derive(Component)]
 pub struct LivesText;
 
+/// Elemento escolhido para os disparos.
+#[derive(Component)]
+pub struct ElementText;
+
+/// Arma equipada e a munição dela.
+#[derive(Component)]
+pub struct WeaponText;
+
 #[derive(Component)]
 pub struct GameOverText;
 
+/// Véu vermelho mostrado quando o jogador leva dano.
+#[derive(Component)]
+pub struct DamageOverlay {
+    timer: Timer,
+}
+
+#[derive(Component)]
+pub struct MainMenuText;
+
+#[derive(Component)]
+pub struct PausedText;
+
+/// Raiz do minimapa.
+#[derive(Component)]
+pub struct Minimap;
+
+/// Raiz do mapa em tela cheia.
+#[derive(Component)]
+pub struct FullMap;
+
+/// Uma sala desenhada no minimapa ou no mapa em tela cheia.
+#[derive(Component, Debug)]
+pub struct MapCell {
+    pub room: RoomId,
+}
+
+/// Se o mapa em tela cheia está aberto.
 #[derive(Resource, Default)]
-pub struct Score {
-    pub value: u32,
+pub struct MapView {
+    pub full_map: bool,
 }
 
 pub struct UiPlugin;
@@ -20,17 +73,26 @@ pub struct UiPlugin;
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
         app
-            .init_resource::<Score>()
+            .init_resource::<MapView>()
             .add_systems(Startup, setup_ui)
-            .add_systems(Update, (update_score, update_lives_text));
+            .add_systems(Update, (update_score_text, update_lives_text, show_damage_overlay, fade_damage_overlay))
+            .add_systems(Update, update_element_text.run_if(resource_changed::<SelectedElement>))
+            .add_systems(Update, update_weapon_text)
+            .add_systems(Update, toggle_full_map.run_if(in_state(AppState::Playing).or_else(in_state(AppState::Paused))))
+            .add_systems(Update, draw_map
+                .after(explore_current_room)
+                .after(toggle_full_map)
+                .run_if(room_layout_changed.or_else(resource_changed::<MapView>)))
+            .add_systems(OnEnter(AppState::MainMenu), show_main_menu)
+            .add_systems(OnExit(AppState::MainMenu), despawn_with::<MainMenuText>)
+            .add_systems(OnEnter(AppState::Paused), show_paused)
+            .add_systems(OnExit(AppState::Paused), despawn_with::<PausedText>)
+            .add_systems(OnEnter(AppState::GameOver), show_game_over)
+            .add_systems(OnExit(AppState::GameOver), despawn_with::<GameOverText>);
     }
 }
 
-fn setup_ui(mut commands: Commands, window_query: Query<&Window, With<PrimaryWindow>>) {
-    let window = window_query.single();
-    let window_width = window.width();
-    let window_height = window.height();
-
+fn setup_ui(mut commands: Commands) {
     // Texto do título
     commands.spawn(
         TextBundle::from_section(
@@ -69,6 +131,15 @@ fn setup_ui(mut commands: Commands, window_query: Query<&Window, With<PrimaryWin
                     ..default()
                 },
             ),
+            // Multiplicador de combo, vazio quando não há combo
+            TextSection::new(
+                "",
+                TextStyle {
+                    font_size: 30.0,
+                    color: Color::ORANGE,
+                    ..default()
+                },
+            ),
         ])
         .with_style(Style {
             position_type: PositionType::Absolute,
@@ -78,54 +149,147 @@ fn setup_ui(mut commands: Commands, window_query: Query<&Window, With<PrimaryWin
         }),
     ));
 
-    // Texto das vidas (corações)
+    // Texto das vidas (corações) e dos pontos de vida da vida atual
     commands.spawn((
         LivesText,
+        TextBundle::from_sections([
+            TextSection::new(
+                "❤️❤️❤️",
+                TextStyle {
+                    font_size: 30.0,
+                    color: Color::RED,
+                    ..default()
+                },
+            ),
+            TextSection::new(
+                "",
+                TextStyle {
+                    font_size: 30.0,
+                    color: Color::PINK,
+                    ..default()
+                },
+            ),
+        ])
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(90.0),
+            left: Val::Px(10.0),
+            ..default()
+        }),
+    ));
+
+    // Texto do elemento escolhido
+    commands.spawn((
+        ElementText,
         TextBundle::from_section(
-            "❤️❤️❤️",
+            "",
             TextStyle {
-                font_size: 30.0,
-                color: Color::RED,
+                font_size: 24.0,
+                color: Color::WHITE,
                 ..default()
             },
         )
         .with_style(Style {
             position_type: PositionType::Absolute,
-            top: Val::Px(90.0),
+            top: Val::Px(130.0),
             left: Val::Px(10.0),
             ..default()
         }),
     ));
 
-    // Texto de Game Over (centralizado)
+    // Texto da arma e da munição
     commands.spawn((
-        GameOverText,
+        WeaponText,
         TextBundle::from_section(
-            "Game Over\nPress Enter to Restart",
+            "",
             TextStyle {
-                font_size: 50.0,
-                color: Color::RED,
+                font_size: 24.0,
+                color: Color::WHITE,
                 ..default()
             },
         )
         .with_style(Style {
             position_type: PositionType::Absolute,
-            top: Val::Px(window_height / 2.0 - 50.0),
-            left: Val::Px(window_width / 2.0 - 200.0),
-            display: Display::None,
+            top: Val::Px(160.0),
+            left: Val::Px(10.0),
             ..default()
         }),
     ));
 }
 
-fn update_score(
-    time: Res<Time>,
-    mut score: ResMut<Score>,
-    mut query: Query<&mut Text, With<ScoreText>>,
+/// Cria um texto centralizado na arena com o marcador informado.
+fn spawn_centered_text(
+    commands: &mut Commands,
+    marker: impl Component,
+    text: &str,
+    color: Color,
+    arena: &ArenaSize,
 ) {
-    score.value += (time.delta_seconds() * 10.0) as u32;
+    commands.spawn((
+        marker,
+        TextBundle::from_section(
+            text,
+            TextStyle {
+                font_size: 50.0,
+                color,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(arena.height / 2.0 - 50.0),
+            left: Val::Px(arena.width / 2.0 - 200.0),
+            ..default()
+        }),
+    ));
+}
+
+fn show_main_menu(mut commands: Commands, arena: Res<ArenaSize>) {
+    spawn_centered_text(&mut commands, MainMenuText, "Rust-eze\nPress Enter to Start\nO: Options", Color::WHITE, &arena);
+}
+
+fn show_paused(mut commands: Commands, arena: Res<ArenaSize>) {
+    spawn_centered_text(&mut commands, PausedText, "Paused\nPress Esc to Resume\nO: Options", Color::WHITE, &arena);
+}
+
+/// Mostra o game over com o resumo da pontuação acima dele.
+fn show_game_over(mut commands: Commands, arena: Res<ArenaSize>, score: Res<Score>) {
+    spawn_centered_text(&mut commands, GameOverText, "Game Over\nPress Enter to Restart", Color::RED, &arena);
+
+    let mut lines: Vec<String> = score
+        .breakdown
+        .lines()
+        .iter()
+        .map(|(label, points)| format!("{label:<16} {points:>7}"))
+        .collect();
+    lines.push(format!("{:<16} {:>7}", "Total", score.value));
+    commands.spawn((
+        GameOverText,
+        TextBundle::from_section(
+            lines.join("\n"),
+            TextStyle {
+                font_size: 22.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(arena.height / 2.0 - 240.0),
+            left: Val::Px(arena.width / 2.0 - 200.0),
+            ..default()
+        }),
+    ));
+}
+
+fn update_score_text(score: Res<Score>, mut query: Query<&mut Text, With<ScoreText>>) {
     for mut text in query.iter_mut() {
         text.sections[1].value = score.value.to_string();
+        text.sections[2].value = if score.combo.multiplier > 1.0 {
+            format!("  x{:.2}", score.combo.multiplier)
+        } else {
+            String::new()
+        };
     }
 }
 
@@ -136,4 +300,228 @@ fn update_lives_text(
     let lives = lives_query.single();
     let mut text = text_query.single_mut();
     text.sections[0].value = "❤️".repeat(lives.count() as usize);
-}
\ No newline at end of file
+    let missing = lives.max_health() - lives.health();
+    text.sections[1].value = format!("  {}{}", "■".repeat(lives.health() as usize), "□".repeat(missing as usize));
+}
+
+/// Nome e cor do elemento escolhido.
+fn update_element_text(selected: Res<SelectedElement>, mut query: Query<&mut Text, With<ElementText>>) {
+    let name = match selected.0 {
+        Element::Standard => "Standard",
+        Element::Fire => "Fire",
+        Element::Ice => "Ice",
+        Element::Electric => "Electric",
+        Element::Explosive => "Explosive",
+    };
+    for mut text in query.iter_mut() {
+        text.sections[0].value = format!("Element: {name} (1-5, Q/E)");
+        text.sections[0].style.color = selected.0.color();
+    }
+}
+
+/// Nome da arma e munição no pente, ou o aviso de recarga.
+fn update_weapon_text(
+    weapon_query: Query<&Weapon, (With<Player>, Changed<Weapon>)>,
+    mut query: Query<&mut Text, With<WeaponText>>,
+) {
+    let Ok(weapon) = weapon_query.get_single() else {
+        return;
+    };
+    let ammo = match (weapon.ammo(), weapon.kind.ammo) {
+        _ if weapon.is_reloading() => "reloading...".to_string(),
+        (Some(ammo), Some(spec)) => format!("{ammo}/{}", spec.magazine),
+        _ => "∞".to_string(),
+    };
+    for mut text in query.iter_mut() {
+        text.sections[0].value = format!("Weapon: {}  {ammo} (Tab, R)", weapon.kind.name);
+    }
+}
+
+/// A tela fica vermelha por um instante quando o jogador leva dano.
+fn show_damage_overlay(
+    mut commands: Commands,
+    mut damaged_events: EventReader<PlayerDamaged>,
+    overlay_query: Query<Entity, With<DamageOverlay>>,
+) {
+    if damaged_events.read().count() == 0 {
+        return;
+    }
+    for entity in overlay_query.iter() {
+        commands.entity(entity).despawn();
+    }
+    commands.spawn((
+        DamageOverlay {
+            timer: Timer::from_seconds(DAMAGE_OVERLAY_TIME, TimerMode::Once),
+        },
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            background_color: Color::rgba(1.0, 0.0, 0.0, DAMAGE_OVERLAY_ALPHA).into(),
+            z_index: ZIndex::Global(5),
+            ..default()
+        },
+    ));
+}
+
+fn fade_damage_overlay(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut overlay_query: Query<(Entity, &mut DamageOverlay, &mut BackgroundColor)>,
+) {
+    for (entity, mut overlay, mut background) in overlay_query.iter_mut() {
+        if overlay.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        background.0.set_a(DAMAGE_OVERLAY_ALPHA * overlay.timer.fraction_remaining());
+    }
+}
+
+/// Abre ou fecha o mapa em tela cheia.
+fn toggle_full_map(actions: Res<ActionState>, mut map_view: ResMut<MapView>) {
+    if actions.just_pressed(Action::Map) {
+        map_view.full_map = !map_view.full_map;
+    }
+}
+
+/// Cor de uma sala no mapa: a atual em branco, as visitadas em cinza e as
+/// só revistas em cinza translúcido.
+fn map_cell_color(room: &Room, current: RoomId) -> Color {
+    if room.id == current {
+        Color::WHITE
+    } else if room.visited {
+        Color::rgb(0.55, 0.55, 0.55)
+    } else {
+        Color::rgba(0.55, 0.55, 0.55, 0.35)
+    }
+}
+
+/// Ícone das salas especiais.
+fn map_icon_color(kind: RoomKind) -> Option<Color> {
+    match kind {
+        RoomKind::Normal | RoomKind::Start => None,
+        RoomKind::Treasure => Some(Color::GOLD),
+        RoomKind::Shop => Some(Color::GREEN),
+        RoomKind::Boss => Some(Color::RED),
+    }
+}
+
+/// Desenha as salas reveladas dentro de `parent`, com a célula `center` da
+/// grade no ponto `origin` do nó pai.
+fn spawn_map_cells(parent: &mut ChildBuilder, room_graph: &RoomGraph, current: RoomId, center: Vec2, origin: Vec2, cell: Vec2) {
+    let gap = cell.y / 4.0;
+    for id in room_graph.room_ids() {
+        let Some(room) = room_graph.get_room(id).filter(|room| room.revealed) else {
+            continue;
+        };
+        // Na interface o eixo y cresce para baixo
+        let offset = (room.position.as_vec2() - center) * (cell + gap) * Vec2::new(1.0, -1.0);
+        let top_left = origin + offset - cell / 2.0;
+
+        parent
+            .spawn((
+                MapCell { room: id },
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(top_left.x),
+                        top: Val::Px(top_left.y),
+                        width: Val::Px(cell.x),
+                        height: Val::Px(cell.y),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    background_color: map_cell_color(room, current).into(),
+                    ..default()
+                },
+            ))
+            .with_children(|cell_node| {
+                if let Some(color) = map_icon_color(room.kind) {
+                    cell_node.spawn(NodeBundle {
+                        style: Style {
+                            width: Val::Px(cell.y / 2.0),
+                            height: Val::Px(cell.y / 2.0),
+                            ..default()
+                        },
+                        background_color: color.into(),
+                        ..default()
+                    });
+                }
+            });
+    }
+}
+
+/// Os dois mapas, recriados juntos a cada mudança.
+type AnyMap = Or<(With<Minimap>, With<FullMap>)>;
+
+/// Recria o minimapa e, se estiver aberto, o mapa em tela cheia.
+fn draw_map(
+    mut commands: Commands,
+    map_query: Query<Entity, AnyMap>,
+    room_graph: Res<RoomGraph>,
+    current_room: Res<CurrentRoom>,
+    map_view: Res<MapView>,
+    arena: Res<ArenaSize>,
+) {
+    for entity in map_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    let Some(current) = room_graph.get_room(current_room.id) else {
+        return;
+    };
+
+    // Minimapa centrado na sala atual
+    commands
+        .spawn((
+            Minimap,
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(10.0),
+                    right: Val::Px(10.0),
+                    width: Val::Px(MINIMAP_SIZE.x),
+                    height: Val::Px(MINIMAP_SIZE.y),
+                    overflow: Overflow::clip(),
+                    ..default()
+                },
+                background_color: Color::rgba(0.0, 0.0, 0.0, 0.5).into(),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            spawn_map_cells(parent, &room_graph, current.id, current.position.as_vec2(), MINIMAP_SIZE / 2.0, MINIMAP_CELL);
+        });
+
+    if !map_view.full_map {
+        return;
+    }
+
+    // Mapa em tela cheia centrado no meio das salas reveladas
+    let revealed: Vec<Vec2> = room_graph.rooms().filter(|room| room.revealed).map(|room| room.position.as_vec2()).collect();
+    let min = revealed.iter().copied().reduce(Vec2::min).unwrap_or_default();
+    let max = revealed.iter().copied().reduce(Vec2::max).unwrap_or_default();
+    commands
+        .spawn((
+            FullMap,
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                background_color: Color::rgba(0.0, 0.0, 0.0, 0.8).into(),
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            let origin = Vec2::new(arena.width, arena.height) / 2.0;
+            spawn_map_cells(parent, &room_graph, current.id, (min + max) / 2.0, origin, FULL_MAP_CELL);
+        });
+}
@@ -1,76 +1,127 @@
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
-use crate::config::GameConfig;
+use crate::config::GameTuning;
+use crate::arena::ArenaSize;
+use crate::collision::push_circle_out_of_rect;
+use crate::room_templates::RoomTemplates;
+use crate::rooms::{door_position, room_layout_changed, CurrentRoom, Direction, RoomGraph};
 
 pub struct WallsPlugin;
 
 impl Plugin for WallsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, spawn_walls);
+        app.add_systems(Update, spawn_walls.run_if(room_layout_changed));
     }
 }
 
 #[derive(Component)]
 pub struct Wall;
 
-fn spawn_walls(mut commands: Commands, window_query: Query<&Window, With<PrimaryWindow>>) {
-    let window = window_query.single();
-    let window_width = window.width();
-    let window_height = window.height();
+/// Buraco no chão da sala.
+#[derive(Component)]
+pub struct Pit;
 
-    // Parede superior
-    commands.spawn((
-        Wall,
-        SpriteBundle {
-            transform: Transform::from_xyz(0.0, window_height / 2.0, 0.0),
-            sprite: Sprite {
-                color: Color::GRAY,
-                custom_size: Some(Vec2::new(window_width, GameConfig::WALL_THICKNESS)),
-                ..default()
-            },
-            ..default()
-        },
-    ));
+/// Retângulo sólido dentro da sala, vindo do modelo dela. Jogador e inimigos
+/// não atravessam nenhum; projéteis só param nas paredes.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Obstacle {
+    pub half_size: Vec2,
+    pub blocks_projectiles: bool,
+}
 
-    // Parede inferior
-    commands.spawn((
-        Wall,
-        SpriteBundle {
-            transform: Transform::from_xyz(0.0, -window_height / 2.0, 0.0),
-            sprite: Sprite {
-                color: Color::GRAY,
-                custom_size: Some(Vec2::new(window_width, GameConfig::WALL_THICKNESS)),
-                ..default()
-            },
-            ..default()
-        },
-    ));
+impl Obstacle {
+    pub fn rect(&self, position: Vec2) -> Rect {
+        Rect::from_center_half_size(position, self.half_size)
+    }
+}
 
-    // Parede esquerda
-    commands.spawn((
-        Wall,
-        SpriteBundle {
-            transform: Transform::from_xyz(-window_width / 2.0, 0.0, 0.0),
-            sprite: Sprite {
-                color: Color::GRAY,
-                custom_size: Some(Vec2::new(GameConfig::WALL_THICKNESS, window_height)),
-                ..default()
-            },
-            ..default()
-        },
-    ));
+/// Corrige a posição de um círculo para que ele não entre em nenhum obstáculo.
+pub fn resolve_obstacles(position: Vec2, radius: f32, obstacles: &[Rect]) -> Vec2 {
+    obstacles
+        .iter()
+        .fold(position, |position, rect| push_circle_out_of_rect(position, radius, *rect))
+}
+
+/// Blocos que `spawn_walls` cria e recria a cada troca de sala.
+type WallOrPit = Or<(With<Wall>, With<Pit>)>;
 
-    // Parede direita
-    commands.spawn((
-        Wall,
-        SpriteBundle {
-            transform: Transform::from_xyz(window_width / 2.0, 0.0, 0.0),
-            sprite: Sprite {
-                color: Color::GRAY,
-                custom_size: Some(Vec2::new(GameConfig::WALL_THICKNESS, window_height)),
+/// Recria as paredes da sala atual, deixando uma abertura onde há porta, e
+/// os obstáculos do modelo da sala.
+fn spawn_walls(
+    mut commands: Commands,
+    wall_query: Query<Entity, WallOrPit>,
+    arena: Res<ArenaSize>,
+    tuning: Res<GameTuning>,
+    current_room: Res<CurrentRoom>,
+    room_graph: Res<RoomGraph>,
+    templates: Res<RoomTemplates>,
+) {
+    for entity in wall_query.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let layout = templates.layout_for(&room_graph, current_room.id, &arena);
+    let blocks = layout
+        .walls
+        .iter()
+        .map(|rect| (rect, true, Color::GRAY))
+        .chain(layout.pits.iter().map(|rect| (rect, false, Color::rgb(0.08, 0.08, 0.1))));
+    for (rect, is_wall, color) in blocks {
+        let center = rect.center();
+        let mut entity = commands.spawn((
+            Obstacle {
+                half_size: rect.half_size(),
+                blocks_projectiles: is_wall,
+            },
+            SpriteBundle {
+                transform: Transform::from_xyz(center.x, center.y, -0.1),
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(rect.size()),
+                    ..default()
+                },
                 ..default()
             },
-            ..default()
-        },
-    ));
+        ));
+        if is_wall {
+            entity.insert(Wall);
+        } else {
+            entity.insert(Pit);
+        }
+    }
+
+    let room = room_graph.get_room(current_room.id);
+    for direction in Direction::ALL {
+        let center = door_position(direction, &arena);
+        // Comprimento da parede e eixo ao longo dela
+        let (length, along) = match direction {
+            Direction::North | Direction::South => (arena.width, Vec2::X),
+            Direction::East | Direction::West => (arena.height, Vec2::Y),
+        };
+        let has_door = room.is_some_and(|room| room.has_connection(direction));
+
+        // Com porta, a parede vira dois pedaços, um de cada lado da abertura
+        let segments = if has_door {
+            let piece = (length - tuning.door_width) / 2.0;
+            let offset = (tuning.door_width + piece) / 2.0;
+            vec![(center - along * offset, piece), (center + along * offset, piece)]
+        } else {
+            vec![(center, length)]
+        };
+
+        for (position, piece) in segments {
+            let size = along * piece + along.perp().abs() * tuning.wall_thickness;
+            commands.spawn((
+                Wall,
+                SpriteBundle {
+                    transform: Transform::from_xyz(position.x, position.y, 0.0),
+                    sprite: Sprite {
+                        color: Color::GRAY,
+                        custom_size: Some(size),
+                        ..default()
+                    },
+                    ..default()
+                },
+            ));
+        }
+    }
 }
@@ -0,0 +1,393 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+use crate::actions::{Action, ActionState};
+use crate::player::Player;
+use crate::state::AppState;
+
+/// Caminho padrão do arquivo com as armas.
+pub const WEAPONS_PATH: &str = "assets/weapons.ron";
+
+/// Cópia do arquivo embutida no executável, usada quando ele falta ou é inválido.
+const BUILTIN_WEAPONS: &str = include_str!("../assets/weapons.ron");
+
+/// Plugin que carrega o `WeaponRegistry` do disco e cuida da troca e da
+/// recarga da arma do jogador. O disparo fica em `projectiles`; as armas
+/// guardadas ficam no `Arsenal` do jogador.
+pub struct WeaponsPlugin;
+
+impl Plugin for WeaponsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<WeaponRegistry>()
+            .add_systems(PreStartup, load_weapons)
+            .add_systems(Update, (switch_weapon, reload_weapon).run_if(in_state(AppState::Playing)));
+    }
+}
+
+/// Pente de uma arma com munição limitada.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Ammo {
+    pub magazine: u32,    // Tiros por pente
+    pub reload_time: f32, // Duração da recarga (s)
+}
+
+/// Uma arma, como descrita no arquivo de armas.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct WeaponKind {
+    pub name: String,
+    pub fire_rate: f32,        // Rajadas por segundo
+    pub projectile_count: u32, // Projéteis de cada tiro
+    pub spread: f32,           // Abertura do leque, em graus
+    pub burst_size: u32,       // Tiros de cada rajada
+    pub burst_interval: f32,   // Intervalo entre os tiros da rajada (s)
+    pub lifetime: f32,         // Tempo até o projétil sumir (s)
+    pub piercing: u32,         // Inimigos extras que cada projétil atravessa
+    pub damage: f32,           // Multiplica o `projectile_damage` dos ajustes
+    #[serde(default)]
+    pub ammo: Option<Ammo>,    // Sem pente, a munição é infinita
+}
+
+impl WeaponKind {
+    /// Direções dos projéteis de um tiro, abertas em leque em volta de `aim`.
+    pub fn shot_directions(&self, aim: Vec2) -> Vec<Vec2> {
+        let count = self.projectile_count.max(1);
+        if count == 1 {
+            return vec![aim];
+        }
+        let spread = self.spread.to_radians();
+        let step = spread / (count - 1) as f32;
+        (0..count)
+            .map(|i| Vec2::from_angle(-spread / 2.0 + step * i as f32).rotate(aim))
+            .collect()
+    }
+}
+
+/// Arma do jogador, com o estado de cadência, rajada e munição.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Weapon {
+    pub kind: WeaponKind,
+    cooldown: f32,       // Segundos até poder começar outra rajada
+    burst_left: u32,     // Tiros que ainda faltam na rajada atual
+    burst_timer: f32,    // Segundos até o próximo tiro da rajada
+    ammo: Option<u32>,   // Tiros no pente; `None` para munição infinita
+    reload: Option<f32>, // Segundos até a recarga terminar
+}
+
+impl Weapon {
+    /// Arma pronta para atirar, com o pente cheio.
+    pub fn new(kind: WeaponKind) -> Self {
+        let ammo = kind.ammo.map(|ammo| ammo.magazine);
+        Weapon {
+            kind,
+            cooldown: 0.0,
+            burst_left: 0,
+            burst_timer: 0.0,
+            ammo,
+            reload: None,
+        }
+    }
+
+    pub fn ammo(&self) -> Option<u32> {
+        self.ammo
+    }
+
+    pub fn is_reloading(&self) -> bool {
+        self.reload.is_some()
+    }
+
+    /// Começa a recarga, se a arma usa pente e ele não está cheio.
+    pub fn start_reload(&mut self) {
+        if let (Some(ammo), Some(spec)) = (self.ammo, self.kind.ammo)
+            && ammo < spec.magazine
+            && self.reload.is_none()
+        {
+            self.burst_left = 0;
+            self.reload = Some(spec.reload_time);
+        }
+    }
+
+    /// Avança `delta` segundos e devolve quantos tiros saem neste passo.
+    /// Com `trigger`, começa uma rajada se a arma estiver pronta; a rajada
+    /// termina sozinha mesmo que o gatilho seja solto.
+    pub fn update(&mut self, delta: f32, trigger: bool) -> u32 {
+        self.cooldown = (self.cooldown - delta).max(0.0);
+        if let Some(remaining) = self.reload.as_mut() {
+            *remaining -= delta;
+            if *remaining > 0.0 {
+                return 0;
+            }
+            self.reload = None;
+            self.ammo = self.kind.ammo.map(|ammo| ammo.magazine);
+        }
+
+        let mut shots = 0;
+        if self.burst_left > 0 {
+            self.burst_timer -= delta;
+            while self.burst_left > 0 && self.burst_timer <= 0.0 && self.take_round() {
+                shots += 1;
+                self.burst_left -= 1;
+                self.burst_timer += self.kind.burst_interval;
+            }
+        } else if trigger && self.cooldown <= 0.0 && self.take_round() {
+            shots = 1;
+            self.burst_left = self.kind.burst_size.saturating_sub(1);
+            self.burst_timer = self.kind.burst_interval;
+            self.cooldown = 1.0 / self.kind.fire_rate;
+        }
+
+        if self.ammo == Some(0) {
+            self.start_reload();
+        }
+        shots
+    }
+
+    /// Guarda a arma: a rajada em curso acaba, o resto fica como está.
+    pub fn holster(&mut self) {
+        self.burst_left = 0;
+        self.burst_timer = 0.0;
+    }
+
+    /// Munição, cadência e recarga da arma, para o save.
+    pub fn to_saved(&self) -> SavedWeapon {
+        SavedWeapon {
+            name: self.kind.name.clone(),
+            ammo: self.ammo,
+            cooldown: self.cooldown,
+            reload: self.reload,
+        }
+    }
+
+    /// Arma do tipo dado no estado salvo. A munição não passa do pente
+    /// atual, e um pente sem munição salva volta cheio.
+    pub fn from_saved(kind: WeaponKind, saved: &SavedWeapon) -> Self {
+        let mut weapon = Weapon::new(kind);
+        if let (Some(full), Some(ammo)) = (weapon.ammo, saved.ammo) {
+            weapon.ammo = Some(ammo.min(full));
+        }
+        weapon.cooldown = saved.cooldown.max(0.0);
+        if weapon.kind.ammo.is_some() {
+            weapon.reload = saved.reload;
+        }
+        weapon
+    }
+
+    /// Gasta um tiro do pente. Devolve `false` se ele estiver vazio.
+    fn take_round(&mut self) -> bool {
+        match self.ammo.as_mut() {
+            Some(0) => {
+                self.burst_left = 0;
+                false
+            }
+            Some(ammo) => {
+                *ammo -= 1;
+                true
+            }
+            None => true,
+        }
+    }
+}
+
+/// Estado salvo de uma arma.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SavedWeapon {
+    pub name: String,
+    pub ammo: Option<u32>,   // Tiros no pente; cheio se `None`
+    pub cooldown: f32,       // Segundos até poder atirar
+    pub reload: Option<f32>, // Segundos até a recarga terminar
+}
+
+/// Armas guardadas do jogador. Cada uma volta como foi guardada, então
+/// trocar de arma não enche o pente nem pula a cadência.
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct Arsenal {
+    holstered: Vec<Weapon>,
+}
+
+impl Arsenal {
+    pub fn holstered(&self) -> &[Weapon] {
+        &self.holstered
+    }
+
+    /// Guarda `current` e equipa `next`: a guardada antes, se houver, ou uma
+    /// nova com o pente cheio.
+    pub fn swap(&mut self, current: &mut Weapon, next: &WeaponKind) {
+        let incoming = match self.holstered.iter().position(|weapon| weapon.kind.name == next.name) {
+            Some(index) => self.holstered.remove(index),
+            None => Weapon::new(next.clone()),
+        };
+        let mut outgoing = std::mem::replace(current, incoming);
+        outgoing.holster();
+        self.holstered.push(outgoing);
+    }
+
+    pub fn insert(&mut self, weapon: Weapon) {
+        self.holstered.retain(|holstered| holstered.kind.name != weapon.kind.name);
+        self.holstered.push(weapon);
+    }
+
+    pub fn clear(&mut self) {
+        self.holstered.clear();
+    }
+}
+
+/// Recurso com todas as armas e a arma inicial do jogador.
+#[derive(Resource, Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct WeaponRegistry {
+    pub starting: String,
+    pub weapons: Vec<WeaponKind>,
+}
+
+impl Default for WeaponRegistry {
+    fn default() -> Self {
+        WeaponRegistry::from_ron(BUILTIN_WEAPONS).expect("assets/weapons.ron embutido é válido")
+    }
+}
+
+impl WeaponRegistry {
+    /// Lê, interpreta e valida um arquivo RON de armas.
+    pub fn load(path: &Path) -> Result<Self, WeaponsError> {
+        let text = std::fs::read_to_string(path).map_err(WeaponsError::Io)?;
+        Self::from_ron(&text)
+    }
+
+    /// Interpreta e valida armas a partir de um texto RON.
+    pub fn from_ron(text: &str) -> Result<Self, WeaponsError> {
+        let registry: WeaponRegistry = ron::from_str(text).map_err(WeaponsError::Parse)?;
+        registry.validate()?;
+        Ok(registry)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&WeaponKind> {
+        self.weapons.iter().find(|weapon| weapon.name == name)
+    }
+
+    /// Arma com que o jogador começa a partida.
+    pub fn starting_weapon(&self) -> Weapon {
+        let kind = self.get(&self.starting).unwrap_or(&self.weapons[0]);
+        Weapon::new(kind.clone())
+    }
+
+    /// Arma seguinte a `name` na lista, dando a volta.
+    pub fn next_after(&self, name: &str) -> &WeaponKind {
+        let index = self.weapons.iter().position(|weapon| weapon.name == name).map_or(0, |index| index + 1);
+        &self.weapons[index % self.weapons.len()]
+    }
+
+    /// Verifica se todos os valores fazem sentido para o jogo.
+    pub fn validate(&self) -> Result<(), WeaponsError> {
+        if self.weapons.is_empty() {
+            return Err(WeaponsError::Invalid {
+                weapon: "weapons".to_string(),
+                reason: "precisa de pelo menos uma arma".to_string(),
+            });
+        }
+
+        let mut names = HashSet::new();
+        for weapon in &self.weapons {
+            let invalid = |reason: String| WeaponsError::Invalid { weapon: weapon.name.clone(), reason };
+            if !names.insert(weapon.name.as_str()) {
+                return Err(invalid("nome repetido".to_string()));
+            }
+            let positive = [
+                ("fire_rate", weapon.fire_rate),
+                ("lifetime", weapon.lifetime),
+                ("damage", weapon.damage),
+            ];
+            for (field, value) in positive {
+                if !value.is_finite() || value <= 0.0 {
+                    return Err(invalid(format!("`{field}` deve ser maior que zero (recebido {value})")));
+                }
+            }
+            if !weapon.burst_interval.is_finite() || weapon.burst_interval < 0.0 {
+                return Err(invalid(format!("`burst_interval` não pode ser negativo (recebido {})", weapon.burst_interval)));
+            }
+            if !(0.0..360.0).contains(&weapon.spread) {
+                return Err(invalid(format!("`spread` deve estar entre 0 e 360 graus (recebido {})", weapon.spread)));
+            }
+            if weapon.projectile_count == 0 || weapon.burst_size == 0 {
+                return Err(invalid("`projectile_count` e `burst_size` devem ser pelo menos 1".to_string()));
+            }
+            if let Some(ammo) = weapon.ammo {
+                if ammo.magazine == 0 {
+                    return Err(invalid("`ammo.magazine` deve ser pelo menos 1".to_string()));
+                }
+                if !ammo.reload_time.is_finite() || ammo.reload_time < 0.0 {
+                    return Err(invalid(format!("`ammo.reload_time` não pode ser negativo (recebido {})", ammo.reload_time)));
+                }
+            }
+        }
+
+        if !names.contains(self.starting.as_str()) {
+            return Err(WeaponsError::Invalid {
+                weapon: "starting".to_string(),
+                reason: format!("arma `{}` não existe", self.starting),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Erros ao carregar o arquivo de armas.
+#[derive(Debug)]
+pub enum WeaponsError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    Invalid { weapon: String, reason: String },
+}
+
+impl fmt::Display for WeaponsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WeaponsError::Io(err) => write!(f, "não foi possível ler o arquivo: {err}"),
+            WeaponsError::Parse(err) => write!(f, "erro de sintaxe: {err}"),
+            WeaponsError::Invalid { weapon, reason } => write!(f, "`{weapon}` inválido: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for WeaponsError {}
+
+/// Carrega as armas. Sem arquivo, fica a cópia embutida.
+fn load_weapons(mut registry: ResMut<WeaponRegistry>) {
+    let path = Path::new(WEAPONS_PATH);
+    if !path.exists() {
+        info!("Arquivo de armas {:?} não encontrado, usando as armas padrão", path);
+        return;
+    }
+
+    match WeaponRegistry::load(path) {
+        Ok(loaded) => *registry = loaded,
+        Err(err) => error!("Armas em {:?} ignoradas: {}", path, err),
+    }
+}
+
+/// Troca para a próxima arma, no estado em que ela foi guardada.
+fn switch_weapon(
+    actions: Res<ActionState>,
+    registry: Res<WeaponRegistry>,
+    mut weapon_query: Query<(&mut Weapon, &mut Arsenal), With<Player>>,
+) {
+    if !actions.just_pressed(Action::NextWeapon) {
+        return;
+    }
+    for (mut weapon, mut arsenal) in weapon_query.iter_mut() {
+        let next = registry.next_after(&weapon.kind.name).clone();
+        arsenal.swap(&mut weapon, &next);
+    }
+}
+
+/// Recarrega antes de o pente acabar.
+fn reload_weapon(actions: Res<ActionState>, mut weapon_query: Query<&mut Weapon, With<Player>>) {
+    if actions.just_pressed(Action::Reload) {
+        for mut weapon in weapon_query.iter_mut() {
+            weapon.start_reload();
+        }
+    }
+}
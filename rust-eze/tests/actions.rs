@@ -0,0 +1,272 @@
+mod common;
+
+use std::path::PathBuf;
+
+use bevy::input::gamepad::{GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType};
+use bevy::prelude::*;
+use rust_eze::actions::{Action, Binding, BindingsError, BindingsFile, InputBindings, Stick, StickAction};
+use rust_eze::options::OptionsMenu;
+use rust_eze::projectiles::Projectile;
+use rust_eze::state::AppState;
+
+use common::{clear_enemies, headless_app, headless_app_with, player_position, state, tap};
+
+const PAD: Gamepad = Gamepad { id: 0 };
+
+/// Pasta de controles só deste teste, apagada no fim.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("rust-eze-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        TempDir(path)
+    }
+
+    fn file(&self) -> PathBuf {
+        self.0.join("bindings.ron")
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Aperta e solta um botão do controle, como o `tap` do teclado.
+fn tap_pad(app: &mut App, button: GamepadButtonType) {
+    let button = GamepadButton::new(PAD, button);
+    app.world.resource_mut::<ButtonInput<GamepadButton>>().press(button);
+    app.update();
+    let mut buttons = app.world.resource_mut::<ButtonInput<GamepadButton>>();
+    buttons.release(button);
+    buttons.clear();
+    app.update();
+}
+
+fn set_stick(app: &mut App, x: GamepadAxisType, y: GamepadAxisType, tilt: Vec2) {
+    let mut axes = app.world.resource_mut::<Axis<GamepadAxis>>();
+    axes.set(GamepadAxis::new(PAD, x), tilt.x);
+    axes.set(GamepadAxis::new(PAD, y), tilt.y);
+}
+
+#[test]
+fn rebinding_replaces_only_the_same_kind_of_input() {
+    let mut bindings = InputBindings::default();
+    bindings.bind(Action::Fire, Binding::Key(KeyCode::KeyF));
+    assert_eq!(
+        bindings.get(Action::Fire),
+        &[
+            Binding::Mouse(MouseButton::Left),
+            Binding::Pad(GamepadButtonType::RightTrigger2),
+            Binding::Key(KeyCode::KeyF),
+        ]
+    );
+    assert_eq!(bindings.describe(Action::Fire), "Mouse Left / Pad RightTrigger2 / F");
+
+    assert_eq!(bindings.bind(Action::Fire, Binding::Pad(GamepadButtonType::LeftThumb)), None);
+    assert!(!bindings.get(Action::Fire).contains(&Binding::Pad(GamepadButtonType::RightTrigger2)));
+    // Outras ações não mudam
+    assert_eq!(bindings.get(Action::Confirm), InputBindings::default().get(Action::Confirm));
+}
+
+#[test]
+fn default_bindings_never_share_an_input() {
+    let bindings = InputBindings::default();
+    let mut seen = Vec::new();
+    for action in Action::ALL {
+        for binding in bindings.get(action) {
+            assert!(!seen.contains(binding), "{binding:?} repetido em {action:?}");
+            seen.push(*binding);
+        }
+    }
+    assert_ne!(bindings.stick(StickAction::Move), bindings.stick(StickAction::Aim));
+}
+
+#[test]
+fn rebinding_a_taken_input_swaps_it_with_the_other_action() {
+    let mut bindings = InputBindings::default();
+    // Tab era da troca de arma, que fica com o R da recarga
+    assert_eq!(bindings.bind(Action::Reload, Binding::Key(KeyCode::Tab)), Some(Action::NextWeapon));
+    assert!(bindings.get(Action::Reload).contains(&Binding::Key(KeyCode::Tab)));
+    assert!(bindings.get(Action::NextWeapon).contains(&Binding::Key(KeyCode::KeyR)));
+
+    // O salvamento rápido não tinha botão de controle: a recarga fica sem
+    assert_eq!(bindings.bind(Action::QuickSave, Binding::Pad(GamepadButtonType::West)), Some(Action::Reload));
+    assert_eq!(bindings.get(Action::Reload), &[Binding::Key(KeyCode::Tab)]);
+
+    assert_eq!(bindings.bind_stick(StickAction::Move, Stick::Right), Some(StickAction::Aim));
+    assert_eq!(bindings.stick(StickAction::Aim), Some(Stick::Left));
+    assert_eq!(bindings.bind_stick(StickAction::Move, Stick::Right), None);
+}
+
+#[test]
+fn bindings_survive_a_round_trip_and_missing_actions_keep_the_defaults() {
+    let dir = TempDir::new("bindings-roundtrip");
+    let mut bindings = InputBindings::default();
+    bindings.bind(Action::MoveUp, Binding::Key(KeyCode::KeyI));
+    bindings.bind(Action::Pause, Binding::Mouse(MouseButton::Right));
+    bindings.bind_stick(StickAction::Aim, Stick::Left);
+    // A pasta ainda não existe
+    bindings.save(&dir.file()).unwrap();
+    assert_eq!(InputBindings::load(&dir.file()).unwrap(), bindings);
+
+    let text = "(version: 1, actions: [(action: Fire, inputs: [Key(KeyF)])], sticks: [])";
+    let loaded = InputBindings::from_ron(text).unwrap();
+    assert_eq!(loaded.get(Action::Fire), &[Binding::Key(KeyCode::KeyF)]);
+    assert_eq!(loaded.get(Action::MoveUp), InputBindings::default().get(Action::MoveUp));
+    assert_eq!(loaded.stick(StickAction::Move), Some(Stick::Left));
+
+    assert!(matches!(
+        InputBindings::from_ron("(version: 9, actions: [])"),
+        Err(BindingsError::UnsupportedVersion(9))
+    ));
+    assert!(matches!(InputBindings::from_ron("(version: 1, actions: [(action: Dance"), Err(BindingsError::Parse(_))));
+}
+
+#[test]
+fn bindings_without_sticks_keep_the_default_sticks() {
+    let text = "(version: 1, actions: [(action: Options, inputs: [Key(KeyO)])])";
+    let loaded = InputBindings::from_ron(text).unwrap();
+    assert_eq!(loaded.get(Action::Options), &[Binding::Key(KeyCode::KeyO)]);
+    assert_eq!(loaded.get(Action::Map), InputBindings::default().get(Action::Map));
+    assert_eq!(loaded.stick(StickAction::Move), Some(Stick::Left));
+    assert_eq!(loaded.stick(StickAction::Aim), Some(Stick::Right));
+}
+
+#[test]
+fn left_stick_moves_the_player_analogically() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+    let start = player_position(&mut app);
+
+    // Dentro da zona morta, nada acontece
+    set_stick(&mut app, GamepadAxisType::LeftStickX, GamepadAxisType::LeftStickY, Vec2::new(0.1, 0.0));
+    app.update();
+    assert_eq!(player_position(&mut app), start);
+
+    // Meio stick, meia velocidade: 300 px/s × 0.5 × 0.1 s
+    set_stick(&mut app, GamepadAxisType::LeftStickX, GamepadAxisType::LeftStickY, Vec2::new(0.0, -0.5));
+    app.update();
+    let moved = player_position(&mut app) - start;
+    assert!(moved.x.abs() < 1e-3, "andou para o lado: {moved}");
+    assert!((moved.y + 15.0).abs() < 3.0, "andou {moved} em 0.1 s");
+}
+
+#[test]
+fn sticks_follow_the_bindings_and_only_the_first_pad() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+    let mut bindings = InputBindings::default();
+    bindings.bind_stick(StickAction::Move, Stick::Right);
+    app.insert_resource(bindings);
+    let start = player_position(&mut app);
+
+    // Outro controle inclinado não conta, mesmo com o primeiro parado
+    let other = Gamepad { id: 1 };
+    let mut axes = app.world.resource_mut::<Axis<GamepadAxis>>();
+    axes.set(GamepadAxis::new(PAD, GamepadAxisType::RightStickX), 0.0);
+    axes.set(GamepadAxis::new(other, GamepadAxisType::RightStickX), -1.0);
+    app.update();
+    assert_eq!(player_position(&mut app), start);
+
+    set_stick(&mut app, GamepadAxisType::RightStickX, GamepadAxisType::RightStickY, Vec2::new(1.0, 0.0));
+    app.update();
+    assert!(player_position(&mut app).x > start.x, "o stick direito move agora");
+}
+
+#[test]
+fn right_stick_aims_and_fires() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+
+    set_stick(&mut app, GamepadAxisType::RightStickX, GamepadAxisType::RightStickY, Vec2::new(-0.9, 0.0));
+    app.update();
+    set_stick(&mut app, GamepadAxisType::RightStickX, GamepadAxisType::RightStickY, Vec2::ZERO);
+    app.update();
+
+    let fired: Vec<Vec2> = app
+        .world
+        .query_filtered::<&Transform, With<Projectile>>()
+        .iter(&app.world)
+        .map(|transform| transform.translation.xy())
+        .collect();
+    assert_eq!(fired.len(), 1);
+    assert!(fired[0].x < 0.0 && fired[0].y.abs() < 1e-3, "para a esquerda: {}", fired[0]);
+}
+
+#[test]
+fn gamepad_buttons_pause_and_resume() {
+    let mut app = headless_app();
+    tap_pad(&mut app, GamepadButtonType::Start);
+    assert_eq!(state(&app), AppState::Paused);
+    tap_pad(&mut app, GamepadButtonType::Start);
+    assert_eq!(state(&app), AppState::Playing);
+}
+
+#[test]
+fn options_menu_rebinds_an_action_and_saves_it() {
+    let dir = TempDir::new("bindings-options");
+    let mut app = headless_app_with(|app| {
+        app.insert_resource(BindingsFile { path: dir.file() });
+    });
+    clear_enemies(&mut app);
+
+    tap(&mut app, KeyCode::Escape);
+    tap(&mut app, KeyCode::KeyO);
+    assert_eq!(state(&app), AppState::Options);
+
+    // A primeira linha é "Move up"; a escolha dá a volta na lista
+    tap(&mut app, KeyCode::KeyW);
+    assert_eq!(app.world.resource::<OptionsMenu>().selected, OptionsMenu::ROWS - 1);
+    tap(&mut app, KeyCode::KeyS);
+    assert_eq!(app.world.resource::<OptionsMenu>().selected, 0);
+
+    tap(&mut app, KeyCode::Enter);
+    assert!(app.world.resource::<OptionsMenu>().listening);
+    tap(&mut app, KeyCode::KeyI);
+    assert!(!app.world.resource::<OptionsMenu>().listening);
+
+    // Sair do menu volta para a pausa e grava os controles
+    tap(&mut app, KeyCode::Escape);
+    assert_eq!(state(&app), AppState::Paused);
+    let saved = InputBindings::load(&dir.file()).unwrap();
+    assert_eq!(saved.get(Action::MoveUp), &[Binding::Pad(GamepadButtonType::DPadUp), Binding::Key(KeyCode::KeyI)]);
+
+    tap(&mut app, KeyCode::Escape);
+    assert_eq!(state(&app), AppState::Playing);
+    let start = player_position(&mut app);
+    tap(&mut app, KeyCode::KeyW);
+    assert_eq!(player_position(&mut app), start, "W não move mais para cima");
+    app.world.resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::KeyI);
+    app.update();
+    app.update();
+    assert!(player_position(&mut app).y > start.y);
+}
+
+#[test]
+fn options_menu_swaps_taken_inputs_and_rebinds_sticks() {
+    let mut app = headless_app();
+    tap(&mut app, KeyCode::Escape);
+    tap(&mut app, KeyCode::KeyO);
+
+    // S já era de "Move down", que fica com o W
+    tap(&mut app, KeyCode::Enter);
+    tap(&mut app, KeyCode::KeyS);
+    assert_eq!(app.world.resource::<OptionsMenu>().swapped.as_deref(), Some("Move down"));
+    let bindings = app.world.resource::<InputBindings>();
+    assert!(bindings.get(Action::MoveDown).contains(&Binding::Key(KeyCode::KeyW)));
+
+    // As linhas dos sticks vêm depois das ações; W agora desce
+    for _ in 0..Action::ALL.len() {
+        tap(&mut app, KeyCode::KeyW);
+    }
+    tap(&mut app, KeyCode::Enter);
+    assert!(app.world.resource::<OptionsMenu>().listening);
+    set_stick(&mut app, GamepadAxisType::RightStickX, GamepadAxisType::RightStickY, Vec2::new(0.0, 1.0));
+    app.update();
+    let bindings = app.world.resource::<InputBindings>();
+    assert_eq!(bindings.stick(StickAction::Move), Some(Stick::Right));
+    assert_eq!(bindings.stick(StickAction::Aim), Some(Stick::Left));
+}
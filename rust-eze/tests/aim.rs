@@ -0,0 +1,87 @@
+mod common;
+
+use bevy::prelude::*;
+use rust_eze::actions::{Action, ActionState};
+use rust_eze::aim::SelectedElement;
+use rust_eze::projectiles::Projectile;
+use rust_eze::status::Element;
+
+use common::{clear_enemies, headless_app, tap};
+
+fn projectiles(app: &mut App) -> Vec<(Element, Vec2)> {
+    app.world
+        .query::<(&Projectile, &Transform)>()
+        .iter(&app.world)
+        .map(|(projectile, transform)| (projectile.element, transform.translation.xy()))
+        .collect()
+}
+
+fn despawn_projectiles(app: &mut App) {
+    let entities: Vec<Entity> = app.world.query_filtered::<Entity, With<Projectile>>().iter(&app.world).collect();
+    for entity in entities {
+        app.world.despawn(entity);
+    }
+}
+
+#[test]
+fn held_directions_combine_into_diagonals() {
+    let mut actions = ActionState::default();
+    assert_eq!(actions.aim(), None);
+
+    actions.press(Action::AimUp);
+    actions.press(Action::AimLeft);
+    let diagonal = actions.aim().unwrap();
+    assert!((diagonal - Vec2::new(-1.0, 1.0).normalize()).length() < 1e-6);
+
+    // Opostas se anulam
+    let mut actions = ActionState::default();
+    actions.press(Action::AimUp);
+    actions.press(Action::AimDown);
+    assert_eq!(actions.aim(), None);
+
+    // O stick direito tem prioridade sobre os botões
+    actions.set_sticks(Vec2::ZERO, Vec2::new(0.0, -0.5));
+    assert_eq!(actions.aim(), Some(Vec2::NEG_Y));
+}
+
+#[test]
+fn aim_and_element_are_chosen_separately_and_share_the_spawn() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+
+    tap(&mut app, KeyCode::Digit3);
+    assert_eq!(app.world.resource::<SelectedElement>().0, Element::Ice);
+
+    app.world.resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::ArrowDown);
+    app.world.resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::ArrowRight);
+    app.update();
+    let mut keyboard = app.world.resource_mut::<ButtonInput<KeyCode>>();
+    keyboard.release(KeyCode::ArrowDown);
+    keyboard.release(KeyCode::ArrowRight);
+    app.update();
+
+    let fired = projectiles(&mut app);
+    assert_eq!(fired.len(), 1);
+    let (element, position) = fired[0];
+    assert_eq!(element, Element::Ice);
+    assert!(position.x > 0.0 && position.y < 0.0, "projétil em {position}");
+    assert!((position.x + position.y).abs() < 1.0, "na diagonal: {position}");
+
+    // A tecla de disparo atira para onde a mira apontou por último
+    tap(&mut app, KeyCode::ArrowLeft);
+    despawn_projectiles(&mut app);
+
+    // A troca dá a volta na lista
+    tap(&mut app, KeyCode::Digit1);
+    tap(&mut app, KeyCode::KeyQ);
+    assert_eq!(app.world.resource::<SelectedElement>().0, Element::Explosive);
+    tap(&mut app, KeyCode::KeyE);
+    tap(&mut app, KeyCode::KeyE);
+    assert_eq!(app.world.resource::<SelectedElement>().0, Element::Fire);
+
+    tap(&mut app, KeyCode::Space);
+    let fired = projectiles(&mut app);
+    assert_eq!(fired.len(), 1);
+    assert_eq!(fired[0].0, Element::Fire);
+    assert!(fired[0].1.x < 0.0 && fired[0].1.y.abs() < 1e-3, "para a esquerda: {}", fired[0].1);
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use bevy::prelude::*;
+use rust_eze::arena::ArenaSize;
+use rust_eze::camera::{letterbox, CameraShake, GameCamera, Letterbox};
+use rust_eze::config::GameTuning;
+use rust_eze::game::PlayerDamaged;
+use rust_eze::player::Player;
+use rust_eze::room_state::LeftRoom;
+use rust_eze::rooms::{CurrentRoom, Door};
+use rust_eze::walls::{Obstacle, Wall};
+
+use common::{clear_enemies, headless_app};
+
+const ARENA: Vec2 = Vec2::new(1280.0, 720.0);
+
+fn camera_offset(app: &mut App) -> Vec2 {
+    app.world
+        .query_filtered::<&Transform, With<GameCamera>>()
+        .single(&app.world)
+        .translation
+        .xy()
+}
+
+#[test]
+fn the_arena_is_scaled_to_fit_the_window_with_bars() {
+    // Mesma proporção: ocupa a janela toda
+    assert_eq!(
+        letterbox(UVec2::new(1920, 1080), ARENA, false),
+        Some(Letterbox { position: UVec2::ZERO, size: UVec2::new(1920, 1080), scale: 1.5 })
+    );
+    // Janela mais alta: faixas em cima e embaixo
+    assert_eq!(
+        letterbox(UVec2::new(1920, 1200), ARENA, false),
+        Some(Letterbox { position: UVec2::new(0, 60), size: UVec2::new(1920, 1080), scale: 1.5 })
+    );
+    // Escala inteira: 1.5 vira 1, centralizado
+    assert_eq!(
+        letterbox(UVec2::new(1920, 1080), ARENA, true),
+        Some(Letterbox { position: UVec2::new(320, 180), size: UVec2::new(1280, 720), scale: 1.0 })
+    );
+    // Janela menor que a arena ainda mostra tudo, mesmo com escala inteira
+    assert_eq!(letterbox(UVec2::new(640, 360), ARENA, true).map(|fit| fit.scale), Some(0.5));
+    assert_eq!(letterbox(UVec2::new(0, 0), ARENA, false), None);
+}
+
+#[test]
+fn damage_shakes_the_camera_and_it_settles_back() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+    assert_eq!(camera_offset(&mut app), Vec2::ZERO);
+
+    app.world.send_event(PlayerDamaged { amount: 1, source: Vec2::ZERO });
+    app.update();
+    let trauma = app.world.resource::<CameraShake>().trauma;
+    assert!(trauma > 0.0);
+    assert!(camera_offset(&mut app) != Vec2::ZERO);
+    let max = app.world.resource::<GameTuning>().camera.shake_max_offset;
+    assert!(camera_offset(&mut app).length() <= max * 2f32.sqrt());
+
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_eq!(app.world.resource::<CameraShake>().trauma, 0.0);
+    assert_eq!(camera_offset(&mut app), Vec2::ZERO);
+}
+
+#[test]
+fn the_camera_pans_from_the_previous_room() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+    let start_room = app.world.resource::<CurrentRoom>().id;
+    let direction = app.world.query::<&Door>().iter(&app.world).next().expect("a sala inicial tem porta").direction;
+    let arena = *app.world.resource::<ArenaSize>();
+    let tuning = app.world.resource::<GameTuning>().clone();
+
+    // Encostado na porta, o jogador passa para a sala vizinha
+    let normal = direction.as_vec2();
+    let bounds = arena.bounds(tuning.wall_thickness, tuning.player_size);
+    let mut transform = app.world.query_filtered::<&mut Transform, With<Player>>().single_mut(&mut app.world);
+    transform.translation = (normal * bounds.dot(normal.abs())).extend(0.0);
+    app.update();
+    app.update();
+    assert_ne!(app.world.resource::<CurrentRoom>().id, start_room);
+    // Os inimigos da sala nova poderiam acertar o jogador e tremer a câmera
+    clear_enemies(&mut app);
+    app.world.resource_mut::<CameraShake>().trauma = 0.0;
+
+    // A sala antiga fica do lado oposto ao da porta usada
+    let offset = camera_offset(&mut app);
+    assert!(offset.dot(normal) < 0.0, "a câmera vem da sala antiga: {offset}");
+
+    // As paredes da sala antiga continuam à vista, uma sala adiante, mas
+    // não são mais obstáculos
+    let left: Vec<Vec2> = app
+        .world
+        .query_filtered::<&Transform, With<LeftRoom>>()
+        .iter(&app.world)
+        .map(|transform| transform.translation.xy())
+        .collect();
+    assert!(!left.is_empty(), "a sala antiga não sumiu no meio do deslizamento");
+    assert!(left.iter().all(|position| position.dot(normal) < 0.0), "{left:?}");
+    assert_eq!(app.world.query_filtered::<(), (With<LeftRoom>, With<Obstacle>)>().iter(&app.world).count(), 0);
+    assert_eq!(app.world.query_filtered::<(), (With<LeftRoom>, With<Wall>)>().iter(&app.world).count(), 0);
+
+    for _ in 0..5 {
+        app.update();
+    }
+    assert_eq!(camera_offset(&mut app), Vec2::ZERO);
+    assert_eq!(app.world.query::<&LeftRoom>().iter(&app.world).count(), 0);
+}
@@ -0,0 +1,40 @@
+mod common;
+
+use bevy::prelude::*;
+use rust_eze::collision::swept_circles_overlap;
+use rust_eze::config::GameTuning;
+use rust_eze::enemies::Enemy;
+use rust_eze::enemy_kinds::EnemyRegistry;
+
+use common::{clear_enemies, headless_app, spawn_enemy_at, tap};
+
+#[test]
+fn swept_test_catches_circles_that_cross_between_steps() {
+    // No início e no fim do passo estão longe, mas se cruzam no meio do caminho
+    let start = Vec2::new(-100.0, 0.0);
+    let end = Vec2::new(100.0, 0.0);
+    assert!(swept_circles_overlap(start, end, 4.0, Vec2::ZERO, Vec2::ZERO, 20.0));
+
+    // Passando ao lado, sem encostar
+    let start = Vec2::new(-100.0, 30.0);
+    let end = Vec2::new(100.0, 30.0);
+    assert!(!swept_circles_overlap(start, end, 4.0, Vec2::ZERO, Vec2::ZERO, 20.0));
+}
+
+#[test]
+fn fast_projectile_does_not_tunnel_through_enemy() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+
+    // Rápido o bastante para pular o inimigo inteiro em um único passo
+    let health = app.world.resource::<EnemyRegistry>().get("chaser").unwrap().health;
+    let mut tuning = app.world.resource_mut::<GameTuning>();
+    tuning.projectile_speed = 6000.0;
+    tuning.projectile_damage = health;
+    spawn_enemy_at(&mut app, Vec2::new(250.0, 0.0));
+
+    tap(&mut app, KeyCode::ArrowRight);
+
+    let enemies = app.world.query::<&Enemy>().iter(&app.world).count();
+    assert_eq!(enemies, 0);
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use bevy::prelude::*;
+use rust_eze::combat::{DamageEvent, Health};
+use rust_eze::config::GameTuning;
+use rust_eze::enemies::{EnemyKilled, HitFlash};
+use rust_eze::scoring::Score;
+
+use common::{clear_enemies, headless_app, spawn_enemy_at};
+
+fn hit(app: &mut App, target: Entity, amount: f32) {
+    app.world.send_event(DamageEvent::hit(target, amount));
+    app.update();
+}
+
+#[test]
+fn enemy_survives_until_health_runs_out() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+    app.world.resource_mut::<GameTuning>().enemy_invulnerability = 0.0;
+    let enemy = spawn_enemy_at(&mut app, Vec2::new(300.0, 0.0));
+    *app.world.get_mut::<Health>(enemy).unwrap() = Health::new(2.0);
+
+    hit(&mut app, enemy, 1.0);
+    assert_eq!(app.world.get::<Health>(enemy).map(|health| health.current), Some(1.0));
+
+    let score_before = app.world.resource::<Score>().value;
+    hit(&mut app, enemy, 1.0);
+    assert!(app.world.get_entity(enemy).is_none(), "inimigo deveria ter morrido");
+
+    let killed: Vec<EnemyKilled> = app.world.resource_mut::<Events<EnemyKilled>>().drain().collect();
+    assert_eq!(killed.len(), 1);
+    assert_eq!(killed[0].entity, enemy);
+    let kill_score = app.world.resource::<GameTuning>().kill_score;
+    assert!(app.world.resource::<Score>().value >= score_before + kill_score);
+}
+
+#[test]
+fn invulnerability_ignores_hits_while_flashing() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+    app.world.resource_mut::<GameTuning>().enemy_invulnerability = 10.0;
+    let enemy = spawn_enemy_at(&mut app, Vec2::new(300.0, 0.0));
+    let max = app.world.get::<Health>(enemy).unwrap().max;
+
+    hit(&mut app, enemy, 1.0);
+    assert!(app.world.get::<HitFlash>(enemy).is_some());
+
+    hit(&mut app, enemy, 1.0);
+    assert_eq!(app.world.get::<Health>(enemy).unwrap().current, max - 1.0);
+}
@@ -0,0 +1,92 @@
+#![allow(dead_code)]
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use rust_eze::RustEzeHeadlessPlugin;
+use rust_eze::enemy_kinds::EnemyRegistry;
+use rust_eze::enemies::Enemy;
+use rust_eze::lives::Lives;
+use rust_eze::player::Player;
+use rust_eze::rooms::CurrentRoom;
+use rust_eze::state::AppState;
+
+/// Cria um app sem janela com passo de tempo fixo de 100 ms, já em `Playing`.
+pub fn headless_app() -> App {
+    headless_app_with(|_| {})
+}
+
+/// Como `headless_app`, mas deixa o teste configurar o app antes do primeiro quadro.
+pub fn headless_app_with(setup: impl FnOnce(&mut App)) -> App {
+    let mut app = App::new();
+    app.add_plugins(RustEzeHeadlessPlugin)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+    setup(&mut app);
+    app.update();
+    app.world.resource_mut::<NextState<AppState>>().set(AppState::Playing);
+    app.update();
+    app
+}
+
+/// Aperta e solta uma tecla, rodando um quadro com ela pressionada e outro
+/// para aplicar a mudança de estado resultante.
+pub fn tap(app: &mut App, key: KeyCode) {
+    app.world.resource_mut::<ButtonInput<KeyCode>>().press(key);
+    app.update();
+    let mut keyboard = app.world.resource_mut::<ButtonInput<KeyCode>>();
+    keyboard.release(key);
+    keyboard.clear();
+    app.update();
+}
+
+pub fn state(app: &App) -> AppState {
+    *app.world.resource::<State<AppState>>().get()
+}
+
+/// Remove os inimigos aleatórios do início para que o teste controle a cena.
+pub fn clear_enemies(app: &mut App) {
+    let enemies: Vec<Entity> = app
+        .world
+        .query_filtered::<Entity, With<Enemy>>()
+        .iter(&app.world)
+        .collect();
+    for enemy in enemies {
+        app.world.despawn(enemy);
+    }
+}
+
+/// Cria um inimigo do tipo "chaser" na sala atual, sem visual.
+pub fn spawn_enemy_at(app: &mut App, position: Vec2) -> Entity {
+    spawn_kind_at(app, "chaser", position)
+}
+
+/// Cria um inimigo do tipo dado na sala atual, sem visual.
+pub fn spawn_kind_at(app: &mut App, kind: &str, position: Vec2) -> Entity {
+    let room = app.world.resource::<CurrentRoom>().id;
+    let bundle = app
+        .world
+        .resource::<EnemyRegistry>()
+        .get(kind)
+        .unwrap_or_else(|| panic!("tipo {kind} não existe"))
+        .bundle(room);
+    app.world
+        .spawn((bundle, Transform::from_xyz(position.x, position.y, 0.0)))
+        .id()
+}
+
+pub fn player_position(app: &mut App) -> Vec3 {
+    app.world
+        .query_filtered::<&Transform, With<Player>>()
+        .single(&app.world)
+        .translation
+}
+
+pub fn lives(app: &mut App) -> u32 {
+    app.world.query::<&Lives>().single(&app.world).count()
+}
+
+/// Pontos de vida da vida atual do jogador.
+pub fn health(app: &mut App) -> u32 {
+    app.world.query::<&Lives>().single(&app.world).health()
+}
@@ -0,0 +1,121 @@
+mod common;
+
+use std::path::Path;
+
+use bevy::prelude::*;
+use bevy::ecs::system::RunSystemOnce;
+use rust_eze::combat::DamageEvent;
+use rust_eze::config::GameTuning;
+use rust_eze::enemies::{Enemy, EnemyBullet, RoomEnemySpawner};
+use rust_eze::enemy_kinds::{Behavior, EnemyKindsError, EnemyRegistry, ENEMY_KINDS_PATH};
+use rust_eze::rng::SeedOverride;
+use rust_eze::rooms::{RoomGraph, RoomId};
+
+use common::{clear_enemies, headless_app, headless_app_with, spawn_kind_at};
+
+fn enemy_kinds(app: &mut App) -> Vec<String> {
+    let mut kinds: Vec<String> = app
+        .world
+        .query::<&Enemy>()
+        .iter(&app.world)
+        .map(|enemy| enemy.kind.clone())
+        .collect();
+    kinds.sort();
+    kinds
+}
+
+#[test]
+fn shipped_enemy_file_is_valid_and_has_every_behavior() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(ENEMY_KINDS_PATH);
+    let registry = EnemyRegistry::load(&path).expect("assets/enemies.ron deveria ser válido");
+    assert_eq!(registry, EnemyRegistry::default());
+
+    let has = |check: fn(&Behavior) -> bool| registry.kinds.iter().any(|kind| check(&kind.behavior));
+    assert!(has(|behavior| matches!(behavior, Behavior::Chaser)));
+    assert!(has(|behavior| matches!(behavior, Behavior::Wanderer { .. })));
+    assert!(has(|behavior| matches!(behavior, Behavior::Charger { .. })));
+    assert!(has(|behavior| matches!(behavior, Behavior::Shooter { .. })));
+    assert!(has(|behavior| matches!(behavior, Behavior::Splitter { .. })));
+}
+
+#[test]
+fn references_to_missing_kinds_are_rejected() {
+    let text = r#"(
+        kinds: [
+            (name: "a", size: 10.0, speed: 1.0, health: 1.0, color: (1.0, 1.0, 1.0),
+             contact_damage: 1, behavior: Splitter(into: "b", count: 2)),
+        ],
+        spawn_tables: [],
+    )"#;
+    let err = EnemyRegistry::from_ron(text).unwrap_err();
+    assert!(matches!(err, EnemyKindsError::Invalid { ref kind, .. } if kind == "a"));
+
+    let text = r#"(
+        kinds: [],
+        spawn_tables: [(count: 1, entries: [(kind: "ghost", weight: 1)])],
+    )"#;
+    let err = EnemyRegistry::from_ron(text).unwrap_err();
+    assert!(err.to_string().contains("ghost"));
+}
+
+#[test]
+fn central_room_uses_its_own_spawn_table() {
+    let mut app = headless_app_with(|app| {
+        app.insert_resource(SeedOverride(7));
+    });
+    let registry = app.world.resource::<EnemyRegistry>().clone();
+    let table = registry.table_for(RoomId::Central).unwrap();
+    assert_eq!(table.room, Some(RoomId::Central));
+
+    let kinds = enemy_kinds(&mut app);
+    assert_eq!(kinds.len(), table.count as usize);
+    for kind in kinds {
+        assert!(table.entries.iter().any(|entry| entry.kind == kind), "{kind} fora da tabela");
+    }
+
+    // Salas sem tabela própria usam a geral
+    assert_eq!(registry.table_for(RoomId::Generated(3)).unwrap().room, None);
+}
+
+#[test]
+fn rooms_without_free_space_skip_their_enemies() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+    // Sem pontos de aparição e com o jogador "perto" de tudo, nenhum sorteio serve
+    app.world.resource_mut::<RoomGraph>().get_room_mut(RoomId::Central).unwrap().template = "open".to_string();
+    app.world.resource_mut::<GameTuning>().spawn_safe_radius = f32::MAX;
+
+    app.world.run_system_once(|mut commands: Commands, mut spawner: RoomEnemySpawner| {
+        spawner.spawn_room(&mut commands, RoomId::Central, Vec2::ZERO);
+    });
+    assert!(enemy_kinds(&mut app).is_empty());
+}
+
+#[test]
+fn splitter_breaks_into_smaller_enemies() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+    let splitter = spawn_kind_at(&mut app, "splitter", Vec2::new(300.0, 200.0));
+
+    app.world.send_event(DamageEvent::hit(splitter, 100.0));
+    app.update();
+
+    assert!(app.world.get_entity(splitter).is_none());
+    assert_eq!(enemy_kinds(&mut app), vec!["splitling".to_string(), "splitling".to_string()]);
+}
+
+#[test]
+fn shooter_keeps_distance_and_fires() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+    let shooter = spawn_kind_at(&mut app, "shooter", Vec2::new(100.0, 0.0));
+
+    for _ in 0..20 {
+        app.update();
+    }
+
+    let position = app.world.get::<Transform>(shooter).unwrap().translation;
+    assert!(position.x > 100.0, "atirador deveria se afastar do jogador: {position}");
+    let bullets = app.world.query::<&EnemyBullet>().iter(&app.world).count();
+    assert!(bullets > 0);
+}
@@ -0,0 +1,108 @@
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use rust_eze::config::FloorTuning;
+use rust_eze::floor::generate_floor;
+use rust_eze::rooms::{Direction, RoomGraph, RoomId, RoomKind};
+
+fn floor(seed: u64, params: &FloorTuning) -> RoomGraph {
+    generate_floor(params, &mut ChaCha8Rng::seed_from_u64(seed))
+}
+
+/// Lista de (sala, posição, tipo, vizinhos) para comparar andares.
+fn layout(graph: &RoomGraph) -> Vec<(RoomId, IVec2, RoomKind, [Option<RoomId>; 4])> {
+    graph
+        .room_ids()
+        .into_iter()
+        .map(|id| {
+            let room = graph.get_room(id).unwrap();
+            (id, room.position, room.kind, Direction::ALL.map(|direction| room.neighbor(direction)))
+        })
+        .collect()
+}
+
+#[test]
+fn generated_floors_are_connected_and_symmetric() {
+    let params = FloorTuning::default();
+    for seed in 0..50 {
+        let graph = floor(seed, &params);
+        assert_eq!(graph.len(), params.room_count, "semente {seed}");
+
+        // Toda sala é alcançável a partir da inicial
+        let distances = graph.distances_from(RoomId::Central);
+        assert_eq!(distances.len(), graph.len(), "semente {seed}: andar desconexo");
+
+        let mut positions = HashSet::new();
+        let mut doors = 0;
+        for room in graph.rooms() {
+            assert!(positions.insert(room.position), "semente {seed}: duas salas em {}", room.position);
+            assert_eq!(graph.room_at(room.position), Some(room.id));
+
+            for direction in Direction::ALL {
+                let Some(other) = room.neighbor(direction) else {
+                    continue;
+                };
+                doors += 1;
+                // A porta existe dos dois lados e liga células vizinhas na grade
+                let other_room = graph.get_room(other).unwrap();
+                assert_eq!(other_room.neighbor(direction.opposite()), Some(room.id), "semente {seed}");
+                assert_eq!(other_room.position, room.position + direction.as_ivec2(), "semente {seed}");
+            }
+        }
+
+        // Cada ciclo é uma ligação a mais que a árvore geradora
+        let links = doors / 2;
+        assert!(links + 1 - graph.len() >= params.loops, "semente {seed}: sem ciclos");
+    }
+}
+
+#[test]
+fn special_rooms_are_dead_ends_at_their_distances() {
+    let params = FloorTuning::default();
+    for seed in 0..50 {
+        let graph = floor(seed, &params);
+        let distances = graph.distances_from(RoomId::Central);
+        let of_kind = |kind: RoomKind| graph.rooms().filter(|room| room.kind == kind).collect::<Vec<_>>();
+
+        assert_eq!(graph.get_room(RoomId::Central).unwrap().kind, RoomKind::Start);
+        for (kind, min_distance) in [
+            (RoomKind::Boss, params.boss_distance),
+            (RoomKind::Treasure, params.treasure_distance),
+            (RoomKind::Shop, params.shop_distance),
+        ] {
+            let rooms = of_kind(kind);
+            assert_eq!(rooms.len(), 1, "semente {seed}: {kind:?}");
+            assert_eq!(rooms[0].connection_count(), 1, "semente {seed}: {kind:?} não é beco");
+            assert!(distances[&rooms[0].id] >= min_distance, "semente {seed}: {kind:?} perto demais");
+        }
+
+        // Nenhuma sala fica mais longe que o chefe entre os becos
+        let boss = of_kind(RoomKind::Boss)[0].id;
+        let dead_ends = graph.rooms().filter(|room| room.kind != RoomKind::Start && room.connection_count() == 1);
+        assert!(dead_ends.into_iter().all(|room| distances[&room.id] <= distances[&boss]));
+    }
+}
+
+#[test]
+fn same_seed_generates_the_same_floor() {
+    let params = FloorTuning::default();
+    assert_eq!(layout(&floor(7, &params)), layout(&floor(7, &params)));
+    assert_ne!(layout(&floor(7, &params)), layout(&floor(8, &params)));
+}
+
+#[test]
+fn rooms_cannot_overlap_and_connect_links_existing_neighbors() {
+    let mut graph = RoomGraph::new();
+    let east = graph.add_room(RoomId::Central, Direction::East).unwrap();
+    let north_east = graph.add_room(east, Direction::North).unwrap();
+    let north = graph.add_room(north_east, Direction::West).unwrap();
+
+    // A célula ao norte da central já tem sala
+    assert_eq!(graph.add_room(RoomId::Central, Direction::North), None);
+    assert_eq!(graph.connect(RoomId::Central, Direction::North), Some(north));
+    assert_eq!(graph.get_room(north).unwrap().neighbor(Direction::South), Some(RoomId::Central));
+    // Sem sala na célula, não há o que ligar
+    assert_eq!(graph.connect(RoomId::Central, Direction::West), None);
+}
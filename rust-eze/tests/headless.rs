@@ -0,0 +1,49 @@
+mod common;
+
+use bevy::prelude::*;
+use rust_eze::scoring::Score;
+
+use common::{clear_enemies, headless_app, health, lives, player_position, spawn_enemy_at};
+
+#[test]
+fn player_moves_right_while_d_is_held() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+
+    let start = player_position(&mut app);
+    app.world.resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::KeyD);
+    for _ in 0..5 {
+        app.update();
+    }
+
+    let end = player_position(&mut app);
+    assert!(end.x > start.x, "jogador deveria ter andado para a direita: {start} -> {end}");
+    assert_eq!(end.y, start.y);
+}
+
+#[test]
+fn enemy_contact_costs_health_and_pushes_the_player_away() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+    assert_eq!(lives(&mut app), 3);
+
+    spawn_enemy_at(&mut app, Vec2::new(5.0, 0.0));
+    app.update();
+
+    assert_eq!(lives(&mut app), 3, "só perde uma vida quando os pontos de vida acabam");
+    assert_eq!(health(&mut app), 2);
+    assert!(player_position(&mut app).x < 0.0, "empurrado para longe do inimigo");
+}
+
+#[test]
+fn score_grows_while_alive() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+
+    let before = app.world.resource::<Score>().value;
+    for _ in 0..10 {
+        app.update();
+    }
+
+    assert!(app.world.resource::<Score>().value > before);
+}
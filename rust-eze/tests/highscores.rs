@@ -0,0 +1,155 @@
+mod common;
+
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use rust_eze::highscores::{
+    format_date, HighScoreEntry, HighScores, HighScoresError, HighScoresFile, NameEntry, RunStats, MAX_HIGH_SCORES,
+};
+use rust_eze::state::AppState;
+use rust_eze::scoring::Score;
+
+use common::{headless_app_with, state, tap};
+
+fn entry(name: &str, score: u32) -> HighScoreEntry {
+    HighScoreEntry {
+        name: name.to_string(),
+        score,
+        rooms_cleared: 3,
+        enemies_killed: 12,
+        duration: 95.5,
+        seed: 42,
+        timestamp: 1_760_000_000,
+    }
+}
+
+fn names(high_scores: &HighScores) -> Vec<&str> {
+    high_scores.entries().iter().map(|entry| entry.name.as_str()).collect()
+}
+
+/// Pasta de recordes só deste teste, apagada no fim.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("rust-eze-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        TempDir(path)
+    }
+
+    fn file(&self) -> PathBuf {
+        self.0.join("highscores.ron")
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn entries_are_ranked_by_score_and_capped() {
+    let mut high_scores = HighScores::default();
+    assert_eq!(high_scores.insert(entry("B", 200)), Some(0));
+    assert_eq!(high_scores.insert(entry("A", 300)), Some(0));
+    assert_eq!(high_scores.insert(entry("C", 100)), Some(2));
+    // Empate: quem chegou antes fica na frente
+    assert_eq!(high_scores.insert(entry("B2", 200)), Some(2));
+    assert_eq!(names(&high_scores), vec!["A", "B", "B2", "C"]);
+
+    for i in 0..MAX_HIGH_SCORES {
+        high_scores.insert(entry(&format!("X{i}"), 150));
+    }
+    assert_eq!(high_scores.entries().len(), MAX_HIGH_SCORES);
+    assert_eq!(high_scores.entries().last().unwrap().score, 150, "o menor saiu da tabela");
+
+    // Tabela cheia: só entra quem passa do último
+    assert!(!high_scores.qualifies(150));
+    assert_eq!(high_scores.insert(entry("Y", 150)), None);
+    assert!(high_scores.qualifies(151));
+}
+
+#[test]
+fn table_survives_a_round_trip_through_a_file() {
+    let dir = TempDir::new("highscores-roundtrip");
+    let mut high_scores = HighScores::default();
+    high_scores.insert(entry("ANA", 900));
+    high_scores.insert(entry("BIA", 450));
+    // Sementes sorteadas usam os 64 bits inteiros
+    high_scores.insert(HighScoreEntry { seed: u64::MAX, ..entry("CAU", 10) });
+
+    // A pasta ainda não existe
+    high_scores.save(&dir.file()).unwrap();
+    assert_eq!(HighScores::load(&dir.file()).unwrap(), high_scores);
+}
+
+#[test]
+fn broken_entries_are_dropped_and_broken_files_rejected() {
+    let text = r#"(
+        version: 1,
+        entries: [
+            (name: "OK", score: 10, rooms_cleared: 1, enemies_killed: 2, duration: 3.0, seed: 4, timestamp: 5),
+            (name: "SEM PONTOS", rooms_cleared: 1),
+            (name: "TOP", score: 99, rooms_cleared: 1, enemies_killed: 2, duration: 3.0, seed: 4, timestamp: 5),
+        ],
+    )"#;
+    let high_scores = HighScores::from_ron(text).unwrap();
+    assert_eq!(names(&high_scores), vec!["TOP", "OK"]);
+
+    assert!(matches!(HighScores::from_ron("(version: 1, entries: [(name: "), Err(HighScoresError::Parse(_))));
+    assert!(matches!(
+        HighScores::from_ron("(version: 7, entries: [])"),
+        Err(HighScoresError::UnsupportedVersion(7))
+    ));
+}
+
+#[test]
+fn a_corrupted_file_is_backed_up_and_the_table_starts_empty() {
+    let dir = TempDir::new("highscores-corrupted");
+    std::fs::create_dir_all(&dir.0).unwrap();
+    std::fs::write(dir.file(), "lixo").unwrap();
+
+    let app = headless_app_with(|app| {
+        app.insert_resource(HighScoresFile { path: dir.file() });
+    });
+    assert!(app.world.resource::<HighScores>().entries().is_empty());
+    assert!(dir.file().with_extension("ron.bak").exists());
+}
+
+#[test]
+fn game_over_asks_for_a_name_and_saves_the_table() {
+    let dir = TempDir::new("highscores-game-over");
+    let mut app = headless_app_with(|app| {
+        app.insert_resource(HighScoresFile { path: dir.file() });
+    });
+    app.world.resource_mut::<Score>().value = 777;
+    app.world.resource_mut::<RunStats>().enemies_killed = 5;
+    app.world.resource_mut::<NextState<AppState>>().set(AppState::GameOver);
+    app.update();
+    assert!(app.world.get_resource::<NameEntry>().is_some());
+
+    for key in [KeyCode::KeyJ, KeyCode::KeyO, KeyCode::KeyX, KeyCode::Backspace, KeyCode::KeyE, KeyCode::Enter] {
+        tap(&mut app, key);
+    }
+    assert_eq!(state(&app), AppState::GameOver, "Enter só confirmou o nome");
+    assert!(app.world.get_resource::<NameEntry>().is_none());
+
+    let saved = HighScores::load(&dir.file()).unwrap();
+    assert_eq!(names(&saved), vec!["JOE"]);
+    assert_eq!(saved.entries()[0].score, 777);
+    assert_eq!(saved.entries()[0].enemies_killed, 5);
+
+    tap(&mut app, KeyCode::Enter);
+    assert_eq!(state(&app), AppState::Playing);
+    let stats = app.world.resource::<RunStats>();
+    assert_eq!(stats.enemies_killed, 0);
+    assert!(stats.duration < 1.0, "a partida nova conta do zero");
+}
+
+#[test]
+fn dates_are_formatted_in_utc() {
+    assert_eq!(format_date(0), "1970-01-01");
+    assert_eq!(format_date(951_782_400), "2000-02-29");
+    assert_eq!(format_date(1_792_281_600), "2026-10-18");
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use bevy::prelude::*;
+use rust_eze::arena::ArenaSize;
+use rust_eze::config::GameTuning;
+use rust_eze::player::Player;
+use rust_eze::rooms::{CurrentRoom, Direction, RoomGraph, RoomId};
+use rust_eze::ui::{FullMap, MapCell, Minimap};
+
+use common::{clear_enemies, headless_app, tap};
+
+/// Salas desenhadas no minimapa.
+fn minimap_rooms(app: &mut App) -> Vec<RoomId> {
+    let minimap = app.world.query_filtered::<Entity, With<Minimap>>().single(&app.world);
+    let mut rooms: Vec<RoomId> = app
+        .world
+        .query::<(&MapCell, &Parent)>()
+        .iter(&app.world)
+        .filter(|(_, parent)| parent.get() == minimap)
+        .map(|(cell, _)| cell.room)
+        .collect();
+    rooms.sort();
+    rooms
+}
+
+fn revealed_rooms(app: &App) -> Vec<RoomId> {
+    let graph = app.world.resource::<RoomGraph>();
+    graph
+        .room_ids()
+        .into_iter()
+        .filter(|id| graph.get_room(*id).unwrap().revealed)
+        .collect()
+}
+
+#[test]
+fn start_room_is_visited_and_its_neighbors_revealed() {
+    let mut app = headless_app();
+    let graph = app.world.resource::<RoomGraph>();
+    let central = graph.get_room(RoomId::Central).unwrap();
+    assert!(central.visited);
+
+    for room in graph.rooms() {
+        let is_neighbor = Direction::ALL.into_iter().any(|direction| central.neighbor(direction) == Some(room.id));
+        assert_eq!(room.revealed, room.id == RoomId::Central || is_neighbor, "{:?}", room.id);
+        assert_eq!(room.visited, room.id == RoomId::Central, "{:?}", room.id);
+    }
+    assert_eq!(minimap_rooms(&mut app), revealed_rooms(&app));
+}
+
+#[test]
+fn minimap_follows_the_current_room() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+
+    // Central -> leste -> leste: a terceira sala só aparece depois de entrar na segunda
+    let mut graph = RoomGraph::new();
+    let east = graph.add_room(RoomId::Central, Direction::East).unwrap();
+    let far_east = graph.add_room(east, Direction::East).unwrap();
+    app.insert_resource(graph);
+    app.update();
+    assert_eq!(minimap_rooms(&mut app), vec![RoomId::Central, east]);
+
+    let arena = *app.world.resource::<ArenaSize>();
+    let tuning = app.world.resource::<GameTuning>().clone();
+    let bounds = arena.bounds(tuning.wall_thickness, tuning.player_size);
+    app.world
+        .query_filtered::<&mut Transform, With<Player>>()
+        .single_mut(&mut app.world)
+        .translation = Vec3::new(bounds.x, 0.0, 0.0);
+    for _ in 0..3 {
+        app.update();
+    }
+
+    assert_eq!(app.world.resource::<CurrentRoom>().id, east);
+    assert!(app.world.resource::<RoomGraph>().get_room(east).unwrap().visited);
+    assert_eq!(minimap_rooms(&mut app), vec![RoomId::Central, east, far_east]);
+}
+
+#[test]
+fn map_key_toggles_the_full_map() {
+    let mut app = headless_app();
+    let full_maps = |app: &mut App| app.world.query_filtered::<Entity, With<FullMap>>().iter(&app.world).count();
+    assert_eq!(full_maps(&mut app), 0);
+
+    tap(&mut app, KeyCode::KeyM);
+    assert_eq!(full_maps(&mut app), 1);
+
+    tap(&mut app, KeyCode::KeyM);
+    assert_eq!(full_maps(&mut app), 0);
+}
@@ -0,0 +1,165 @@
+mod common;
+
+use bevy::prelude::*;
+use rust_eze::collision::{Collider, Layers};
+use rust_eze::config::GameTuning;
+use rust_eze::enemies::EnemyBullet;
+use rust_eze::movement::{Dash, DashTrail, Dashing, Velocity};
+use rust_eze::player::{Invulnerable, Player};
+use rust_eze::rooms::{CurrentRoom, RoomMember};
+
+use common::{clear_enemies, headless_app, health, player_position, spawn_enemy_at};
+
+fn hold(app: &mut App, keys: &[KeyCode]) {
+    let mut keyboard = app.world.resource_mut::<ButtonInput<KeyCode>>();
+    for key in keys {
+        keyboard.press(*key);
+    }
+}
+
+fn release_all(app: &mut App) {
+    let mut keyboard = app.world.resource_mut::<ButtonInput<KeyCode>>();
+    keyboard.release_all();
+    keyboard.clear();
+}
+
+fn velocity(app: &mut App) -> Vec2 {
+    app.world.query_filtered::<&Velocity, With<Player>>().single(&app.world).0
+}
+
+fn is_dashing(app: &mut App) -> bool {
+    app.world.query::<&Dash>().single(&app.world).is_dashing()
+}
+
+fn trail_count(app: &mut App) -> usize {
+    app.world.query::<&DashTrail>().iter(&app.world).count()
+}
+
+fn place_player(app: &mut App, position: Vec2) {
+    let mut transform = app.world.query_filtered::<&mut Transform, With<Player>>().single_mut(&mut app.world);
+    transform.translation = position.extend(0.0);
+}
+
+/// Segura as teclas por dois quadros e devolve quanto o jogador andou.
+fn distance_moved(keys: &[KeyCode]) -> f32 {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+    let start = player_position(&mut app);
+    hold(&mut app, keys);
+    app.update();
+    app.update();
+    player_position(&mut app).distance(start)
+}
+
+#[test]
+fn diagonal_movement_is_as_fast_as_straight_movement() {
+    let straight = distance_moved(&[KeyCode::KeyD]);
+    let diagonal = distance_moved(&[KeyCode::KeyD, KeyCode::KeyW]);
+    assert!(straight > 0.0);
+    assert!((diagonal - straight).abs() < 1.0, "reta {straight}, diagonal {diagonal}");
+}
+
+#[test]
+fn the_player_accelerates_and_slides_to_a_stop() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+    let tuning = app.world.resource::<GameTuning>().clone();
+
+    // Acelerando devagar, o primeiro quadro fica longe da velocidade toda
+    app.world.resource_mut::<GameTuning>().player_acceleration = tuning.player_speed;
+    hold(&mut app, &[KeyCode::KeyD]);
+    app.update();
+    let ramping = velocity(&mut app);
+    assert!(ramping.x > 0.0 && ramping.x < tuning.player_speed * 0.2, "acelerando: {ramping}");
+    for _ in 0..10 {
+        app.update();
+    }
+    assert!((velocity(&mut app).x - tuning.player_speed).abs() < 1e-3);
+
+    // Soltando, o jogador ainda desliza um pouco antes de parar
+    release_all(&mut app);
+    let released_at = player_position(&mut app);
+    app.update();
+    assert!(player_position(&mut app).x > released_at.x, "deslizou de {released_at}");
+    assert!(velocity(&mut app).x > 0.0, "2400 px/s² levam 0.125 s para parar 300 px/s");
+    app.update();
+    assert_eq!(velocity(&mut app), Vec2::ZERO);
+    let stopped = player_position(&mut app);
+    app.update();
+    assert_eq!(player_position(&mut app), stopped);
+}
+
+#[test]
+fn dash_crosses_enemies_without_damage() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+    let full = health(&mut app);
+    let enemy = spawn_enemy_at(&mut app, Vec2::new(100.0, 0.0));
+
+    hold(&mut app, &[KeyCode::KeyD, KeyCode::ShiftLeft]);
+    app.update();
+    // A arrancada começa no passo fixo seguinte ao aperto
+    app.world.resource_mut::<ButtonInput<KeyCode>>().release(KeyCode::ShiftLeft);
+    app.world.resource_mut::<ButtonInput<KeyCode>>().clear();
+    app.update();
+    assert!(is_dashing(&mut app));
+    assert_eq!(app.world.query_filtered::<(), (With<Player>, With<Dashing>)>().iter(&app.world).count(), 1);
+    assert!(trail_count(&mut app) > 0, "a arrancada deixa rastro");
+
+    // No começo da arrancada, um tiro que acerta some sem causar dano
+    let room = app.world.resource::<CurrentRoom>().id;
+    let at = player_position(&mut app);
+    let bullet = app
+        .world
+        .spawn((
+            EnemyBullet { velocity: Vec2::ZERO, damage: 2 },
+            RoomMember(room),
+            Collider::circle(5.0, Layers::ENEMY_PROJECTILE, Layers::PLAYER),
+            Transform::from_xyz(at.x + 10.0, at.y, 0.0),
+        ))
+        .id();
+    app.update();
+    assert!(app.world.get_entity(bullet).is_none());
+    assert_eq!(app.world.query_filtered::<(), (With<Player>, With<Invulnerable>)>().iter(&app.world).count(), 0);
+
+    let player = player_position(&mut app);
+    let enemy = app.world.get::<Transform>(enemy).unwrap().translation;
+    assert!(player.x > enemy.x, "o jogador em {player} passou pelo inimigo em {enemy}");
+    assert!(player.x > 150.0, "a arrancada vai longe: {player}");
+    assert_eq!(health(&mut app), full);
+    assert!(!is_dashing(&mut app));
+    assert_eq!(app.world.query::<&Dashing>().iter(&app.world).count(), 0);
+}
+
+#[test]
+fn dash_stops_at_the_wall_and_waits_for_the_cooldown() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+    let tuning = app.world.resource::<GameTuning>().clone();
+    // Longe das portas, perto da parede de cima
+    place_player(&mut app, Vec2::new(300.0, 250.0));
+
+    hold(&mut app, &[KeyCode::KeyW, KeyCode::ShiftLeft]);
+    app.update();
+    release_all(&mut app);
+    app.update();
+    app.update();
+    let top = (720.0 - tuning.wall_thickness - tuning.player_size) / 2.0;
+    assert!((player_position(&mut app).y - top).abs() < 1e-3, "parou na parede: {}", player_position(&mut app));
+    assert_eq!(velocity(&mut app).y, 0.0);
+
+    // A arrancada durou 0.15 s; ainda faltam 0.6 s para a próxima
+    let dash_cooldown = app.world.query::<&Dash>().single(&app.world).cooldown();
+    assert!(dash_cooldown > 0.0);
+    hold(&mut app, &[KeyCode::ShiftLeft]);
+    app.update();
+    release_all(&mut app);
+    app.update();
+    assert!(!is_dashing(&mut app), "ainda esperando o intervalo");
+
+    // O rastro some sozinho
+    for _ in 0..5 {
+        app.update();
+    }
+    assert_eq!(trail_count(&mut app), 0);
+}
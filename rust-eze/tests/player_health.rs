@@ -0,0 +1,105 @@
+mod common;
+
+use bevy::prelude::*;
+use rust_eze::collision::{Collider, Layers};
+use rust_eze::config::GameTuning;
+use rust_eze::enemies::EnemyBullet;
+use rust_eze::game::PlayerDamaged;
+use rust_eze::lives::Lives;
+use rust_eze::player::{Invulnerable, Player};
+use rust_eze::rooms::{CurrentRoom, RoomMember};
+use rust_eze::state::AppState;
+
+use common::{clear_enemies, headless_app, health, lives, player_position, spawn_kind_at, state, tap};
+
+fn damage_events(app: &mut App) -> Vec<PlayerDamaged> {
+    app.world.resource_mut::<Events<PlayerDamaged>>().drain().collect()
+}
+
+fn is_invulnerable(app: &mut App) -> bool {
+    app.world.query_filtered::<(), (With<Player>, With<Invulnerable>)>().iter(&app.world).count() == 1
+}
+
+#[test]
+fn losing_all_health_costs_one_life_and_refills_it() {
+    let mut lives = Lives::new(3, 3);
+    assert!(!lives.take_damage(2));
+    assert_eq!((lives.count(), lives.health()), (3, 1));
+
+    // O dano que sobra não passa para a próxima vida
+    assert!(!lives.take_damage(5));
+    assert_eq!((lives.count(), lives.health()), (2, 3));
+
+    lives.lose_life();
+    assert!(lives.take_damage(3), "era a última vida");
+    assert_eq!(lives.count(), 0);
+}
+
+#[test]
+fn hits_come_from_the_enemy_and_invulnerability_blocks_the_next_ones() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+    app.world.resource_mut::<GameTuning>().player_health = 5;
+    *app.world.query::<&mut Lives>().single_mut(&mut app.world) = Lives::new(3, 5);
+
+    // O charger tira 2 pontos de vida e está acima do jogador
+    spawn_kind_at(&mut app, "charger", Vec2::new(0.0, 10.0));
+    app.update();
+    let events = damage_events(&mut app);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].amount, 2);
+    assert_eq!(health(&mut app), 3);
+    assert!(is_invulnerable(&mut app));
+    assert!(player_position(&mut app).y < 0.0, "empurrado para baixo, longe do inimigo");
+
+    // Ainda encostando, mas invulnerável
+    app.update();
+    assert!(damage_events(&mut app).is_empty());
+    assert_eq!(health(&mut app), 3);
+
+    // Passada a invulnerabilidade, o jogador volta a aparecer
+    clear_enemies(&mut app);
+    for _ in 0..12 {
+        app.update();
+    }
+    assert!(!is_invulnerable(&mut app));
+    let visibility = *app.world.query_filtered::<&Visibility, With<Player>>().single(&app.world);
+    assert_eq!(visibility, Visibility::Inherited);
+}
+
+#[test]
+fn enemy_bullets_deal_their_damage_and_disappear() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+    let room = app.world.resource::<CurrentRoom>().id;
+    let bullet = app
+        .world
+        .spawn((
+            EnemyBullet { velocity: Vec2::ZERO, damage: 2 },
+            RoomMember(room),
+            Collider::circle(5.0, Layers::ENEMY_PROJECTILE, Layers::PLAYER),
+            Transform::from_xyz(10.0, 0.0, 0.0),
+        ))
+        .id();
+    app.update();
+
+    assert_eq!(health(&mut app), 1);
+    assert!(app.world.get_entity(bullet).is_none());
+}
+
+#[test]
+fn starting_lives_and_health_come_from_the_tuning() {
+    let mut app = headless_app();
+    {
+        let mut tuning = app.world.resource_mut::<GameTuning>();
+        tuning.starting_lives = 2;
+        tuning.player_health = 4;
+    }
+    app.world.resource_mut::<NextState<AppState>>().set(AppState::GameOver);
+    app.update();
+    tap(&mut app, KeyCode::Enter);
+    tap(&mut app, KeyCode::Enter);
+    assert_eq!(state(&app), AppState::Playing);
+    assert_eq!(lives(&mut app), 2);
+    assert_eq!(health(&mut app), 4);
+}
@@ -0,0 +1,153 @@
+use bevy::input::gamepad::{GamepadAxis, GamepadAxisType};
+use bevy::prelude::*;
+use rust_eze::RustEzeHeadlessPlugin;
+use rust_eze::actions::{Action, Binding, InputBindings, Stick, StickAction};
+use rust_eze::aim::Aim;
+use rust_eze::config::GameTuning;
+use rust_eze::player::Player;
+use rust_eze::projectiles::Projectile;
+use rust_eze::replay::{self, Replay, ReplayError, DEFAULT_TIMESTEP};
+use rust_eze::rng::SeedOverride;
+
+/// Roda um quadro com as teclas informadas pressionadas, imitando o
+/// `InputPlugin`, que limpa `just_pressed`/`just_released` a cada quadro.
+fn frame(app: &mut App, held: &[KeyCode]) {
+    {
+        let mut keyboard = app.world.resource_mut::<ButtonInput<KeyCode>>();
+        let released: Vec<KeyCode> = keyboard.get_pressed().copied().filter(|key| !held.contains(key)).collect();
+        keyboard.clear();
+        for key in released {
+            keyboard.release(key);
+        }
+        for &key in held {
+            keyboard.press(key);
+        }
+    }
+    app.update();
+}
+
+fn record_run() -> Replay {
+    let mut app = App::new();
+    app.add_plugins(RustEzeHeadlessPlugin)
+        .insert_resource(SeedOverride(2024));
+    replay::configure_recording(&mut app, None, DEFAULT_TIMESTEP);
+
+    frame(&mut app, &[]);
+    frame(&mut app, &[KeyCode::Enter]);
+    frame(&mut app, &[]);
+    for _ in 0..20 {
+        frame(&mut app, &[KeyCode::KeyD, KeyCode::KeyW]);
+    }
+    frame(&mut app, &[KeyCode::ArrowUp]);
+    for _ in 0..15 {
+        frame(&mut app, &[KeyCode::KeyA]);
+    }
+    frame(&mut app, &[KeyCode::Space]);
+    for _ in 0..30 {
+        frame(&mut app, &[]);
+    }
+
+    replay::finish_recording(&mut app.world).expect("gravação ativa")
+}
+
+#[test]
+fn recorded_run_replays_to_same_outcome() {
+    let replay = record_run();
+    assert_eq!(replay.frames, 70);
+    assert_eq!(replay.seed, 2024);
+    assert!(!replay.inputs.is_empty());
+
+    let outcome = replay::run_headless(replay.clone()).expect("replay deveria bater");
+    assert_eq!(Some(outcome), replay.outcome);
+}
+
+#[test]
+fn tampered_outcome_is_reported() {
+    let mut replay = record_run();
+    if let Some(outcome) = replay.outcome.as_mut() {
+        outcome.score += 1;
+    }
+
+    let err = replay::run_headless(replay).unwrap_err();
+    assert!(matches!(err, ReplayError::Mismatch { .. }));
+}
+
+#[test]
+fn replay_file_round_trips() {
+    let replay = record_run();
+    let path = std::env::temp_dir().join(format!("rust-eze-replay-{}.ron", std::process::id()));
+
+    replay.save(&path).unwrap();
+    let loaded = Replay::load(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(loaded, replay);
+}
+
+/// Posição do jogador e dos projéteis, para comparar gravação e reprodução.
+fn positions(app: &mut App) -> (Vec2, Vec<Vec2>) {
+    let player = app.world.query_filtered::<&Transform, With<Player>>().single(&app.world).translation.xy();
+    let mut projectiles: Vec<Vec2> = app
+        .world
+        .query_filtered::<&Transform, With<Projectile>>()
+        .iter(&app.world)
+        .map(|transform| transform.translation.xy())
+        .collect();
+    projectiles.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    (player, projectiles)
+}
+
+#[test]
+fn sticks_and_mouse_aim_replay_with_other_bindings() {
+    let mut recording = App::new();
+    recording.add_plugins(RustEzeHeadlessPlugin).insert_resource(SeedOverride(7));
+    replay::configure_recording(&mut recording, None, DEFAULT_TIMESTEP);
+    frame(&mut recording, &[]);
+    frame(&mut recording, &[KeyCode::Enter]);
+    frame(&mut recording, &[]);
+
+    // Stick esquerdo do controle, que o teclado não tem como imitar
+    let pad = Gamepad { id: 0 };
+    let mut axes = recording.world.resource_mut::<Axis<GamepadAxis>>();
+    axes.set(GamepadAxis::new(pad, GamepadAxisType::LeftStickX), 0.0);
+    axes.set(GamepadAxis::new(pad, GamepadAxisType::LeftStickY), -0.6);
+    for _ in 0..10 {
+        frame(&mut recording, &[]);
+    }
+    recording.world.resource_mut::<Axis<GamepadAxis>>().set(GamepadAxis::new(pad, GamepadAxisType::LeftStickY), 0.0);
+
+    // Mira para a esquerda, como o cursor do mouse faria, e atira
+    recording.world.resource_mut::<Aim>().direction = Vec2::NEG_X;
+    frame(&mut recording, &[]);
+    for _ in 0..5 {
+        frame(&mut recording, &[KeyCode::Space]);
+    }
+    frame(&mut recording, &[]);
+    let recorded = positions(&mut recording);
+    assert!(recorded.0.y < 0.0, "o stick moveu para baixo: {}", recorded.0);
+    assert!(!recorded.1.is_empty() && recorded.1.iter().all(|shot| shot.x < recorded.0.x));
+    let replay = replay::finish_recording(&mut recording.world).unwrap();
+
+    // Sem controle, com o tiro em outra tecla e os sticks trocados
+    let mut bindings = InputBindings::default();
+    bindings.bind(Action::Fire, Binding::Key(KeyCode::KeyF));
+    bindings.bind_stick(StickAction::Move, Stick::Right);
+    let mut playback = App::new();
+    playback.add_plugins(RustEzeHeadlessPlugin).insert_resource(bindings);
+    replay::configure_playback(&mut playback, replay.clone());
+    for _ in 0..replay.frames {
+        playback.update();
+    }
+    assert_eq!(positions(&mut playback), recorded);
+}
+
+#[test]
+fn replays_recorded_with_other_tuning_are_rejected() {
+    let mut replay = record_run();
+    let shipped = GameTuning::load(std::path::Path::new("assets/tuning.ron")).unwrap();
+    assert_eq!(replay.tuning, shipped);
+    replay.tuning.player_speed += 1.0;
+
+    let err = replay::run_headless(replay).unwrap_err();
+    assert!(matches!(err, ReplayError::TuningMismatch));
+}
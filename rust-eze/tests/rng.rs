@@ -0,0 +1,51 @@
+mod common;
+
+use bevy::prelude::*;
+use rust_eze::cli::{CliArgs, CliError};
+use rust_eze::enemies::Enemy;
+use rust_eze::rng::{GameRng, SeedOverride};
+
+use common::headless_app_with;
+
+fn enemy_positions(seed: u64) -> Vec<Vec3> {
+    let mut app = headless_app_with(|app| {
+        app.insert_resource(SeedOverride(seed));
+    });
+    assert_eq!(app.world.resource::<GameRng>().seed(), seed);
+
+    let mut positions: Vec<Vec3> = app
+        .world
+        .query_filtered::<&Transform, With<Enemy>>()
+        .iter(&app.world)
+        .map(|transform| transform.translation)
+        .collect();
+    positions.sort_by(|a, b| a.x.total_cmp(&b.x));
+    positions
+}
+
+#[test]
+fn same_seed_places_enemies_identically() {
+    assert_eq!(enemy_positions(42), enemy_positions(42));
+    assert_ne!(enemy_positions(42), enemy_positions(43));
+}
+
+#[test]
+fn streams_are_independent() {
+    use rand::Rng;
+
+    let mut a = GameRng::new(7);
+    let mut b = GameRng::new(7);
+    for _ in 0..10 {
+        let _: u32 = a.loot.gen_range(0..100);
+    }
+    assert_eq!(a.enemies.gen_range(0..1000), b.enemies.gen_range(0..1000));
+}
+
+#[test]
+fn seed_flag_is_parsed() {
+    let args = CliArgs::parse(["--seed".to_string(), "1234".to_string()]).unwrap();
+    assert_eq!(args.seed, Some(1234));
+
+    let err = CliArgs::parse(["--seed".to_string(), "abc".to_string()]).unwrap_err();
+    assert!(matches!(err, CliError::InvalidValue { flag: "--seed", .. }));
+}
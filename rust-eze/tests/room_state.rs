@@ -0,0 +1,137 @@
+mod common;
+
+use bevy::prelude::*;
+use rust_eze::arena::ArenaSize;
+use rust_eze::combat::Health;
+use rust_eze::config::GameTuning;
+use rust_eze::enemies::Enemy;
+use rust_eze::player::Player;
+use rust_eze::projectiles::powerups::{spawn_powerup, PowerUp, PowerUpType};
+use rust_eze::rooms::{CurrentRoom, Direction, RoomGraph, RoomId, RoomMember};
+use rust_eze::state::AppState;
+
+use common::{clear_enemies, headless_app, spawn_kind_at, state};
+
+/// Abre uma sala vizinha à central e devolve a direção e o id dela.
+fn open_neighbor(app: &mut App) -> (Direction, RoomId) {
+    let mut graph = app.world.resource_mut::<RoomGraph>();
+    let central = graph.get_room(RoomId::Central).unwrap();
+    if let Some((direction, id)) = Direction::ALL
+        .into_iter()
+        .find_map(|direction| central.neighbor(direction).map(|id| (direction, id)))
+    {
+        return (direction, id);
+    }
+    let id = graph.add_room(RoomId::Central, Direction::North).unwrap();
+    (Direction::North, id)
+}
+
+/// Leva o jogador até a porta do lado dado e para logo depois de restaurar a
+/// sala nova, antes de os inimigos voltarem a se mexer.
+fn go_through(app: &mut App, direction: Direction) {
+    let arena = *app.world.resource::<ArenaSize>();
+    let tuning = app.world.resource::<GameTuning>().clone();
+    let bounds = arena.bounds(tuning.wall_thickness, tuning.player_size);
+    let normal = direction.as_vec2();
+
+    let mut transform = app
+        .world
+        .query_filtered::<&mut Transform, With<Player>>()
+        .single_mut(&mut app.world);
+    transform.translation = (normal * bounds.dot(normal.abs())).extend(0.0);
+
+    for _ in 0..5 {
+        app.update();
+        if state(app) == AppState::RoomTransition {
+            return;
+        }
+    }
+    panic!("o jogador não passou pela porta {direction:?}");
+}
+
+fn members_of(app: &mut App, room: RoomId) -> usize {
+    app.world
+        .query::<&RoomMember>()
+        .iter(&app.world)
+        .filter(|member| member.0 == room)
+        .count()
+}
+
+fn spawn_pickup_at(app: &mut App, powerup_type: PowerUpType, position: Vec2) {
+    let room = app.world.resource::<CurrentRoom>().id;
+    app.world.resource_scope(|world, mut meshes: Mut<Assets<Mesh>>| {
+        world.resource_scope(|world, mut materials: Mut<Assets<ColorMaterial>>| {
+            let mut queue = bevy::ecs::system::CommandQueue::default();
+            let mut commands = Commands::new(&mut queue, world);
+            spawn_powerup(&mut commands, powerup_type, position, room, &mut meshes, &mut materials);
+            queue.apply(world);
+        });
+    });
+}
+
+fn despawn_pickups(app: &mut App) {
+    let pickups: Vec<Entity> = app.world.query_filtered::<Entity, With<PowerUp>>().iter(&app.world).collect();
+    for pickup in pickups {
+        app.world.despawn(pickup);
+    }
+}
+
+#[test]
+fn leaving_a_room_despawns_its_entities_and_first_visit_spawns_enemies() {
+    let mut app = headless_app();
+    let (direction, neighbor) = open_neighbor(&mut app);
+    // Limpar a sala abre as portas; o que fica no chão também pertence a ela
+    clear_enemies(&mut app);
+    spawn_pickup_at(&mut app, PowerUpType::RotatingCircle, Vec2::new(150.0, -100.0));
+    app.update();
+    assert!(members_of(&mut app, RoomId::Central) > 0);
+
+    go_through(&mut app, direction);
+    app.update();
+
+    assert_eq!(app.world.resource::<CurrentRoom>().id, neighbor);
+    assert_eq!(members_of(&mut app, RoomId::Central), 0);
+    assert!(members_of(&mut app, neighbor) > 0, "a sala nova não tem inimigos");
+}
+
+#[test]
+fn returning_restores_enemies_and_pickups_as_they_were() {
+    let mut app = headless_app();
+    let (direction, _) = open_neighbor(&mut app);
+    // Sala já limpa e sem a recompensa, para sair com um inimigo vivo
+    clear_enemies(&mut app);
+    app.update();
+    despawn_pickups(&mut app);
+
+    let enemy = spawn_kind_at(&mut app, "chaser", Vec2::new(-200.0, 150.0));
+    app.world.get_mut::<Health>(enemy).unwrap().current = 2.0;
+    app.world.get_mut::<Enemy>(enemy).unwrap().speed = 0.0;
+    spawn_pickup_at(&mut app, PowerUpType::RotatingCircle, Vec2::new(150.0, -100.0));
+    app.update();
+    let enemy_position = app.world.get::<Transform>(enemy).unwrap().translation.xy();
+
+    go_through(&mut app, direction);
+    // Sai do jogo; só a imagem fica enquanto a câmera desliza
+    assert!(app.world.get::<Enemy>(enemy).is_none());
+    app.update();
+    clear_enemies(&mut app);
+    app.update();
+    go_through(&mut app, direction.opposite());
+    assert_eq!(app.world.resource::<CurrentRoom>().id, RoomId::Central);
+
+    let enemies: Vec<(String, Vec2, f32)> = app
+        .world
+        .query::<(&Enemy, &Transform, &Health)>()
+        .iter(&app.world)
+        .map(|(enemy, transform, health)| (enemy.kind.clone(), transform.translation.xy(), health.current))
+        .collect();
+    assert_eq!(enemies, vec![("chaser".to_string(), enemy_position, 2.0)]);
+
+    let pickups: Vec<(PowerUpType, Vec2)> = app
+        .world
+        .query::<(&PowerUp, &Transform)>()
+        .iter(&app.world)
+        .map(|(powerup, transform)| (powerup.powerup_type, transform.translation.xy()))
+        .collect();
+    assert_eq!(pickups, vec![(PowerUpType::RotatingCircle, Vec2::new(150.0, -100.0))]);
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use std::path::Path;
+
+use bevy::prelude::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use rust_eze::collision::Collider;
+use rust_eze::config::{FloorTuning, GameTuning};
+use rust_eze::floor::generate_floor;
+use rust_eze::player::Player;
+use rust_eze::room_templates::{RoomTemplates, RoomTemplatesError, ROOM_TEMPLATES_PATH};
+use rust_eze::rooms::{RoomGraph, RoomId};
+use rust_eze::walls::Obstacle;
+
+use common::{clear_enemies, headless_app, spawn_enemy_at};
+
+/// Modelos de teste: a central ganha uma parede a leste do centro.
+const WALLED: &str = r#"(
+    templates: [
+        (name: "open", kinds: [Start, Normal, Treasure, Shop, Boss]),
+        (name: "wall", kinds: [Start], walls: [(center: (0.1, 0.0), size: (0.05, 0.3))]),
+    ],
+)"#;
+
+fn walled_app() -> App {
+    // Depois do início, que carrega assets/rooms.ron
+    let mut app = headless_app();
+    app.insert_resource(RoomTemplates::from_ron(WALLED).unwrap());
+    clear_enemies(&mut app);
+    let mut graph = RoomGraph::new();
+    graph.get_room_mut(RoomId::Central).unwrap().template = "wall".to_string();
+    app.insert_resource(graph);
+    app.update();
+    app
+}
+
+fn wall_rect(app: &mut App) -> Rect {
+    let (transform, obstacle) = app.world.query::<(&Transform, &Obstacle)>().single(&app.world);
+    obstacle.rect(transform.translation.truncate())
+}
+
+#[test]
+fn shipped_templates_are_valid() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(ROOM_TEMPLATES_PATH);
+    let templates = RoomTemplates::load(&path).expect("assets/rooms.ron deveria ser válido");
+    assert_eq!(templates, RoomTemplates::default());
+}
+
+#[test]
+fn blocks_over_the_center_or_a_door_are_rejected() {
+    let err = RoomTemplates::from_ron(
+        r#"(templates: [
+            (name: "open", kinds: [Start, Normal, Treasure, Shop, Boss]),
+            (name: "bad", walls: [(center: (0.0, 0.0), size: (0.1, 0.1))]),
+        ])"#,
+    )
+    .unwrap_err();
+    assert!(matches!(&err, RoomTemplatesError::Invalid { template, .. } if template == "bad"), "{err}");
+
+    // Um buraco na frente da porta leste fecha a passagem...
+    let door_pit = r#"(name: "bad", pits: [(center: (0.45, 0.0), size: (0.05, 0.1))]DOORS)"#;
+    let with_doors = |doors: &str| {
+        format!(
+            r#"(templates: [(name: "open", kinds: [Start, Normal, Treasure, Shop, Boss]), {}])"#,
+            door_pit.replace("DOORS", doors)
+        )
+    };
+    assert!(RoomTemplates::from_ron(&with_doors("")).is_err());
+    // ...a não ser que o modelo não tenha porta desse lado
+    assert!(RoomTemplates::from_ron(&with_doors(", doors: [North, South, West]")).is_ok());
+}
+
+#[test]
+fn every_room_kind_needs_a_template_with_all_doors() {
+    let err = RoomTemplates::from_ron(r#"(templates: [(name: "open", kinds: [Start, Normal, Treasure, Shop])])"#)
+        .unwrap_err();
+    assert!(err.to_string().contains("Boss"), "{err}");
+}
+
+#[test]
+fn assigned_templates_fit_each_room() {
+    let templates = RoomTemplates::default();
+    for seed in 0..20 {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let mut graph = generate_floor(&FloorTuning::default(), &mut rng);
+        templates.assign(&mut graph, &mut rng);
+        for room in graph.rooms() {
+            let template = templates.get(&room.template).unwrap();
+            assert!(template.fits(room), "semente {seed}: {:?} com {}", room.id, room.template);
+        }
+    }
+}
+
+#[test]
+fn player_and_enemies_do_not_walk_through_interior_walls() {
+    let mut app = walled_app();
+    let wall = wall_rect(&mut app);
+    let tuning = app.world.resource::<GameTuning>().clone();
+
+    // Inimigo do outro lado da parede, parado de frente para o jogador
+    let enemy = spawn_enemy_at(&mut app, Vec2::new(wall.max.x + 60.0, 0.0));
+    app.world.resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::KeyD);
+    for _ in 0..10 {
+        app.update();
+    }
+
+    let player = app
+        .world
+        .query_filtered::<&Transform, With<Player>>()
+        .single(&app.world)
+        .translation;
+    // Chegou até a parede, mas não passou dela
+    let stop = wall.min.x - tuning.player_size;
+    assert!((player.x - stop).abs() < 1.0, "jogador em {player}, parede em {stop}");
+
+    let enemy_x = app.world.get::<Transform>(enemy).unwrap().translation.x;
+    let enemy_radius = app.world.get::<Collider>(enemy).unwrap().radius();
+    assert!(enemy_x >= wall.max.x + enemy_radius - 0.01, "inimigo em {enemy_x}");
+}
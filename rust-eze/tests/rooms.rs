@@ -0,0 +1,125 @@
+mod common;
+
+use bevy::prelude::*;
+use rust_eze::arena::ArenaSize;
+use rust_eze::config::GameTuning;
+use rust_eze::player::Player;
+use rust_eze::projectiles::powerups::PowerUp;
+use rust_eze::rooms::{CurrentRoom, Direction, Door, RoomCleared, RoomGraph, RoomId, RoomMember};
+
+use common::{clear_enemies, headless_app, player_position};
+
+/// Coloca o jogador encostado na parede do lado dado, no meio da abertura.
+fn walk_into_wall(app: &mut App, direction: Direction) {
+    let arena = *app.world.resource::<ArenaSize>();
+    let tuning = app.world.resource::<GameTuning>().clone();
+    let bounds = arena.bounds(tuning.wall_thickness, tuning.player_size);
+    let normal = direction.as_vec2();
+    let position = normal * bounds.dot(normal.abs());
+
+    let mut transform = app
+        .world
+        .query_filtered::<&mut Transform, With<Player>>()
+        .single_mut(&mut app.world);
+    transform.translation = position.extend(0.0);
+
+    for _ in 0..3 {
+        app.update();
+    }
+}
+
+fn doors(app: &mut App) -> Vec<(Direction, RoomId)> {
+    app.world
+        .query::<&Door>()
+        .iter(&app.world)
+        .map(|door| (door.direction, door.room_id))
+        .collect()
+}
+
+#[test]
+fn doors_lead_both_ways_in_every_direction() {
+    for direction in Direction::ALL {
+        let mut app = headless_app();
+        clear_enemies(&mut app);
+
+        // Um andar só com a sala central, para abrir a porta em qualquer direção
+        let mut graph = RoomGraph::new();
+        let neighbor = graph.add_room(RoomId::Central, direction).unwrap();
+        app.insert_resource(graph);
+        app.update();
+        assert!(doors(&mut app).contains(&(direction, neighbor)), "sem porta para {direction:?}");
+
+        walk_into_wall(&mut app, direction);
+        let current = app.world.resource::<CurrentRoom>();
+        assert_eq!(current.id, neighbor, "indo para {direction:?}");
+        assert_eq!(current.entered_from, Some(direction.opposite()));
+        // O jogador aparece do lado da porta por onde entrou
+        let position = player_position(&mut app).truncate();
+        assert!(position.dot(direction.opposite().as_vec2()) > 0.0, "{direction:?}: {position}");
+        assert!(doors(&mut app).contains(&(direction.opposite(), RoomId::Central)));
+
+        // A sala nova tem inimigos: só dá para voltar depois de limpá-la
+        clear_enemies(&mut app);
+        app.update();
+        walk_into_wall(&mut app, direction.opposite());
+        assert_eq!(app.world.resource::<CurrentRoom>().id, RoomId::Central, "voltando de {direction:?}");
+    }
+}
+
+#[test]
+fn walls_without_doors_do_not_change_room() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+
+    let closed = {
+        let graph = app.world.resource::<RoomGraph>();
+        let room = graph.get_room(RoomId::Central).unwrap();
+        Direction::ALL.into_iter().find(|direction| !room.has_connection(*direction))
+    };
+    let Some(closed) = closed else {
+        return; // Todas as direções já têm porta
+    };
+
+    walk_into_wall(&mut app, closed);
+    assert_eq!(app.world.resource::<CurrentRoom>().id, RoomId::Central);
+}
+
+#[test]
+fn doors_stay_locked_until_the_last_enemy_dies() {
+    let mut app = headless_app();
+    let mut graph = RoomGraph::new();
+    let neighbor = graph.add_room(RoomId::Central, Direction::East).unwrap();
+    app.insert_resource(graph);
+    app.update();
+    let mut cleared = app.world.resource::<Events<RoomCleared>>().get_reader();
+
+    // Com inimigos vivos a porta fica trancada e encostar nela não faz nada
+    let locked = |app: &mut App| app.world.query::<&Door>().iter(&app.world).all(|door| door.locked);
+    assert!(locked(&mut app));
+    walk_into_wall(&mut app, Direction::East);
+    assert_eq!(app.world.resource::<CurrentRoom>().id, RoomId::Central);
+    assert_eq!(cleared.read(app.world.resource::<Events<RoomCleared>>()).count(), 0);
+
+    clear_enemies(&mut app);
+    app.update();
+    let events: Vec<RoomId> = cleared
+        .read(app.world.resource::<Events<RoomCleared>>())
+        .map(|event| event.room)
+        .collect();
+    assert_eq!(events, vec![RoomId::Central]);
+    assert!(!locked(&mut app));
+    assert!(app.world.resource::<RoomGraph>().get_room(RoomId::Central).unwrap().cleared);
+
+    // A recompensa aparece na sala limpa
+    let rewards = app
+        .world
+        .query_filtered::<&RoomMember, With<PowerUp>>()
+        .iter(&app.world)
+        .filter(|member| member.0 == RoomId::Central)
+        .count();
+    assert_eq!(rewards, 1);
+
+    // O jogador continua encostado na porta, agora aberta
+    app.update();
+    assert_eq!(app.world.resource::<CurrentRoom>().id, neighbor);
+}
@@ -0,0 +1,189 @@
+mod common;
+
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use rust_eze::arena::ArenaSize;
+use rust_eze::combat::Health;
+use rust_eze::config::GameTuning;
+use rust_eze::enemies::Enemy;
+use rust_eze::lives::Lives;
+use rust_eze::player::Player;
+use rust_eze::projectiles::powerups::PlayerPowerUpState;
+use rust_eze::rng::SeedOverride;
+use rust_eze::rooms::{CurrentRoom, Direction, RoomGraph, RoomId};
+use rust_eze::save::{SaveError, SaveFile, SaveGame, SAVE_VERSION};
+use rust_eze::state::AppState;
+use rust_eze::weapons::{Arsenal, Weapon, WeaponRegistry};
+use rust_eze::scoring::Score;
+
+use common::{clear_enemies, headless_app, headless_app_with, lives, player_position, state};
+
+/// Arquivo de save só deste teste, apagado no fim.
+struct TempSave(PathBuf);
+
+impl TempSave {
+    fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("rust-eze-{name}-{}.ron", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        TempSave(path)
+    }
+
+    fn save_file(&self) -> SaveFile {
+        SaveFile {
+            path: self.0.clone(),
+            resume: true,
+        }
+    }
+}
+
+impl Drop for TempSave {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Uma partida no meio: jogador fora do centro, pontos, uma vida a menos,
+/// power-up ativo, um inimigo ferido e a espingarda com um tiro a menos.
+fn mid_run_app(seed: u64) -> App {
+    let mut app = headless_app_with(|app| {
+        app.insert_resource(SeedOverride(seed));
+    });
+    app.world.resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::KeyW);
+    for _ in 0..3 {
+        app.update();
+    }
+    app.world.resource_mut::<ButtonInput<KeyCode>>().release(KeyCode::KeyW);
+
+    app.world.resource_mut::<Score>().value = 1234;
+    app.world.resource_mut::<PlayerPowerUpState>().faster_projectile_timer = Some(Timer::from_seconds(4.0, TimerMode::Once));
+    app.world.query::<&mut Lives>().single_mut(&mut app.world).lose_life();
+    let enemy = app.world.query_filtered::<Entity, With<Enemy>>().iter(&app.world).next().unwrap();
+    app.world.get_mut::<Health>(enemy).unwrap().current = 0.5;
+
+    let shotgun = app.world.resource::<WeaponRegistry>().get("shotgun").unwrap().clone();
+    let (mut weapon, mut arsenal) = app
+        .world
+        .query_filtered::<(&mut Weapon, &mut Arsenal), With<Player>>()
+        .single_mut(&mut app.world);
+    arsenal.swap(&mut weapon, &shotgun);
+    weapon.update(0.0, true);
+    app
+}
+
+fn weapons(app: &mut App) -> (Weapon, Arsenal) {
+    let (weapon, arsenal) = app.world.query_filtered::<(&Weapon, &Arsenal), With<Player>>().single(&app.world);
+    (weapon.clone(), arsenal.clone())
+}
+
+fn enemies(app: &mut App) -> Vec<(String, Vec2, f32)> {
+    let mut enemies: Vec<(String, Vec2, f32)> = app
+        .world
+        .query::<(&Enemy, &Transform, &Health)>()
+        .iter(&app.world)
+        .map(|(enemy, transform, health)| (enemy.kind.clone(), transform.translation.xy(), health.current))
+        .collect();
+    enemies.sort_by(|a, b| a.1.x.total_cmp(&b.1.x));
+    enemies
+}
+
+#[test]
+fn save_survives_a_round_trip_through_ron() {
+    let mut app = mid_run_app(11);
+    let save = SaveGame::capture(&mut app.world);
+    assert_eq!(save.version, SAVE_VERSION);
+    assert_eq!(save.lives, 2);
+
+    let text = save.to_ron().unwrap();
+    assert_eq!(SaveGame::from_ron(&text).unwrap(), save);
+}
+
+#[test]
+fn loading_a_save_restores_the_run_in_another_app() {
+    let mut original = mid_run_app(11);
+    let save = SaveGame::capture(&mut original.world);
+
+    // Outra semente, outro andar: tudo tem que vir do save
+    let mut restored = headless_app_with(|app| {
+        app.insert_resource(SeedOverride(99));
+    });
+    SaveGame::from_ron(&save.to_ron().unwrap()).unwrap().apply(&mut restored.world);
+
+    assert_eq!(SaveGame::capture(&mut restored.world), save);
+    assert_eq!(enemies(&mut restored), enemies(&mut original));
+    assert_eq!(player_position(&mut restored), player_position(&mut original));
+    assert_eq!(lives(&mut restored), 2);
+    assert_eq!(weapons(&mut restored), weapons(&mut original));
+    assert_eq!(weapons(&mut restored).0.ammo(), Some(5));
+
+    // Daqui em diante as duas partidas seguem iguais
+    for app in [&mut original, &mut restored] {
+        app.world.resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::KeyD);
+        for _ in 0..5 {
+            app.update();
+        }
+    }
+    assert_eq!(SaveGame::capture(&mut restored.world), SaveGame::capture(&mut original.world));
+}
+
+#[test]
+fn unknown_versions_and_garbage_are_rejected() {
+    let mut app = headless_app();
+    let text = SaveGame::capture(&mut app.world).to_ron().unwrap();
+
+    let future = text.replacen(&format!("version:{SAVE_VERSION}"), "version:99", 1);
+    assert_ne!(future, text, "o campo de versão deveria estar no texto");
+    assert!(matches!(SaveGame::from_ron(&future), Err(SaveError::UnsupportedVersion(99))));
+    let unversioned = text.replacen(&format!("version:{SAVE_VERSION}"), "version:0", 1);
+    assert!(matches!(SaveGame::from_ron(&unversioned), Err(SaveError::UnsupportedVersion(0))));
+    assert!(matches!(SaveGame::from_ron("(lives: 3)"), Err(SaveError::Parse(_))));
+}
+
+#[test]
+fn room_transitions_save_and_startup_resumes() {
+    let file = TempSave::new("transition");
+    let mut app = headless_app_with(|app| {
+        app.insert_resource(file.save_file());
+    });
+    assert!(!file.0.exists(), "nada foi pedido ainda");
+
+    // Sala central limpa, com uma vizinha a leste
+    clear_enemies(&mut app);
+    let mut graph = RoomGraph::new();
+    let east = graph.add_room(RoomId::Central, Direction::East).unwrap();
+    app.insert_resource(graph);
+    app.update();
+    let bounds = {
+        let arena = *app.world.resource::<ArenaSize>();
+        let tuning = app.world.resource::<GameTuning>();
+        arena.bounds(tuning.wall_thickness, tuning.player_size)
+    };
+    app.world
+        .query_filtered::<&mut Transform, With<Player>>()
+        .single_mut(&mut app.world)
+        .translation = Vec3::new(bounds.x, 0.0, 0.0);
+    for _ in 0..3 {
+        app.update();
+    }
+    assert_eq!(app.world.resource::<CurrentRoom>().id, east);
+    assert!(file.0.exists(), "a troca de sala deveria salvar");
+    assert!(!file.0.with_extension("ron.tmp").exists(), "o temporário vira o save");
+
+    let resumed = headless_app_with(|app| {
+        app.insert_resource(file.save_file());
+    });
+    assert_eq!(resumed.world.resource::<CurrentRoom>().id, east);
+    assert_eq!(resumed.world.resource::<RoomGraph>().len(), 2);
+    assert_eq!(state(&resumed), AppState::Playing);
+}
+
+#[test]
+fn a_corrupted_save_starts_a_new_run() {
+    let file = TempSave::new("corrupted");
+    std::fs::write(&file.0, "isto não é um save").unwrap();
+
+    let app = headless_app_with(|app| {
+        app.insert_resource(file.save_file());
+    });
+    assert_eq!(app.world.resource::<CurrentRoom>().id, RoomId::Central);
+}
@@ -0,0 +1,175 @@
+mod common;
+
+use bevy::prelude::*;
+use rust_eze::combat::DamageEvent;
+use rust_eze::config::GameTuning;
+use rust_eze::enemies::Enemy;
+use rust_eze::game::PlayerDamaged;
+use rust_eze::projectiles::powerups::{spawn_powerup, PowerUpType};
+use rust_eze::rooms::{CurrentRoom, RoomGraph};
+use rust_eze::scoring::{Score, ScorePopup};
+use rust_eze::state::AppState;
+use rust_eze::ui::GameOverText;
+
+use common::{clear_enemies, headless_app, player_position, spawn_kind_at};
+
+/// App sem pontos por tempo vivo, para as contas fecharem.
+fn scoring_app() -> App {
+    let mut app = headless_app();
+    app.world.resource_mut::<GameTuning>().scoring.survival_per_second = 0.0;
+    app
+}
+
+/// Mata de uma vez os inimigos dados.
+fn kill(app: &mut App, enemies: &[Entity]) {
+    for &enemy in enemies {
+        app.world.send_event(DamageEvent::hit(enemy, 100.0));
+    }
+    app.update();
+}
+
+fn all_enemies(app: &mut App) -> Vec<Entity> {
+    app.world.query_filtered::<Entity, With<Enemy>>().iter(&app.world).collect()
+}
+
+fn spawn_pickup_at(app: &mut App, powerup_type: PowerUpType, position: Vec2) {
+    let room = app.world.resource::<CurrentRoom>().id;
+    app.world.resource_scope(|world, mut meshes: Mut<Assets<Mesh>>| {
+        world.resource_scope(|world, mut materials: Mut<Assets<ColorMaterial>>| {
+            let mut queue = bevy::ecs::system::CommandQueue::default();
+            let mut commands = Commands::new(&mut queue, world);
+            spawn_powerup(&mut commands, powerup_type, position, room, &mut meshes, &mut materials);
+            queue.apply(world);
+        });
+    });
+}
+
+#[test]
+fn kills_are_weighted_by_enemy_kind() {
+    let mut app = scoring_app();
+    app.world.resource_mut::<GameTuning>().scoring.combo_step = 0.0;
+    let charger = spawn_kind_at(&mut app, "charger", Vec2::new(300.0, 200.0));
+    let splitling = spawn_kind_at(&mut app, "splitling", Vec2::new(-300.0, 200.0));
+
+    kill(&mut app, &[charger, splitling]);
+    let score = app.world.resource::<Score>();
+    // 100 × 2.0 + 100 × 0.3; os outros inimigos seguem vivos
+    assert_eq!(score.breakdown.kills, 230);
+    assert_eq!(score.breakdown.rooms, 0);
+    assert_eq!(score.value, 230);
+}
+
+#[test]
+fn kill_streaks_raise_the_combo_until_it_decays_or_the_player_is_hit() {
+    let mut app = scoring_app();
+    // Só os inimigos do teste: os da sala poderiam alcançar o jogador e zerar
+    // o combo. A sala conta como limpa para o bônus dela não entrar na conta.
+    clear_enemies(&mut app);
+    let room = app.world.resource::<CurrentRoom>().id;
+    if let Some(room) = app.world.resource_mut::<RoomGraph>().bypass_change_detection().get_room_mut(room) {
+        room.cleared = true;
+    }
+    {
+        let mut tuning = app.world.resource_mut::<GameTuning>();
+        tuning.scoring.combo_step = 0.5;
+        tuning.scoring.combo_max = 2.0;
+        tuning.scoring.combo_grace = 5.0;
+    }
+    let first = spawn_kind_at(&mut app, "chaser", Vec2::new(300.0, 200.0));
+    let second = spawn_kind_at(&mut app, "chaser", Vec2::new(-300.0, 200.0));
+    let third = spawn_kind_at(&mut app, "chaser", Vec2::new(300.0, -200.0));
+
+    // 100 a ×1, 150 a ×1.5, 200 a ×2 (o máximo)
+    kill(&mut app, &[first, second, third]);
+    let score = app.world.resource::<Score>();
+    assert_eq!(score.value, 450);
+    assert_eq!(score.breakdown.kills, 300);
+    assert_eq!(score.breakdown.combo, 150);
+    assert_eq!(score.combo.multiplier, 2.0);
+
+    // Sem matar, o combo cai até 1
+    {
+        let mut tuning = app.world.resource_mut::<GameTuning>();
+        tuning.scoring.combo_grace = 0.0;
+        tuning.scoring.combo_decay = 5.0;
+    }
+    for _ in 0..3 {
+        app.update();
+    }
+    assert_eq!(app.world.resource::<Score>().combo.multiplier, 1.0);
+
+    // Ser atingido zera o combo na hora
+    app.world.resource_mut::<GameTuning>().scoring.combo_decay = 0.0;
+    let fourth = spawn_kind_at(&mut app, "chaser", Vec2::new(-300.0, -200.0));
+    kill(&mut app, &[fourth]);
+    assert_eq!(app.world.resource::<Score>().combo.multiplier, 1.5);
+    app.world.send_event(PlayerDamaged { amount: 1, source: Vec2::ZERO });
+    app.update();
+    assert_eq!(app.world.resource::<Score>().combo.multiplier, 1.0);
+}
+
+#[test]
+fn clearing_a_room_pays_a_bonus_and_more_without_damage() {
+    let mut app = scoring_app();
+    let enemies = all_enemies(&mut app);
+    kill(&mut app, &enemies);
+    let score = app.world.resource::<Score>();
+    assert_eq!(score.breakdown.rooms, 250);
+    assert_eq!(score.breakdown.no_damage, 500);
+
+    let mut app = scoring_app();
+    app.world.send_event(PlayerDamaged { amount: 1, source: Vec2::ZERO });
+    let enemies = all_enemies(&mut app);
+    kill(&mut app, &enemies);
+    let score = app.world.resource::<Score>();
+    assert_eq!(score.breakdown.rooms, 250);
+    assert_eq!(score.breakdown.no_damage, 0, "o jogador foi atingido nesta sala");
+}
+
+#[test]
+fn pickups_score_points_and_spawn_a_popup() {
+    let mut app = scoring_app();
+    let position = player_position(&mut app).xy();
+    spawn_pickup_at(&mut app, PowerUpType::FasterProjectile, position);
+    app.update();
+
+    assert_eq!(app.world.resource::<Score>().breakdown.pickups, 50);
+    let popups: Vec<String> = app
+        .world
+        .query_filtered::<&Text, With<ScorePopup>>()
+        .iter(&app.world)
+        .map(|text| text.sections[0].value.clone())
+        .collect();
+    assert_eq!(popups, vec!["+50".to_string()]);
+
+    // Os números somem sozinhos
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_eq!(app.world.query::<&ScorePopup>().iter(&app.world).count(), 0);
+}
+
+#[test]
+fn game_over_shows_a_breakdown_that_adds_up_to_the_score() {
+    let mut app = headless_app();
+    let enemies = all_enemies(&mut app);
+    kill(&mut app, &enemies);
+    for _ in 0..5 {
+        app.update();
+    }
+    let score = app.world.resource::<Score>().clone();
+    assert!(score.breakdown.survival > 0);
+    assert!(score.breakdown.kills > 0);
+    assert_eq!(score.breakdown.total(), score.value);
+
+    app.world.resource_mut::<NextState<AppState>>().set(AppState::GameOver);
+    app.update();
+    let texts: Vec<String> = app
+        .world
+        .query_filtered::<&Text, With<GameOverText>>()
+        .iter(&app.world)
+        .map(|text| text.sections[0].value.clone())
+        .collect();
+    let breakdown = texts.iter().find(|text| text.contains("Total")).expect("resumo da pontuação");
+    assert!(breakdown.contains(&score.value.to_string()));
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy::transform::TransformSystem;
+use rust_eze::player::Player;
+use rust_eze::save::SaveGame;
+use rust_eze::scoring::Score;
+
+use common::{clear_enemies, headless_app, player_position};
+
+/// Posição do jogador como foi desenhada no último quadro.
+#[derive(Resource, Default)]
+struct Drawn(Vec3);
+
+fn record_drawn(query: Query<&Transform, With<Player>>, mut drawn: ResMut<Drawn>) {
+    if let Ok(transform) = query.get_single() {
+        drawn.0 = transform.translation;
+    }
+}
+
+fn set_frame_time(app: &mut App, frame: Duration) {
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(frame));
+}
+
+/// Anda para a direita por um segundo de jogo com quadros do tamanho dado.
+fn distance_walked_in_one_second(frame: Duration) -> f32 {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+    set_frame_time(&mut app, frame);
+
+    let start = player_position(&mut app).x;
+    app.world.resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::KeyD);
+    let frames = (Duration::from_secs(1).as_nanos() / frame.as_nanos()) as usize;
+    for _ in 0..frames {
+        app.update();
+    }
+    player_position(&mut app).x - start
+}
+
+#[test]
+fn movement_does_not_depend_on_frame_rate() {
+    let slow = distance_walked_in_one_second(Duration::from_millis(100));
+    let fast = distance_walked_in_one_second(Duration::from_millis(5));
+
+    // No máximo um passo fixo de diferença (300 px/s a 60 Hz = 5 px).
+    assert!((slow - fast).abs() <= 5.5, "lento {slow} x rápido {fast}");
+}
+
+#[test]
+fn score_grows_at_high_frame_rate() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+    set_frame_time(&mut app, Duration::from_millis(2));
+
+    // Só os pontos por tempo vivo: a recompensa da sala limpa também pontua
+    let before = app.world.resource::<Score>().breakdown.survival;
+    for _ in 0..500 {
+        app.update();
+    }
+
+    let gained = app.world.resource::<Score>().breakdown.survival - before;
+    assert!((9..=11).contains(&gained), "ganhou {gained} pontos em 1 s");
+}
+
+#[test]
+fn teleports_are_drawn_at_the_new_position() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+    // Quadros menores que o passo fixo: o desenho fica entre dois passos
+    set_frame_time(&mut app, Duration::from_millis(5));
+    app.init_resource::<Drawn>()
+        .add_systems(PostUpdate, record_drawn.after(TransformSystem::TransformPropagate));
+    app.update();
+
+    let mut save = SaveGame::capture(&mut app.world);
+    save.player_position = Vec2::new(200.0, -150.0);
+    save.apply(&mut app.world);
+    app.update();
+
+    assert_eq!(app.world.resource::<Drawn>().0.truncate(), Vec2::new(200.0, -150.0));
+    assert_eq!(player_position(&mut app).truncate(), Vec2::new(200.0, -150.0));
+}
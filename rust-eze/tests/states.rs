@@ -0,0 +1,139 @@
+mod common;
+
+use bevy::prelude::*;
+use rust_eze::RustEzeHeadlessPlugin;
+use rust_eze::arena::ArenaSize;
+use rust_eze::config::GameTuning;
+use rust_eze::enemies::Enemy;
+use rust_eze::lives::Lives;
+use rust_eze::player::Player;
+use rust_eze::projectiles::powerups::{PlayerPowerUpState, RotatingCircle};
+use rust_eze::rng::GameRng;
+use rust_eze::rooms::{CurrentRoom, Door, RoomGraph, RoomId, RoomMember};
+use rust_eze::state::AppState;
+use rust_eze::scoring::Score;
+
+use common::{clear_enemies, headless_app, lives, player_position, spawn_enemy_at, state, tap};
+
+#[test]
+fn game_starts_in_main_menu_and_enter_starts_playing() {
+    let mut app = App::new();
+    app.add_plugins(RustEzeHeadlessPlugin);
+    app.update();
+    assert_eq!(state(&app), AppState::MainMenu);
+
+    tap(&mut app, KeyCode::Enter);
+    assert_eq!(state(&app), AppState::Playing);
+}
+
+#[test]
+fn escape_pauses_and_freezes_gameplay() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+
+    tap(&mut app, KeyCode::Escape);
+    assert_eq!(state(&app), AppState::Paused);
+
+    let start = player_position(&mut app);
+    app.world.resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::KeyD);
+    app.update();
+    app.update();
+    assert_eq!(player_position(&mut app), start);
+
+    app.world.resource_mut::<ButtonInput<KeyCode>>().release(KeyCode::KeyD);
+    tap(&mut app, KeyCode::Escape);
+    assert_eq!(state(&app), AppState::Playing);
+}
+
+#[test]
+fn losing_last_life_goes_to_game_over_and_enter_restarts() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+    app.world.resource_mut::<Score>().value = 500;
+    // Power-ups ativos não passam para a partida seguinte
+    app.world.resource_mut::<PlayerPowerUpState>().faster_projectile_timer =
+        Some(Timer::from_seconds(60.0, TimerMode::Once));
+    app.world.spawn((
+        RotatingCircle { angle: 0.0, speed: 2.0, radius: 50.0, timer: Timer::from_seconds(60.0, TimerMode::Once) },
+        Transform::default(),
+    ));
+
+    // Última vida, com um ponto de vida só
+    *app.world.query::<&mut Lives>().single_mut(&mut app.world) = Lives::new(1, 1);
+    spawn_enemy_at(&mut app, Vec2::new(5.0, 0.0));
+    app.update();
+    app.update();
+    assert_eq!(state(&app), AppState::GameOver);
+    assert_eq!(lives(&mut app), 0);
+
+    // O primeiro Enter confirma o nome do recorde, o segundo recomeça
+    tap(&mut app, KeyCode::Enter);
+    assert_eq!(state(&app), AppState::GameOver);
+    tap(&mut app, KeyCode::Enter);
+    assert_eq!(state(&app), AppState::Playing);
+    assert_eq!(lives(&mut app), 3);
+    assert!(app.world.resource::<Score>().value < 500);
+    assert_eq!(player_position(&mut app), Vec3::ZERO);
+    assert_eq!(*app.world.resource::<PlayerPowerUpState>(), PlayerPowerUpState::default());
+    assert_eq!(app.world.query::<&RotatingCircle>().iter(&app.world).count(), 0);
+
+    let enemies = app.world.query::<&Enemy>().iter(&app.world).count();
+    assert_eq!(enemies, 3);
+}
+
+/// Posição e tipo de cada sala, para comparar andares.
+fn floor_layout(app: &App) -> Vec<(IVec2, String)> {
+    let graph = app.world.resource::<RoomGraph>();
+    graph
+        .room_ids()
+        .into_iter()
+        .map(|id| {
+            let room = graph.get_room(id).unwrap();
+            (room.position, format!("{:?}", room.kind))
+        })
+        .collect()
+}
+
+#[test]
+fn restarting_after_dying_in_another_room_starts_a_fresh_floor() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+    app.update();
+    let first_seed = app.world.resource::<GameRng>().seed();
+    let first_floor = floor_layout(&app);
+
+    // Pela porta aberta da sala inicial até a vizinha
+    let door = app.world.query::<&Door>().iter(&app.world).next().expect("a sala inicial tem porta").direction;
+    let arena = *app.world.resource::<ArenaSize>();
+    let tuning = app.world.resource::<GameTuning>().clone();
+    let bounds = arena.bounds(tuning.wall_thickness, tuning.player_size);
+    let normal = door.as_vec2();
+    let mut transform = app.world.query_filtered::<&mut Transform, With<Player>>().single_mut(&mut app.world);
+    transform.translation = (normal * bounds.dot(normal.abs())).extend(0.0);
+    app.update();
+    app.update();
+    assert_ne!(app.world.resource::<CurrentRoom>().id, RoomId::Central);
+    assert!(app.world.resource::<RoomGraph>().get_room(RoomId::Central).unwrap().saved.is_some());
+
+    // Morre ali
+    *app.world.query::<&mut Lives>().single_mut(&mut app.world) = Lives::new(1, 1);
+    let at = player_position(&mut app).xy();
+    spawn_enemy_at(&mut app, at + Vec2::X * 5.0);
+    app.update();
+    app.update();
+    assert_eq!(state(&app), AppState::GameOver);
+    tap(&mut app, KeyCode::Enter);
+    tap(&mut app, KeyCode::Enter);
+    assert_eq!(state(&app), AppState::Playing);
+
+    // Andar novo, sem nada da partida anterior, começando na sala inicial
+    assert_ne!(app.world.resource::<GameRng>().seed(), first_seed);
+    assert_ne!(floor_layout(&app), first_floor);
+    assert_eq!(*app.world.resource::<CurrentRoom>(), CurrentRoom::default());
+    let graph = app.world.resource::<RoomGraph>();
+    assert!(graph.rooms().all(|room| room.saved.is_none()));
+    assert!(graph.rooms().all(|room| room.visited == (room.id == RoomId::Central)));
+    assert!(!graph.get_room(RoomId::Central).unwrap().cleared);
+    assert!(app.world.query::<&RoomMember>().iter(&app.world).all(|member| member.0 == RoomId::Central));
+    assert_eq!(player_position(&mut app), Vec3::ZERO);
+}
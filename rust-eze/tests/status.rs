@@ -0,0 +1,107 @@
+mod common;
+
+use bevy::prelude::*;
+use rust_eze::combat::Health;
+use rust_eze::config::GameTuning;
+use rust_eze::enemies::Enemy;
+use rust_eze::status::{Burning, Chilled, Element, ElementHit};
+
+use common::{clear_enemies, headless_app, spawn_enemy_at};
+
+/// App sem inimigos aleatórios.
+fn status_app() -> App {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+    app
+}
+
+/// Inimigo resistente e parado, para o teste controlar as distâncias.
+fn dummy_at(app: &mut App, position: Vec2) -> Entity {
+    let enemy = spawn_enemy_at(app, position);
+    *app.world.get_mut::<Health>(enemy).unwrap() = Health::new(10.0);
+    app.world.get_mut::<Enemy>(enemy).unwrap().speed = 0.0;
+    enemy
+}
+
+fn element_hit(app: &mut App, target: Entity, element: Element) {
+    let position = app.world.get::<Transform>(target).unwrap().translation.xy();
+    app.world.send_event(ElementHit { target, element, position });
+    app.update();
+}
+
+fn health(app: &App, entity: Entity) -> f32 {
+    app.world.get::<Health>(entity).unwrap().current
+}
+
+fn make_immune(app: &mut App, entity: Entity, element: Element) {
+    app.world.get_mut::<Enemy>(entity).unwrap().immunities.push(element);
+}
+
+#[test]
+fn fire_stacks_up_to_the_limit_and_burns_over_time() {
+    let mut app = status_app();
+    let max_stacks = app.world.resource::<GameTuning>().status.burn_max_stacks;
+    let enemy = dummy_at(&mut app, Vec2::new(300.0, 0.0));
+
+    for _ in 0..max_stacks + 2 {
+        element_hit(&mut app, enemy, Element::Fire);
+    }
+    assert_eq!(app.world.get::<Burning>(enemy).unwrap().stacks, max_stacks);
+    assert!(health(&app, enemy) < 10.0);
+
+    let immune = dummy_at(&mut app, Vec2::new(-300.0, 0.0));
+    make_immune(&mut app, immune, Element::Fire);
+    element_hit(&mut app, immune, Element::Fire);
+    assert!(app.world.get::<Burning>(immune).is_none());
+}
+
+#[test]
+fn ice_slows_enemy_movement() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+    let chilled = spawn_enemy_at(&mut app, Vec2::new(300.0, 0.0));
+    let normal = spawn_enemy_at(&mut app, Vec2::new(-300.0, 0.0));
+
+    element_hit(&mut app, chilled, Element::Ice);
+    assert!(app.world.get::<Chilled>(chilled).is_some());
+
+    let chilled_x = app.world.get::<Transform>(chilled).unwrap().translation.x;
+    let normal_x = app.world.get::<Transform>(normal).unwrap().translation.x;
+    assert!(300.0 - chilled_x < normal_x + 300.0, "congelado deveria andar menos");
+}
+
+#[test]
+fn electric_chains_to_nearby_enemies_only() {
+    let mut app = status_app();
+    let chain_damage = app.world.resource::<GameTuning>().status.chain_damage;
+    let target = dummy_at(&mut app, Vec2::new(300.0, 0.0));
+    let near = dummy_at(&mut app, Vec2::new(350.0, 0.0));
+    let immune = dummy_at(&mut app, Vec2::new(300.0, 100.0));
+    let far = dummy_at(&mut app, Vec2::new(-300.0, 0.0));
+    make_immune(&mut app, immune, Element::Electric);
+
+    element_hit(&mut app, target, Element::Electric);
+
+    assert_eq!(health(&app, near), 10.0 - chain_damage);
+    assert_eq!(health(&app, immune), 10.0);
+    assert_eq!(health(&app, far), 10.0);
+    assert_eq!(health(&app, target), 10.0);
+}
+
+#[test]
+fn explosion_damages_everything_in_radius() {
+    let mut app = status_app();
+    let blast_damage = app.world.resource::<GameTuning>().status.blast_damage;
+    let target = dummy_at(&mut app, Vec2::new(300.0, 0.0));
+    let near = dummy_at(&mut app, Vec2::new(330.0, 0.0));
+    let immune = dummy_at(&mut app, Vec2::new(300.0, -30.0));
+    let far = dummy_at(&mut app, Vec2::new(450.0, 0.0));
+    make_immune(&mut app, immune, Element::Explosive);
+
+    element_hit(&mut app, target, Element::Explosive);
+
+    assert_eq!(health(&app, target), 10.0 - blast_damage);
+    assert_eq!(health(&app, near), 10.0 - blast_damage);
+    assert_eq!(health(&app, immune), 10.0);
+    assert_eq!(health(&app, far), 10.0);
+}
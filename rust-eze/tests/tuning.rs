@@ -0,0 +1,33 @@
+use std::path::Path;
+
+use rust_eze::config::{GameTuning, TuningError, TUNING_PATH};
+
+#[test]
+fn shipped_tuning_file_matches_defaults() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(TUNING_PATH);
+    let tuning = GameTuning::load(&path).expect("assets/tuning.ron deveria ser válido");
+    assert_eq!(tuning, GameTuning::default());
+}
+
+#[test]
+fn missing_fields_fall_back_to_defaults() {
+    let tuning = GameTuning::from_ron("(player_speed: 450.0)").unwrap();
+    assert_eq!(tuning.player_speed, 450.0);
+    assert_eq!(tuning.projectile_speed, GameTuning::default().projectile_speed);
+}
+
+#[test]
+fn invalid_values_are_rejected_with_field_name() {
+    let err = GameTuning::from_ron("(projectile_speed: -10.0)").unwrap_err();
+    assert!(matches!(err, TuningError::Invalid { field: "projectile_speed", .. }));
+    assert!(err.to_string().contains("projectile_speed"));
+
+    let err = GameTuning::from_ron("(max_lives: 0)").unwrap_err();
+    assert!(matches!(err, TuningError::Invalid { field: "max_lives", .. }));
+}
+
+#[test]
+fn unknown_fields_are_syntax_errors() {
+    let err = GameTuning::from_ron("(player_sped: 1.0)").unwrap_err();
+    assert!(matches!(err, TuningError::Parse(_)));
+}
@@ -0,0 +1,185 @@
+mod common;
+
+use std::path::Path;
+
+use bevy::prelude::*;
+use rust_eze::combat::Health;
+use rust_eze::player::Player;
+use rust_eze::projectiles::Projectile;
+use rust_eze::state::AppState;
+use rust_eze::weapons::{Arsenal, Weapon, WeaponKind, WeaponRegistry, WeaponsError, WEAPONS_PATH};
+
+use common::{clear_enemies, headless_app, spawn_enemy_at, state, tap};
+
+fn kind(name: &str) -> WeaponKind {
+    WeaponRegistry::default().get(name).unwrap_or_else(|| panic!("arma {name} não existe")).clone()
+}
+
+fn equip(app: &mut App, kind: WeaponKind) {
+    let mut weapon = app.world.query_filtered::<&mut Weapon, With<Player>>().single_mut(&mut app.world);
+    *weapon = Weapon::new(kind);
+}
+
+fn equipped(app: &mut App) -> String {
+    app.world.query_filtered::<&Weapon, With<Player>>().single(&app.world).kind.name.clone()
+}
+
+fn projectile_count(app: &mut App) -> usize {
+    app.world.query::<&Projectile>().iter(&app.world).count()
+}
+
+#[test]
+fn shipped_weapon_file_is_valid() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(WEAPONS_PATH);
+    let registry = WeaponRegistry::load(&path).expect("assets/weapons.ron deveria ser válido");
+    assert_eq!(registry, WeaponRegistry::default());
+    for name in ["pistol", "shotgun", "smg", "burst_rifle"] {
+        assert!(registry.get(name).is_some(), "falta a arma {name}");
+    }
+    assert_eq!(registry.starting_weapon().kind.name, "pistol");
+}
+
+#[test]
+fn fire_rate_limits_shots_and_bursts_finish_on_their_own() {
+    let mut pistol = Weapon::new(kind("pistol"));
+    assert_eq!(pistol.update(0.0, true), 1);
+    assert_eq!(pistol.update(0.1, true), 0, "5 tiros por segundo");
+    assert_eq!(pistol.update(0.15, true), 1);
+    assert_eq!(pistol.update(1.0, false), 0, "sem gatilho, sem tiro");
+    assert_eq!(pistol.ammo(), None);
+
+    let mut rifle = Weapon::new(kind("burst_rifle"));
+    assert_eq!(rifle.update(0.0, true), 1);
+    // O resto da rajada sai mesmo com o gatilho solto
+    assert_eq!(rifle.update(0.05, false), 0);
+    assert_eq!(rifle.update(0.05, false), 1);
+    assert_eq!(rifle.update(0.1, false), 1);
+    assert_eq!(rifle.update(0.1, false), 0);
+    assert_eq!(rifle.ammo(), Some(9));
+}
+
+#[test]
+fn empty_magazines_reload_before_firing_again() {
+    let mut shotgun = Weapon::new(kind("shotgun"));
+    // Pente cheio não recarrega
+    shotgun.start_reload();
+    assert!(!shotgun.is_reloading());
+
+    for _ in 0..6 {
+        assert_eq!(shotgun.update(1.0, true), 1);
+    }
+    assert_eq!(shotgun.ammo(), Some(0));
+    assert!(shotgun.is_reloading(), "recarrega sozinha ao esvaziar");
+    assert_eq!(shotgun.update(1.0, true), 0);
+    assert_eq!(shotgun.update(1.0, true), 1, "recarga de 1.5 s terminou");
+    assert_eq!(shotgun.ammo(), Some(5));
+
+    shotgun.start_reload();
+    assert!(shotgun.is_reloading());
+    assert_eq!(shotgun.update(1.0, true), 0);
+}
+
+#[test]
+fn shotgun_spreads_its_pellets_in_an_even_fan() {
+    let directions = kind("shotgun").shot_directions(Vec2::X);
+    assert_eq!(directions.len(), 5);
+    let angles: Vec<f32> = directions.iter().map(|direction| direction.to_angle().to_degrees()).collect();
+    for (angle, expected) in angles.iter().zip([-20.0, -10.0, 0.0, 10.0, 20.0]) {
+        assert!((angle - expected).abs() < 1e-3, "ângulos {angles:?}");
+    }
+    assert_eq!(kind("pistol").shot_directions(Vec2::Y), vec![Vec2::Y]);
+}
+
+#[test]
+fn invalid_weapons_are_rejected() {
+    let weapon = |name: &str, fire_rate: f32| {
+        format!(
+            "(name: \"{name}\", fire_rate: {fire_rate:?}, projectile_count: 1, spread: 0.0, burst_size: 1, \
+             burst_interval: 0.0, lifetime: 1.0, piercing: 0, damage: 1.0)"
+        )
+    };
+
+    let text = format!("(starting: \"a\", weapons: [{}])", weapon("a", 0.0));
+    let err = WeaponRegistry::from_ron(&text).unwrap_err();
+    assert!(matches!(err, WeaponsError::Invalid { ref weapon, .. } if weapon == "a"));
+
+    let text = format!("(starting: \"a\", weapons: [{}, {}])", weapon("a", 1.0), weapon("a", 2.0));
+    assert!(WeaponRegistry::from_ron(&text).unwrap_err().to_string().contains("repetido"));
+
+    let text = format!("(starting: \"laser\", weapons: [{}])", weapon("a", 1.0));
+    assert!(WeaponRegistry::from_ron(&text).unwrap_err().to_string().contains("laser"));
+}
+
+#[test]
+fn projectiles_expire_after_the_weapon_lifetime() {
+    let mut app = headless_app();
+    clear_enemies(&mut app);
+    equip(&mut app, kind("shotgun"));
+
+    tap(&mut app, KeyCode::ArrowUp);
+    assert_eq!(projectile_count(&mut app), 5);
+    for _ in 0..5 {
+        app.update();
+    }
+    assert_eq!(projectile_count(&mut app), 0, "alcance de 0.5 s");
+}
+
+#[test]
+fn piercing_projectiles_hit_the_next_enemy_in_line() {
+    for (piercing, expected) in [(0, 1), (1, 2)] {
+        let mut app = headless_app();
+        clear_enemies(&mut app);
+        // Dano pequeno para ninguém morrer
+        equip(&mut app, WeaponKind { piercing, damage: 0.01, ..kind("pistol") });
+        let enemies = [spawn_enemy_at(&mut app, Vec2::new(200.0, 0.0)), spawn_enemy_at(&mut app, Vec2::new(300.0, 0.0))];
+
+        tap(&mut app, KeyCode::ArrowRight);
+        for _ in 0..4 {
+            app.update();
+        }
+        let hit = enemies
+            .iter()
+            .filter(|&&enemy| {
+                let health = app.world.get::<Health>(enemy).unwrap();
+                health.current < health.max
+            })
+            .count();
+        assert_eq!(hit, expected, "com `piercing: {piercing}`");
+    }
+}
+
+#[test]
+fn switching_back_keeps_the_ammo_and_cooldown() {
+    let mut app = headless_app();
+    tap(&mut app, KeyCode::Tab);
+    assert_eq!(equipped(&mut app), "shotgun");
+    let mut shotgun = app.world.query_filtered::<&mut Weapon, With<Player>>().single_mut(&mut app.world);
+    assert_eq!(shotgun.update(0.0, true), 1);
+
+    // Dá a volta pelas quatro armas
+    for _ in 0..4 {
+        tap(&mut app, KeyCode::Tab);
+    }
+    assert_eq!(equipped(&mut app), "shotgun");
+    let shotgun = app.world.query_filtered::<&Weapon, With<Player>>().single(&app.world);
+    assert_eq!(shotgun.ammo(), Some(5), "o pente não enche na troca");
+    assert!(shotgun.to_saved().cooldown > 0.5, "a cadência só corre com a arma na mão");
+    let arsenal = app.world.query_filtered::<&Arsenal, With<Player>>().single(&app.world);
+    assert_eq!(arsenal.holstered().len(), 3);
+}
+
+#[test]
+fn weapons_switch_and_reset_on_restart() {
+    let mut app = headless_app();
+    assert_eq!(equipped(&mut app), "pistol");
+    tap(&mut app, KeyCode::Tab);
+    assert_eq!(equipped(&mut app), "shotgun");
+
+    app.world.resource_mut::<NextState<AppState>>().set(AppState::GameOver);
+    app.update();
+    // O primeiro Enter confirma o nome do recorde, o segundo recomeça
+    tap(&mut app, KeyCode::Enter);
+    tap(&mut app, KeyCode::Enter);
+    assert_eq!(state(&app), AppState::Playing);
+    assert_eq!(equipped(&mut app), "pistol");
+}